@@ -0,0 +1,79 @@
+//! Data kept between sessions, so that it is not fetched again every time kayak runs
+//!
+//! Currently this is only the list of every project on an index, which is large but rarely
+//! changes in ways that matter for suggesting a misspelled name. Each index's list is stored as
+//! a compressed zip file under the cache directory, named for a digest of the index url.
+use anyhow::Result;
+use ring::digest;
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// How long a list of projects is used before it is fetched from the index again
+pub const PROJECTS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The name of the only file within each cached zip
+const PROJECTS_ENTRY: &str = "projects.txt";
+
+/// Where data is cached between sessions
+///
+/// This is kayak under $XDG_CACHE_HOME, or under ~/.cache if that is not set
+pub fn default_dir() -> Option<PathBuf> {
+    let cache_home = env::var_os("XDG_CACHE_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))?;
+    Some(cache_home.join("kayak"))
+}
+
+/// Where the list of projects on index is cached within dir
+fn projects_path(dir: &Path, index: &str) -> PathBuf {
+    let key: String = digest::digest(&digest::SHA256, index.as_bytes())
+        .as_ref()
+        .iter()
+        .take(16)
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    dir.join("projects").join(format!("{key}.zip"))
+}
+
+/// The cached list of projects on index, if one was written less than ttl ago
+///
+/// A missing, stale, or unreadable cache is never an error, the list is just fetched again.
+pub fn read_projects(dir: &Path, index: &str, ttl: Duration) -> Option<Vec<String>> {
+    let path = projects_path(dir, index);
+    let age = SystemTime::now()
+        .duration_since(fs::metadata(&path).ok()?.modified().ok()?)
+        .unwrap_or_default();
+    if age > ttl {
+        return None;
+    }
+    let mut archive = ZipArchive::new(File::open(path).ok()?).ok()?;
+    let entry = archive.by_name(PROJECTS_ENTRY).ok()?;
+    BufReader::new(entry).lines().collect::<Result<_, _>>().ok()
+}
+
+/// Replace the cached list of projects on index
+pub fn write_projects(dir: &Path, index: &str, projects: &[String]) -> Result<()> {
+    let path = projects_path(dir, index);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // written aside and moved into place, so a concurrent session never reads half a list
+    let partial = path.with_extension("zip.partial");
+    let mut archive = ZipWriter::new(File::create(&partial)?);
+    archive.start_file(
+        PROJECTS_ENTRY,
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+    )?;
+    for project in projects {
+        writeln!(archive, "{project}")?;
+    }
+    archive.finish()?;
+    fs::rename(partial, path)?;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Default options read from a configuration file
+//!
+//! The file is a list of `option = value` lines, where each option is the long name of a
+//! command line option. Flags are set with `true` or `false`, and options that may be repeated,
+//! such as `verbose`, are set with the number of times they would be repeated.
+//! ```ini
+//! # always display licenses and urls
+//! verbose = 1
+//! license = true
+//! urls = true
+//! format = text
+//! ```
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Where the configuration file is looked for, if no other location is given
+///
+/// This is kayak/config.ini under $XDG_CONFIG_HOME, or under ~/.config if that is not set
+pub fn default_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(config_home.join("kayak").join("config.ini"))
+}
+
+#[derive(Debug, Default)]
+pub struct Config {
+    values: HashMap<String, String>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let ini = ini::Ini::load_from_file(path)?;
+        let values = ini
+            .general_section()
+            .iter()
+            .map(|(k, v)| (k.trim().replace('_', "-"), v.trim().to_string()))
+            .collect();
+        Ok(Config { values })
+    }
+
+    /// Fail on any option that is not one of known, so that typos do not go unnoticed
+    pub fn check_options(&self, known: &[&str]) -> Result<()> {
+        match self.values.keys().find(|k| !known.contains(&k.as_str())) {
+            Some(unknown) => Err(anyhow!("unknown option {unknown:?} in config file")),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get(&self, option: &str) -> Option<&str> {
+        self.values.get(option).map(String::as_str)
+    }
+
+    pub fn get_bool(&self, option: &str) -> Result<Option<bool>> {
+        self.get(option)
+            .map(|v| match v {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(anyhow!(
+                    "config option {option} must be true or false, not {v:?}"
+                )),
+            })
+            .transpose()
+    }
+
+    pub fn get_count(&self, option: &str) -> Result<Option<u8>> {
+        self.get(option)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| anyhow!("config option {option} must be a number, not {v:?}"))
+            })
+            .transpose()
+    }
+}
@@ -1,66 +1,538 @@
+use std::cmp::Ordering;
+use std::env;
 use std::error::Error as stdError;
 use std::fmt;
+use std::iter::Peekable;
+use std::process::Command;
+use std::sync::LazyLock;
 
 use anyhow::Result;
 use pep440::Version;
 use regex::Regex;
 
+// names are normalized and requirements are rewritten for every project, version, and
+// dependency looked at, so each pattern is only compiled the first time it is needed
+static VALID_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([a-zA-Z0-9]|[a-zA-Z0-9][a-zA-Z0-9._-]*[a-zA-Z0-9])$").unwrap());
+static SEPARATORS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[-_.]+").unwrap());
+static PARENTHESIZED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?<name>[^\s(;\[]+(\[[^\]]*\])?)\s*\((?<specifier>[^)]*)\)(?<marker>.*)$")
+        .unwrap()
+});
+
 /// Normalize the package name
 /// https://packaging.python.org/en/latest/specifications/name-normalization/
-/// An Error is returned if the name is not valid to begin with
+/// An Error is returned if the name is not valid to begin with, saying what makes it invalid
 pub fn normalize_package_name(name: &str) -> Result<String> {
-    let valid_name = Regex::new(r"^([a-zA-Z0-9]|[a-zA-Z0-9][a-zA-Z0-9._-]*[a-zA-Z0-9])$").unwrap();
-    if !valid_name.is_match(name) {
-        Err(Error::InvalidPackageName)?;
+    if !VALID_NAME.is_match(name) {
+        Err(Error::InvalidPackageName(invalid_name_reason(name)))?;
     }
 
-    let separators = Regex::new(r"[-_.]+").unwrap();
-    let normalized = separators.replace_all(name, "-");
+    let normalized = SEPARATORS.replace_all(name, "-");
     Ok(normalized.to_ascii_lowercase())
 }
 
+/// Whether a and b name the same project, comparing them as they are if either is not valid
+pub fn same_package_name(a: &str, b: &str) -> bool {
+    match (normalize_package_name(a), normalize_package_name(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Why name does not match VALID_NAME, checking the whole name before its ends
+fn invalid_name_reason(name: &str) -> String {
+    let separator = |c: char| matches!(c, '.' | '_' | '-');
+    if name.is_empty() {
+        return "the name is empty".to_string();
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|&c| !(c.is_ascii_alphanumeric() || separator(c)))
+    {
+        return format!(
+            "{c:?} is not allowed, names may only contain ASCII letters, digits, '.', '_', and '-'"
+        );
+    }
+    if name.chars().all(separator) {
+        return "names must contain a letter or digit".to_string();
+    }
+    match (name.chars().next(), name.chars().last()) {
+        (Some(first), _) if separator(first) => {
+            format!("names must start with a letter or digit, not {first:?}")
+        }
+        (_, Some(last)) => format!("names must end with a letter or digit, not {last:?}"),
+        _ => unreachable!("an empty name was already rejected"),
+    }
+}
+
+/// Rewrite a Requires-Dist value as a requirement specifier
+/// https://packaging.python.org/en/latest/specifications/dependency-specifiers/
+/// Older metadata wraps version specifiers in parentheses, which is not accepted in a requirements
+/// file, so they are unwrapped
+pub fn requirement_line(requires_dist: &str) -> String {
+    PARENTHESIZED
+        .replace(requires_dist.trim(), "$name$specifier$marker")
+        .to_string()
+}
+
+/// Normalize the name of an extra
+/// https://packaging.python.org/en/latest/specifications/core-metadata/#provides-extra-multiple-use
+pub fn normalize_extra_name(name: &str) -> String {
+    SEPARATORS
+        .replace_all(name.trim(), "-")
+        .to_ascii_lowercase()
+}
+
+/// Split the extras a Requires-Dist value is conditional on from the rest of the requirement
+///
+/// Requirements pulled in by an extra carry an `extra == "name"` environment marker, or several
+/// joined by `or` when more than one extra pulls them in. Those clauses are removed from the
+/// returned requirement, and any other clauses joined to them by `and` are kept. Requirements that
+/// are not conditional on an extra, or whose marker cannot be split without changing when they
+/// are required, are returned as they are with no extras.
+pub fn split_extras(requires_dist: &str) -> (Vec<String>, String) {
+    let unconditional = || (Vec::new(), requires_dist.trim().to_string());
+    let Some((requirement, marker)) = requires_dist.split_once(';') else {
+        return unconditional();
+    };
+    let Some((extras, marker)) = Marker::parse(marker).and_then(|m| m.separate_extras()) else {
+        return unconditional();
+    };
+    let requirement = requirement.trim();
+    match marker {
+        Some(marker) => (extras, format!("{requirement}; {marker}")),
+        None => (extras, requirement.to_string()),
+    }
+}
+
+/// An environment marker, parsed only as far as needed to find the extras it is conditional on
+/// https://packaging.python.org/en/latest/specifications/dependency-specifiers/#environment-markers
+#[derive(Clone, Debug, PartialEq)]
+enum Marker {
+    /// A comparison of two values, each a variable name or a quoted string, as they were written
+    Compare(String, String, String),
+    And(Vec<Marker>),
+    Or(Vec<Marker>),
+}
+
+const MARKER_OPERATORS: [&str; 10] = [
+    "===", "==", "!=", "<=", ">=", "<", ">", "~=", "in", "not in",
+];
+
+impl Marker {
+    /// Parse marker, or None if it is not a valid environment marker
+    fn parse(marker: &str) -> Option<Self> {
+        let mut tokens = Vec::new();
+        let mut rest = marker.trim_start();
+        while let Some(c) = rest.chars().next() {
+            let len = match c {
+                '(' | ')' => 1,
+                '\'' | '"' => rest[1..].find(c)? + 2,
+                '<' | '>' | '=' | '!' | '~' => {
+                    rest.find(|c| !"<>=!~".contains(c)).unwrap_or(rest.len())
+                }
+                _ => rest
+                    .find(|c: char| c.is_whitespace() || "()<>=!~'\"".contains(c))
+                    .unwrap_or(rest.len()),
+            };
+            tokens.push(&rest[..len]);
+            rest = rest[len..].trim_start();
+        }
+        let mut tokens = tokens.into_iter().peekable();
+        let marker = Self::parse_or(&mut tokens)?;
+        tokens.next().is_none().then_some(marker)
+    }
+
+    fn parse_or<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        let mut clauses = vec![Self::parse_and(tokens)?];
+        while tokens.next_if_eq(&"or").is_some() {
+            clauses.push(Self::parse_and(tokens)?);
+        }
+        Some(if clauses.len() == 1 {
+            clauses.remove(0)
+        } else {
+            Marker::Or(clauses)
+        })
+    }
+
+    fn parse_and<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        let mut clauses = vec![Self::parse_expression(tokens)?];
+        while tokens.next_if_eq(&"and").is_some() {
+            clauses.push(Self::parse_expression(tokens)?);
+        }
+        Some(if clauses.len() == 1 {
+            clauses.remove(0)
+        } else {
+            Marker::And(clauses)
+        })
+    }
+
+    fn parse_expression<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        if tokens.next_if_eq(&"(").is_some() {
+            let marker = Self::parse_or(tokens)?;
+            return tokens.next_if_eq(&")").and(Some(marker));
+        }
+        let is_value = |t: &&str| !["(", ")", "and", "or"].contains(t);
+        let left = tokens.next().filter(is_value)?;
+        let mut operator = tokens.next()?.to_string();
+        if operator == "not" && tokens.next_if_eq(&"in").is_some() {
+            operator = "not in".to_string();
+        }
+        if !MARKER_OPERATORS.contains(&operator.as_str()) {
+            return None;
+        }
+        let right = tokens.next().filter(is_value)?;
+        Some(Marker::Compare(
+            left.to_string(),
+            operator,
+            right.to_string(),
+        ))
+    }
+
+    /// The extras this marker is conditional on, and what is left of it once they are removed
+    ///
+    /// None if the marker is not conditional on an extra, or is conditional on one in a way that
+    /// cannot be separated from the rest of it, like `extra == "a" or python_version < "3.8"`.
+    fn separate_extras(&self) -> Option<(Vec<String>, Option<Self>)> {
+        match self {
+            Marker::Compare(left, operator, right) if operator == "==" => {
+                let name = match (left.as_str(), right.as_str()) {
+                    ("extra", name) | (name, "extra") => name,
+                    _ => return None,
+                };
+                let name = name.strip_prefix(['"', '\''])?.strip_suffix(['"', '\''])?;
+                Some((vec![normalize_extra_name(name)], None))
+            }
+            Marker::Compare(..) => None,
+            Marker::And(clauses) => {
+                // the first clause naming extras decides them, any others are kept in the marker
+                let (i, (extras, rest)) = clauses
+                    .iter()
+                    .enumerate()
+                    .find_map(|(i, c)| Some((i, c.separate_extras()?)))?;
+                let mut rest = clauses[..i]
+                    .iter()
+                    .cloned()
+                    .chain(rest)
+                    .chain(clauses[i + 1..].iter().cloned())
+                    .collect::<Vec<_>>();
+                let rest = match rest.len() {
+                    0 | 1 => rest.pop(),
+                    _ => Some(Marker::And(rest)),
+                };
+                Some((extras, rest))
+            }
+            Marker::Or(clauses) => {
+                let separated = clauses
+                    .iter()
+                    .map(Marker::separate_extras)
+                    .collect::<Option<Vec<_>>>()?;
+                // the extras are interchangeable only if each leaves the same marker behind
+                let rest = separated[0].1.clone();
+                if separated.iter().any(|(_, r)| *r != rest) {
+                    return None;
+                }
+                let mut extras = Vec::new();
+                for extra in separated.into_iter().flat_map(|(e, _)| e) {
+                    if !extras.contains(&extra) {
+                        extras.push(extra);
+                    }
+                }
+                Some((extras, rest))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Marker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Marker::Compare(left, operator, right) => write!(f, "{left} {operator} {right}"),
+            Marker::And(clauses) => {
+                for (i, clause) in clauses.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " and ")?;
+                    }
+                    match clause {
+                        Marker::Or(_) => write!(f, "({clause})")?,
+                        _ => write!(f, "{clause}")?,
+                    }
+                }
+                Ok(())
+            }
+            Marker::Or(clauses) => {
+                for (i, clause) in clauses.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " or ")?;
+                    }
+                    write!(f, "{clause}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Whether version is a pre-release, including development releases
+/// https://packaging.python.org/en/latest/specifications/version-specifiers/#pre-releases
+pub fn is_prerelease(version: &Version) -> bool {
+    version.pre.is_some() || version.dev.is_some()
+}
+
+/// Compare release segments, where missing segments are 0, so that 3.7 == 3.7.0
+fn cmp_release(a: &[u32], b: &[u32]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// The comparison a version specifier makes
+/// https://packaging.python.org/en/latest/specifications/version-specifiers/#version-specifiers
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    /// ~=
+    Compatible,
+    /// ==, or a prefix match when the version ends with .*
+    Equal,
+    /// !=, or a prefix exclusion when the version ends with .*
+    NotEqual,
+    /// <=
+    LessEqual,
+    /// >=
+    GreaterEqual,
+    /// <
+    Less,
+    /// >
+    Greater,
+    /// ===
+    Arbitrary,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operator = match self {
+            Operator::Compatible => "~=",
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+            Operator::LessEqual => "<=",
+            Operator::GreaterEqual => ">=",
+            Operator::Less => "<",
+            Operator::Greater => ">",
+            Operator::Arbitrary => "===",
+        };
+        write!(f, "{operator}")
+    }
+}
+
+/// A single clause of a version specifier, such as ">=3.8" or "!=3.9.*"
+#[derive(Clone, Debug)]
+pub struct Specifier {
+    pub operator: Operator,
+    pub version: Version,
+    /// Whether the version ends with .*, matching every version it is a prefix of
+    pub wildcard: bool,
+}
+
+impl Specifier {
+    pub fn parse(clause: &str) -> Option<Self> {
+        let clause = clause.trim();
+        let version_at = clause.find(|c: char| !"<>=!~".contains(c))?;
+        let (operator, version) = clause.split_at(version_at);
+        let operator = match operator {
+            "~=" => Operator::Compatible,
+            "==" => Operator::Equal,
+            "!=" => Operator::NotEqual,
+            "<=" => Operator::LessEqual,
+            ">=" => Operator::GreaterEqual,
+            "<" => Operator::Less,
+            ">" => Operator::Greater,
+            "===" => Operator::Arbitrary,
+            _ => return None,
+        };
+        let version = version.trim();
+        let (version, wildcard) = match version.strip_suffix(".*") {
+            Some(prefix) => (prefix, true),
+            None => (version, false),
+        };
+        Some(Specifier {
+            operator,
+            version: Version::parse(version)?,
+            wildcard,
+        })
+    }
+
+    /// The normalized version this clause compares against, ending with .* if it is a prefix
+    pub fn version_pattern(&self) -> String {
+        let wildcard = if self.wildcard { ".*" } else { "" };
+        format!("{}{wildcard}", self.version.normalize())
+    }
+
+    /// Whether the release segment candidate, such as [3, 11, 2], satisfies this clause
+    fn admits_release(&self, candidate: &[u32]) -> bool {
+        let release = &self.version.release;
+        let prefix = |p: &[u32]| cmp_release(&candidate[..p.len().min(3)], p).is_eq();
+        match (self.operator, self.wildcard) {
+            (Operator::Equal, true) => prefix(release),
+            (Operator::NotEqual, true) => !prefix(release),
+            (Operator::Equal | Operator::Arbitrary, false) => {
+                cmp_release(candidate, release).is_eq()
+            }
+            (Operator::NotEqual, false) => cmp_release(candidate, release).is_ne(),
+            (Operator::GreaterEqual, _) => cmp_release(candidate, release).is_ge(),
+            (Operator::LessEqual, _) => cmp_release(candidate, release).is_le(),
+            (Operator::Greater, _) => cmp_release(candidate, release).is_gt(),
+            (Operator::Less, _) => cmp_release(candidate, release).is_lt(),
+            (Operator::Compatible, _) if release.len() > 1 => {
+                cmp_release(candidate, release).is_ge() && prefix(&release[..release.len() - 1])
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Specifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.operator, self.version_pattern())
+    }
+}
+
+/// Every clause of a version specifier, such as the Requires-Python ">=3.7, <4", all of which a
+/// version must satisfy
+///
+/// It is displayed in canonical form, with each version normalized and the clauses separated by
+/// commas alone, keeping the order they were written in.
+#[derive(Clone, Debug)]
+pub struct SpecifierSet(pub Vec<Specifier>);
+
+impl SpecifierSet {
+    /// None is returned if any clause cannot be understood
+    pub fn parse(specifiers: &str) -> Option<Self> {
+        specifiers
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(Specifier::parse)
+            .collect::<Option<Vec<_>>>()
+            .map(SpecifierSet)
+    }
+
+    /// Whether some release of Python major.minor satisfies every clause
+    pub fn admits_python(&self, python: (u32, u32)) -> bool {
+        // only the minor version is known, so patch releases either side of each bound are tried
+        let (major, minor) = python;
+        let mut patches = vec![0, u32::MAX];
+        for specifier in &self.0 {
+            if let [m, n, patch, ..] = specifier.version.release[..] {
+                if (m, n) == python {
+                    patches.extend([patch.saturating_sub(1), patch, patch.saturating_add(1)]);
+                }
+            }
+        }
+        patches.into_iter().any(|patch| {
+            self.0
+                .iter()
+                .all(|s| s.admits_release(&[major, minor, patch]))
+        })
+    }
+}
+
+impl fmt::Display for SpecifierSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let clauses = self.0.iter().map(Specifier::to_string).collect::<Vec<_>>();
+        write!(f, "{}", clauses.join(","))
+    }
+}
+
+/// Whether a Requires-Python specifier, such as ">=3.8, !=3.9.*", admits some release of Python
+/// major.minor
+/// https://packaging.python.org/en/latest/specifications/version-specifiers/
+///
+/// None is returned if the specifier cannot be understood.
+pub fn admits_python(requires_python: &str, python: (u32, u32)) -> Option<bool> {
+    Some(SpecifierSet::parse(requires_python)?.admits_python(python))
+}
+
 #[derive(Debug)]
 pub enum Error {
     InvalidWheelName,
-    InvalidPackageName,
+    /// The name is not a valid project name, for the reason given
+    InvalidPackageName(String),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::InvalidWheelName => write!(f, "InvalidWheelName"),
-            Error::InvalidPackageName => write!(f, "InvalidPackageName"),
+            Error::InvalidPackageName(reason) => write!(f, "InvalidPackageName: {reason}"),
         }
     }
 }
 
 impl stdError for Error {}
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+/// A wheel's build tag, ordered by its leading number and then the rest of the tag
+#[derive(Default)]
 pub struct BuildTag {
     pub number: usize,
     pub string: String,
+    /// The tag as it was written, which may have leading zeros that number does not
+    tag: String,
+}
+
+impl PartialEq for BuildTag {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for BuildTag {}
+
+impl PartialOrd for BuildTag {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BuildTag {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.number, &self.string).cmp(&(other.number, &other.string))
+    }
 }
 
 impl BuildTag {
-    fn parse(buildtag: &str) -> Option<Self> {
-        let number = buildtag
+    pub fn parse(buildtag: &str) -> Option<Self> {
+        if buildtag.is_empty()
+            || buildtag
+                .chars()
+                .any(|c| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+        {
+            return None;
+        }
+        let digits = buildtag
             .chars()
             .take_while(|b| b.is_ascii_digit())
-            .collect::<String>()
-            .parse::<usize>()
-            .ok()?;
-        let string = buildtag
-            .chars()
-            .skip_while(|b| b.is_ascii_digit())
             .collect::<String>();
-        Some(Self { number, string })
+        // PEP-427 requires a leading number, but some published wheels have none; they are
+        // ordered as though it were 0
+        let number = if digits.is_empty() {
+            0
+        } else {
+            digits.parse::<usize>().ok()?
+        };
+        let string = buildtag[digits.len()..].to_string();
+        Some(Self {
+            number,
+            string,
+            tag: buildtag.to_string(),
+        })
     }
 }
 
 impl fmt::Display for BuildTag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.number, self.string)
+        write!(f, "{}", self.tag)
     }
 }
 
@@ -141,6 +613,50 @@ impl CompatibilityTag {
         Self::from_parts(py, api, plat)
     }
 
+    /// Parse a tag the way a person might type it
+    ///
+    /// Case is ignored, hyphens and other separators within the platform are read as the
+    /// underscores they are normalized to in a wheel's filename, and a whole wheel filename is read
+    /// as its tag.
+    pub fn from_tag_lenient(tag: &str) -> Option<Self> {
+        let tag = tag.trim();
+        if tag.to_ascii_lowercase().ends_with(".whl") {
+            return WheelName::from_filename(tag)
+                .ok()
+                .map(|w| w.compatibility_tag.normalized());
+        }
+        let tag = tag.to_ascii_lowercase();
+        let mut tags = tag.splitn(3, '-');
+        let py = tags.next()?;
+        let api = tags.next()?;
+        let plat = tags
+            .next()?
+            .replace(|c: char| !(c.is_alphanumeric() || c == '.'), "_");
+        Self::from_parts(py, api, &plat).map(|t| t.normalized())
+    }
+
+    /// The same tag with each part lowercased and its compressed tag set sorted
+    fn normalized(&self) -> Self {
+        let normalize = |part: &Vec<String>| {
+            let mut part = part
+                .iter()
+                .map(|t| t.to_ascii_lowercase())
+                .collect::<Vec<_>>();
+            part.sort_unstable();
+            part
+        };
+        CompatibilityTag {
+            python_tag: normalize(&self.python_tag),
+            api_tag: self.api_tag.as_ref().map(normalize),
+            platform_tag: self.platform_tag.as_ref().map(normalize),
+        }
+    }
+
+    /// Whether both tags name the same set of tags, regardless of case or the order they are listed
+    pub fn matches(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+
     pub fn python_tags(&self) -> Vec<&str> {
         self.python_tag.iter().map(AsRef::as_ref).collect()
     }
@@ -176,6 +692,36 @@ impl CompatibilityTag {
     pub fn for_any_abi(&self) -> bool {
         self.api_tag.is_none()
     }
+
+    /// Describe which interpreters and platforms this tag is compatible with, in human terms
+    ///
+    /// For example cp311-cp311-manylinux_2_17_x86_64 is "CPython 3.11, manylinux glibc 2.17,
+    /// x86_64"
+    pub fn describe(&self) -> String {
+        let mut description = self
+            .python_tag
+            .iter()
+            .map(|t| describe_python_tag(t))
+            .collect::<Vec<_>>()
+            .join(" or ");
+        if self.api_tags().contains(&"abi3") {
+            description += ", stable ABI";
+        }
+        if self.for_any_platform() {
+            description += ", any platform";
+        } else {
+            let mut platforms = Vec::new();
+            for platform in self.platform_tags().into_iter().map(describe_platform_tag) {
+                // a wheel is often tagged with both the legacy and modern names of one platform
+                if !platforms.contains(&platform) {
+                    platforms.push(platform);
+                }
+            }
+            description += ", ";
+            description += &platforms.join(" or ");
+        }
+        description
+    }
 }
 
 pub fn split_python_tag(python_tag: &str) -> (String, String) {
@@ -190,6 +736,67 @@ pub fn split_python_tag(python_tag: &str) -> (String, String) {
     (implementation, version)
 }
 
+/// The human name of a PEP-425 Python tag, such as "CPython 3.11" for cp311
+fn describe_python_tag(python_tag: &str) -> String {
+    let (implementation, version) = split_python_tag(python_tag);
+    let implementation = match implementation.as_str() {
+        "py" => "Python",
+        "cp" => "CPython",
+        "pp" => "PyPy",
+        "ip" => "IronPython",
+        "jy" => "Jython",
+        "gp" => "GraalPy",
+        _ => return python_tag.to_string(),
+    };
+    // the major version is always a single digit, the rest is the minor version
+    match version.split_at_checked(1) {
+        Some((major, "")) => format!("{implementation} {major}"),
+        Some((major, minor)) => format!("{implementation} {major}.{minor}"),
+        None => implementation.to_string(),
+    }
+}
+
+/// The human name of a platform tag, such as "manylinux glibc 2.17, x86_64"
+fn describe_platform_tag(platform_tag: &str) -> String {
+    // the legacy manylinux aliases from PEP-513, PEP-571, and PEP-599
+    for (legacy, glibc) in [
+        ("manylinux1_", "2.5"),
+        ("manylinux2010_", "2.12"),
+        ("manylinux2014_", "2.17"),
+    ] {
+        if let Some(arch) = platform_tag.strip_prefix(legacy) {
+            return format!("manylinux glibc {glibc}, {arch}");
+        }
+    }
+    // PEP-600 and PEP-656 tags are {family}_{major}_{minor}_{arch}, as are macOS tags
+    for (prefix, name) in [
+        ("manylinux_", "manylinux glibc"),
+        ("musllinux_", "musllinux musl"),
+        ("macosx_", "macOS"),
+    ] {
+        if let Some(rest) = platform_tag.strip_prefix(prefix) {
+            let mut parts = rest.splitn(3, '_');
+            if let (Some(major), Some(minor), Some(arch)) =
+                (parts.next(), parts.next(), parts.next())
+            {
+                return format!("{name} {major}.{minor}, {arch}");
+            }
+        }
+    }
+    match platform_tag {
+        "win32" => "Windows, x86".to_string(),
+        _ => {
+            if let Some(arch) = platform_tag.strip_prefix("win_") {
+                format!("Windows, {arch}")
+            } else if let Some(arch) = platform_tag.strip_prefix("linux_") {
+                format!("Linux, {arch}")
+            } else {
+                platform_tag.to_string()
+            }
+        }
+    }
+}
+
 impl fmt::Display for CompatibilityTag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let python_tag = self.python_tag.join(".");
@@ -207,9 +814,235 @@ impl fmt::Display for CompatibilityTag {
     }
 }
 
+/// A platform that wheels can be built for, parsed from a platform tag
+///
+/// Tags that name the same platform family are comparable, so that a manylinux_2_17 wheel is known
+/// to support a host with glibc 2.36.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Platform {
+    /// One of linux, manylinux, musllinux, macosx, or win
+    pub family: String,
+    /// The minimum libc or operating system version, if the family is versioned
+    pub version: Option<(u32, u32)>,
+    pub arch: String,
+}
+
+impl Platform {
+    pub fn from_tag(platform_tag: &str) -> Option<Self> {
+        let versioned = |family: &str, major: u32, minor: u32, arch: &str| Platform {
+            family: family.to_string(),
+            version: Some((major, minor)),
+            arch: arch.to_string(),
+        };
+        for (legacy, minor) in [
+            ("manylinux1_", 5),
+            ("manylinux2010_", 12),
+            ("manylinux2014_", 17),
+        ] {
+            if let Some(arch) = platform_tag.strip_prefix(legacy) {
+                return Some(versioned("manylinux", 2, minor, arch));
+            }
+        }
+        for family in ["manylinux", "musllinux", "macosx"] {
+            if let Some(rest) = platform_tag
+                .strip_prefix(family)
+                .and_then(|r| r.strip_prefix('_'))
+            {
+                let mut parts = rest.splitn(3, '_');
+                let major = parts.next()?.parse().ok()?;
+                let minor = parts.next()?.parse().ok()?;
+                return Some(versioned(family, major, minor, parts.next()?));
+            }
+        }
+        let (family, arch) = match platform_tag {
+            "win32" => ("win", "x86"),
+            _ => platform_tag
+                .strip_prefix("win_")
+                .map(|arch| ("win", arch))
+                .or_else(|| platform_tag.strip_prefix("linux_").map(|a| ("linux", a)))?,
+        };
+        Some(Platform {
+            family: family.to_string(),
+            version: None,
+            arch: arch.to_string(),
+        })
+    }
+
+    /// Guess the platform kayak is running on
+    ///
+    /// The libc is found by asking ldd, and the macOS version by asking sw_vers. If they cannot
+    /// answer, the platform is left unversioned and every version of its family is supported.
+    pub fn detect() -> Option<Self> {
+        let arch = match (env::consts::OS, env::consts::ARCH) {
+            ("macos", "aarch64") => "arm64",
+            ("windows", "x86_64") => "amd64",
+            ("windows", "aarch64") => "arm64",
+            ("linux", "x86") => "i686",
+            ("linux", "arm") => "armv7l",
+            ("linux", "powerpc64") if cfg!(target_endian = "little") => "ppc64le",
+            (_, arch) => arch,
+        };
+        let unversioned = |family: &str| Platform {
+            family: family.to_string(),
+            version: None,
+            arch: arch.to_string(),
+        };
+        match env::consts::OS {
+            "linux" => {
+                // glibc's ldd answers on stdout, musl's on stderr
+                let ldd = Command::new("ldd").arg("--version").output().ok();
+                let answer = ldd
+                    .map(|o| {
+                        String::from_utf8_lossy(&o.stdout).to_string()
+                            + &String::from_utf8_lossy(&o.stderr)
+                    })
+                    .unwrap_or_default();
+                let family = if answer.contains("musl") {
+                    "musllinux"
+                } else if answer.contains("GNU") || answer.contains("GLIBC") {
+                    "manylinux"
+                } else {
+                    return Some(unversioned("linux"));
+                };
+                let version = Regex::new(r"(\d+)\.(\d+)")
+                    .unwrap()
+                    .captures(&answer)
+                    .and_then(|c| Some((c[1].parse().ok()?, c[2].parse().ok()?)));
+                Some(Platform {
+                    version,
+                    ..unversioned(family)
+                })
+            }
+            "macos" => {
+                let sw_vers = Command::new("sw_vers")
+                    .arg("-productVersion")
+                    .output()
+                    .ok()
+                    .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+                    .unwrap_or_default();
+                let mut parts = sw_vers.trim().split('.').map(str::parse);
+                let version = match (parts.next(), parts.next()) {
+                    (Some(Ok(major)), Some(Ok(minor))) => Some((major, minor)),
+                    (Some(Ok(major)), None) => Some((major, 0)),
+                    _ => None,
+                };
+                Some(Platform {
+                    version,
+                    ..unversioned("macosx")
+                })
+            }
+            "windows" => Some(unversioned("win")),
+            _ => None,
+        }
+    }
+
+    /// Whether a wheel built for platform can be installed on this platform
+    pub fn supports(&self, platform: &Platform) -> bool {
+        let family = platform.family == self.family
+            // plain linux wheels make no promises about libc, but are installable on any linux
+            || (platform.family == "linux" && ["manylinux", "musllinux"].contains(&self.family.as_str()))
+            || (self.family == "linux" && platform.family == "manylinux");
+        let version = match (self.version, platform.version) {
+            (Some(host), Some(required)) if platform.family == self.family => required <= host,
+            _ => true,
+        };
+        let arch = platform.arch == self.arch
+            || (platform.family == "macosx"
+                && match platform.arch.as_str() {
+                    "universal2" => ["arm64", "x86_64"].contains(&self.arch.as_str()),
+                    "universal" | "intel" => self.arch == "x86_64",
+                    _ => false,
+                });
+        family && version && arch
+    }
+}
+
+// detecting the host runs other programs, so it is only done once a wheel is picked for it
+static DETECTED_PYTHON: LazyLock<Option<(u32, u32)>> = LazyLock::new(Host::detect_python);
+static DETECTED_PLATFORM: LazyLock<Option<Platform>> = LazyLock::new(Platform::detect);
+
+/// A best-effort guess of the environment that a wheel would be installed into
+///
+/// Either part can be unknown, in which case every wheel is assumed to support that part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Host {
+    /// The major and minor version of CPython
+    pub python: Option<(u32, u32)>,
+    pub platform: Option<Platform>,
+}
+
+impl Host {
+    /// Guess the environment kayak is running in
+    ///
+    /// The Python version is that of the first python3 or python found on the PATH.
+    pub fn detect() -> Self {
+        Host {
+            python: Self::detect_python(),
+            platform: Platform::detect(),
+        }
+    }
+
+    /// Fill in each unknown part with the guess [`Host::detect`] would make
+    ///
+    /// Each guess is only made the first time it is needed, and kept for the rest of the session.
+    pub fn or_detected(self) -> Self {
+        Host {
+            python: self.python.or(*DETECTED_PYTHON),
+            platform: self.platform.or_else(|| DETECTED_PLATFORM.clone()),
+        }
+    }
+
+    pub fn detect_python() -> Option<(u32, u32)> {
+        ["python3", "python"].iter().find_map(|python| {
+            let output = Command::new(python).arg("--version").output().ok()?;
+            // some old interpreters print their version to stderr
+            let version = String::from_utf8_lossy(&output.stdout).to_string()
+                + &String::from_utf8_lossy(&output.stderr);
+            parse_python_version(version.trim().strip_prefix("Python ")?)
+        })
+    }
+
+    /// Whether a wheel built with tag can be installed into this environment
+    pub fn supports(&self, tag: &CompatibilityTag) -> bool {
+        let python = match self.python {
+            Some((major, minor)) => tag.python_tags().iter().any(|t| {
+                let (implementation, version) = split_python_tag(t);
+                let (tag_major, tag_minor) = version.split_at_checked(1).unwrap_or(("", ""));
+                let tag_minor = tag_minor.parse::<u32>().ok();
+                tag_major == major.to_string()
+                    && match implementation.as_str() {
+                        "py" => tag_minor.is_none_or(|m| m <= minor),
+                        // only the stable ABI is forward compatible between CPython releases
+                        "cp" if tag.api_tags().contains(&"abi3") => {
+                            tag_minor.is_some_and(|m| m <= minor)
+                        }
+                        "cp" => tag_minor == Some(minor),
+                        _ => false,
+                    }
+            }),
+            None => true,
+        };
+        let platform = match &self.platform {
+            Some(host) if !tag.for_any_platform() => tag
+                .platform_tags()
+                .iter()
+                .filter_map(|t| Platform::from_tag(t))
+                .any(|p| host.supports(&p)),
+            _ => true,
+        };
+        python && platform
+    }
+}
+
+/// Parse a Python version such as 3.11 or 3.11.4 into its major and minor parts
+pub fn parse_python_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.');
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
+
 pub struct WheelName {
-    distribution: String,
-    version: Version,
+    pub distribution: String,
+    pub version: Version,
     pub build_tag: Option<BuildTag>,
     pub compatibility_tag: CompatibilityTag,
 }
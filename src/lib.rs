@@ -0,0 +1,132 @@
+//! Look up the key-data of Python projects published to a package index
+//!
+//! [`Project`] lazily fetches a project's metadata and inspects its distributions, while
+//! [`ProjectReport`] collects all of it into one serializable value.
+
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+use clap::ValueEnum;
+
+pub mod cache;
+pub mod config;
+pub mod distribution;
+pub mod package_inspect;
+pub mod picker;
+pub mod report;
+pub mod ui;
+pub mod warehouse;
+pub mod warnings;
+
+pub use crate::picker::{Project, ProjectOptions};
+pub use crate::report::ProjectReport;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum SortBy {
+    Version,
+    Date,
+}
+
+/// Which API of the package index projects are looked up with
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Api {
+    /// The JSON api of warehouse, the index behind pypi.org, with all of a project's metadata
+    Json,
+    /// The PEP-691 JSON simple api that other indexes implement, with only a project's files
+    Simple,
+}
+
+/// A section of project details
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    Name,
+    Time,
+    License,
+    People,
+    Summary,
+    Downloads,
+    Urls,
+    Keywords,
+    Classifiers,
+    Artifacts,
+    #[value(alias = "deps")]
+    Dependencies,
+    Packages,
+    Executables,
+    EntryPoints,
+    Readme,
+}
+
+impl Field {
+    /// The order sections are displayed in, unless another is given
+    pub const DEFAULT_ORDER: [Field; 15] = [
+        Field::Name,
+        Field::Time,
+        Field::License,
+        Field::People,
+        Field::Summary,
+        Field::Downloads,
+        Field::Urls,
+        Field::Keywords,
+        Field::Classifiers,
+        Field::Artifacts,
+        Field::Dependencies,
+        Field::Packages,
+        Field::Executables,
+        Field::EntryPoints,
+        Field::Readme,
+    ];
+}
+
+#[derive(Clone, Debug)]
+pub struct DisplayFields {
+    pub name: bool,
+    pub versions: bool,
+    pub sort_by: SortBy,
+    pub limit: Option<usize>,
+    pub reverse: bool,
+    pub stable_only: bool,
+    pub time: bool,
+    /// Display times in the local zone instead of UTC, except in machine formats
+    pub local_time: bool,
+    /// A strftime format to display times with, instead of ui::DEFAULT_DATE_FORMAT, except in
+    /// machine formats
+    pub date_format: Option<String>,
+    /// Display how long ago each time was instead of the time itself, except in machine formats
+    pub relative_time: bool,
+    pub summary: bool,
+    pub license: bool,
+    /// Display how many times the project was downloaded recently, if that can be looked up
+    pub downloads: bool,
+    /// Display the authors and maintainers separately from the copyright
+    pub people: bool,
+    pub urls: bool,
+    pub keywords: bool,
+    pub classifiers: bool,
+    pub artifacts: u8,
+    /// Describe the selected distribution's compatibility tag in human terms
+    pub describe_tags: bool,
+    pub dependencies: bool,
+    /// Only display the dependencies of these extras, besides those that are always required
+    pub extras: Option<Vec<String>>,
+    pub readme: u8,
+    pub packages: u8,
+    pub executables: bool,
+    pub entry_points: bool,
+    pub color: bool,
+    /// Mark each URL with an emoji icon, instead of a short ASCII label
+    pub emoji: bool,
+    /// Lay out details this many columns wide, instead of as wide as the terminal
+    pub width: Option<u16>,
+    /// Display only these sections, in this order, instead of every section in the default order
+    pub order: Option<Vec<Field>>,
+}
+
+impl DisplayFields {
+    /// The sections that may be displayed, in the order they are displayed
+    pub fn ordered(&self) -> Vec<Field> {
+        self.order
+            .clone()
+            .unwrap_or_else(|| Field::DEFAULT_ORDER.to_vec())
+    }
+}
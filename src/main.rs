@@ -1,17 +1,19 @@
-#![deny(unused_crate_dependencies)]
-#![deny(unused_extern_crates)]
-
-use crate::picker::Project;
-use crate::ui::{interactive, pretty, text};
-use anyhow::Result;
-use clap::{Parser, ValueEnum};
+use anyhow::{anyhow, Context, Result};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use kayak::config::{self, Config};
+use kayak::ui::{self, interactive, json, pretty, text, yaml, Spinner};
+use kayak::{
+    distribution, package_inspect, picker, warehouse, warnings, Api, DisplayFields, Field, Project,
+    ProjectOptions, SortBy,
+};
 use pep440::Version;
-
-pub mod distribution;
-pub mod package_inspect;
-pub mod picker;
-pub mod ui;
-pub mod warehouse;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
 
 #[derive(Parser, Debug)]
 #[command(version, about)]
@@ -19,11 +21,16 @@ struct Cli {
     #[arg(
         long_help = "the name of the python project to look up",
         // what I want: required_unless_present_and_eq_all([("format", "interactive")])
-        required_unless_present_all = ["format"],
-        required_if_eq_any = [
-            ("format", "text"),
-            ("format", "pretty"),
-        ]
+        required_unless_present_any = [
+            "format",
+            "provides",
+            "json_schema",
+            "normalize_name",
+            "from_url",
+            "from_file",
+            "inspect_url",
+            "refresh_index",
+        ],
     )]
     project: Option<String>,
     #[arg(
@@ -33,13 +40,127 @@ struct Cli {
     package_version: Option<String>,
     #[arg(
         value_name = "DIST",
-        long_help = "if not specified, a suitable distribution will be automatically retrieved.\n\
+        long_help = "sdist, a wheel's compatibility tag, or any part of a wheel's filename or tag,\n\
+                     such as win_amd64 or cp312. If not specified, a suitable distribution will be\n\
+                     automatically retrieved.\n\
                      If any level of artifact metadata is to be displayed, metadata will only be\n\
                      displayed for the specified distribution, otherwise for all distributions the\n\
                      particular version provides"
     )]
     dist: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["project", "diff", "provides", "requirements", "download", "verify_download"],
+        help = "look up every project listed in PATH",
+        long_help = "look up each project listed in PATH, one per line, instead of a single PROJECT.\n\
+                     Each line is a project name, optionally followed by a VERSION and DIST. Blank\n\
+                     lines and lines starting with # are ignored, and a PATH of - reads from stdin.\n\
+                     Requires --format json or line, and writes one JSON object or line per project. A\n\
+                     project that cannot be looked up is written with only its name and an error"
+    )]
+    projects_file: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "URL",
+        conflicts_with_all = ["project", "projects_file", "provides"],
+        help = "look up the project metadata at URL",
+        long_help = "look up the project or version described by the JSON api response at URL,\n\
+                     instead of at the path PROJECT has on the --index. Metadata that the response\n\
+                     does not include is still retrieved from the index"
+    )]
+    from_url: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["project", "projects_file", "provides", "from_url"],
+        help = "look up the project metadata saved in PATH",
+        long_help = "look up the project or version described by a JSON api response saved in PATH,\n\
+                     instead of retrieving it from the --index. Metadata that the response does not\n\
+                     include is still retrieved from the index"
+    )]
+    from_file: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "WHEEL_URL",
+        conflicts_with_all = [
+            "project",
+            "projects_file",
+            "provides",
+            "from_url",
+            "from_file",
+            "diff",
+            "requirements",
+            "download",
+            "verify_download",
+        ],
+        help = "inspect only the wheel at WHEEL_URL",
+        long_help = "inspect the wheel at WHEEL_URL, without looking up any project on the --index,\n\
+                     and display the packages, executables, and entry points it provides. Unless\n\
+                     some are selected with --packages, --executables, or --entry-points, packages\n\
+                     and executables are displayed. Always written as text"
+    )]
+    inspect_url: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "URL",
+        default_value = warehouse::PYPI_URI,
+        help = "package index to look up projects on",
+        long_help = "the root URL of the package index to retrieve project metadata from. The index\n\
+                     must implement the same JSON API as pypi.org. A custom index is checked for\n\
+                     compatibility before any project is looked up"
+    )]
+    index: String,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = Api::Json,
+        help = "API of the index to look up projects with",
+        long_help = "the API of the index to look up projects with:\n\
+                     json: the JSON API of pypi.org, with all of a project's metadata\n\
+                     simple: the JSON simple API (PEP 691) that devpi and other indexes implement.\n\
+                     \t\tOnly a project's versions and files are known, so most details are empty"
+    )]
+    api: Api,
+    #[arg(
+        long,
+        help = "fail instead of following redirects",
+        long_help = "fail any request that is answered with a redirect, instead of following it to\n\
+                     wherever it leads. A redirect to another host could otherwise carry the index's\n\
+                     credentials along with it. Redirects that are followed are reported by --warnings"
+    )]
+    no_redirect: bool,
+    #[arg(
+        long,
+        conflicts_with = "refresh_index",
+        help = "fail instead of fetching anything over the network",
+        long_help = "never send a request over the network. Anything that would have to be fetched\n\
+                     is an error instead, so only what --from-file describes can be displayed, and\n\
+                     the cached list of projects is used to suggest projects however old it is"
+    )]
+    offline: bool,
+    #[arg(
+        long,
+        value_name = "URL",
+        value_parser = parse_proxy,
+        help = "send requests through the proxy at URL",
+        long_help = "send every request through the proxy at URL, such as http://proxy.example:3128,\n\
+                     instead of the proxy named by the HTTPS_PROXY or HTTP_PROXY environment\n\
+                     variables. Hosts listed in the NO_PROXY environment variable are always\n\
+                     reached directly"
+    )]
+    proxy: Option<String>,
+    #[arg(
+        long,
+        visible_alias = "prerelease",
+        conflicts_with = "package_version",
+        help = "allow a pre-release to be automatically retrieved",
+        long_help = "when VERSION is not specified, consider pre-release and development versions\n\
+                     when automatically retrieving the greatest version. Pre-releases are always\n\
+                     considered for projects which have not made any final releases"
+    )]
+    pre: bool,
     #[arg(
         long,
         conflicts_with = "package_version",
@@ -47,7 +168,153 @@ struct Cli {
         long_help = "instead of displaying project details, list all versions available"
     )]
     versions: bool,
+    #[arg(
+        long,
+        value_name = "OTHER_VERSION",
+        conflicts_with_all = ["versions", "dist"],
+        help = "compare metadata against another version",
+        long_help = "instead of displaying project details, display the differences in summary,\n\
+                     license, classifiers, and dependencies between VERSION and OTHER_VERSION.\n\
+                     The comparison is always written as text"
+    )]
+    diff: Option<String>,
+    #[arg(
+        long,
+        value_name = "IMPORT_NAME",
+        conflicts_with = "project",
+        help = "find which projects provide an importable name",
+        long_help = "instead of displaying project details, list the projects whose wheels provide\n\
+                     IMPORT_NAME as a top-level importable name. Searching every project on the\n\
+                     index is not feasible, so only the projects given by --candidates are\n\
+                     inspected, or if none are given, a small number of projects with names similar\n\
+                     to IMPORT_NAME"
+    )]
+    provides: Option<String>,
+    #[arg(
+        long,
+        value_name = "IMPORT_NAME",
+        conflicts_with_all = ["provides", "projects_file", "inspect_url", "metadata_only"],
+        help = "check that the wheel provides IMPORT_NAME",
+        long_help = "instead of displaying project details, check that the selected wheel provides\n\
+                     IMPORT_NAME as a top-level importable name. Exits with status 5 if it does not.\n\
+                     The wheel is selected as for --packages, preferring one for the --platform and\n\
+                     --python-version given"
+    )]
+    has_import: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["provides", "projects_file", "metadata_only", "has_import", "has_command"],
+        help = "list the files the wheel installs",
+        long_help = "instead of displaying project details, list every file the selected wheel, or the\n\
+                     wheel given by --inspect-url, would install, as its RECORD lists them. Files are\n\
+                     grouped by where they are installed: site-packages, scripts, headers, or data.\n\
+                     --format json and yaml list each file with its location instead"
+    )]
+    files: bool,
+    #[arg(
+        long,
+        conflicts_with_all = ["provides", "projects_file", "metadata_only", "has_import", "has_command", "files"],
+        help = "report how large the wheel is",
+        long_help = "instead of displaying project details, report how large the selected wheel, or\n\
+                     the wheel given by --inspect-url, is to download and once installed, as the sum\n\
+                     of the file sizes its RECORD lists. --format json and yaml give sizes in bytes"
+    )]
+    size_report: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "size_report",
+        help = "list the N largest files with --size-report"
+    )]
+    largest: Option<usize>,
+    #[arg(
+        long,
+        value_name = "COMMAND",
+        conflicts_with_all = ["provides", "projects_file", "inspect_url", "metadata_only"],
+        help = "check that the wheel provides COMMAND",
+        long_help = "instead of displaying project details, check that the selected wheel provides\n\
+                     COMMAND as an executable or console script. Exits with status 5 if it does not.\n\
+                     The wheel is selected as for --executables, preferring one for the --platform\n\
+                     and --python-version given"
+    )]
+    has_command: Option<String>,
+    #[arg(
+        long,
+        value_name = "PROJECT",
+        value_delimiter = ',',
+        requires = "provides",
+        help = "projects to search with --provides",
+        long_help = "a comma separated list of projects to inspect when searching with --provides"
+    )]
+    candidates: Vec<String>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = SortBy::Version,
+        help = "order in which to list versions",
+        long_help = "select the order in which --versions are listed:\n\
+                     version: newest version first, by comparison order\n\
+                     date: most recently uploaded version first"
+    )]
+    sort_by: SortBy,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "list only the newest N versions",
+        long_help = "when listing --versions, list only the newest N versions"
+    )]
+    limit: Option<usize>,
+    #[arg(
+        long,
+        help = "list versions oldest first",
+        long_help = "list --versions in ascending order, instead of newest first. When combined with\n\
+                     --limit, the newest versions are still the ones selected"
+    )]
+    reverse: bool,
+    #[arg(
+        long,
+        help = "list only stable versions",
+        long_help = "exclude pre-release and development versions when listing --versions"
+    )]
+    stable_only: bool,
 
+    #[arg(
+        long,
+        short = 't',
+        help = "display the project's release time",
+        long_help = "force the project's release time to display, otherwise requires verbosity 1\n\
+                     before being displayed. When listing --versions, the release time of each\n\
+                     version is displayed"
+    )]
+    time: bool,
+    #[arg(
+        long,
+        help = "display times in the local time zone",
+        long_help = "convert every displayed time, such as the release time and artifact upload\n\
+                     times, from UTC into the local time zone. JSON and YAML always give times as\n\
+                     RFC 3339 in UTC"
+    )]
+    local_time: bool,
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_parser = parse_date_format,
+        help = "display times with a strftime FORMAT",
+        long_help = "display every time, such as the release time and artifact upload times, with a\n\
+                     strftime FORMAT such as \"%d %b %Y\". By default times are displayed like\n\
+                     2024-01-31 12:00:00 UTC, always including the time zone. JSON and YAML always\n\
+                     give times as RFC 3339"
+    )]
+    date_format: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["local_time", "date_format"],
+        help = "display how long ago each time was",
+        long_help = "display every time, such as the release time and artifact upload times, as how\n\
+                     long before now it was, like \"3 months ago\", instead of the time itself. JSON\n\
+                     and YAML always give times as RFC 3339"
+    )]
+    relative_time: bool,
     #[arg(
         long,
         short = 's',
@@ -64,6 +331,33 @@ struct Cli {
                      being displayed"
     )]
     license: bool,
+    #[arg(
+        long,
+        help = "display the project's authors and maintainers",
+        long_help = "force the project's authors and maintainers to display, each with their name\n\
+                     and email, otherwise requires verbosity 1 before being displayed. Someone who is\n\
+                     both author and maintainer is listed once"
+    )]
+    people: bool,
+    #[arg(
+        long,
+        help = "display how many times the project was downloaded recently",
+        long_help = "display how many times the project was downloaded in the last day, week, and\n\
+                     month, as counted by --downloads-source. Nothing is displayed when the source\n\
+                     cannot be reached or does not know the project"
+    )]
+    downloads: bool,
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "where --downloads are counted",
+        long_help = "the pypistats-style API that --downloads are counted by, with {project} in place\n\
+                     of the project's normalized name. The response must hold the counts under\n\
+                     data.last_day, data.last_week, and data.last_month. By default downloads from\n\
+                     PyPI are counted by pypistats.org; with any other --index, nothing is counted\n\
+                     unless this is given"
+    )]
+    downloads_source: Option<String>,
     #[arg(
         long,
         short = 'u',
@@ -94,11 +388,69 @@ struct Cli {
         action = clap::ArgAction::Count,
         help = "display the project's artifact types",
         long_help = "force the project's artifact types to display, otherwise requires verbosity 3\n\
-                     before being displayed. This option can be passed up to 4 times, each time will\n\
+                     before being displayed. This option can be passed up to 5 times, each time will\n\
                      display more details about the artifacts available. Verbosity of level 3 or\n\
                      higher will still only display the first level of artifact detail"
     )]
     artifacts: u8,
+    #[arg(
+        long,
+        help = "describe the distribution's compatibility",
+        long_help = "along with the selected distribution's compatibility tag, describe the Python\n\
+                     interpreters and platforms it is built for, such as \"CPython 3.11, manylinux\n\
+                     glibc 2.17, x86_64\" for cp311-cp311-manylinux_2_17_x86_64"
+    )]
+    describe_tags: bool,
+    #[arg(
+        long,
+        conflicts_with_all = ["dist", "verify_download", "download", "provides"],
+        help = "never inspect distributions",
+        long_help = "only display details from the project's metadata, never selecting or downloading\n\
+                     a distribution. Artifacts, packages, executables, and entry points are not\n\
+                     displayed, even when requested"
+    )]
+    metadata_only: bool,
+    #[arg(
+        long,
+        value_name = "TAG",
+        value_parser = parse_platform,
+        help = "prefer wheels for the platform TAG",
+        long_help = "when no DIST is given, prefer wheels that can be installed on the platform TAG,\n\
+                     such as manylinux_2_17_x86_64, macosx_11_0_arm64, or win_amd64, instead of the\n\
+                     platform kayak is running on. By default the running platform is guessed from\n\
+                     its operating system, architecture, and libc"
+    )]
+    platform: Option<distribution::Platform>,
+    #[arg(
+        long,
+        value_name = "X.Y",
+        value_parser = parse_python_version,
+        help = "prefer wheels for CPython X.Y",
+        long_help = "when no DIST is given, prefer wheels that can be installed into CPython X.Y,\n\
+                     instead of the version of the python3 found on the PATH. A warning is given if\n\
+                     the selected release does not support this version of Python"
+    )]
+    python_version: Option<(u32, u32)>,
+    #[arg(
+        long,
+        requires = "dist",
+        help = "verify the selected distribution's digest",
+        long_help = "download the selected distribution and confirm its sha256 digest matches the\n\
+                     one published by the package index. Requires a DIST to be specified"
+    )]
+    verify_download: bool,
+    #[arg(
+        long,
+        value_name = "DIR",
+        num_args = 0..=1,
+        default_missing_value = ".",
+        help = "save the selected distribution to disk",
+        long_help = "download the selected distribution into DIR, or the current directory if DIR is\n\
+                     not given. If DIST is not specified, a suitable distribution will be\n\
+                     automatically selected. The file's sha256 digest is verified after download\n\
+                     and the path of the saved file is printed"
+    )]
+    download: Option<PathBuf>,
     #[arg(
         long,
         short = 'd',
@@ -107,6 +459,24 @@ struct Cli {
                      before being displayed"
     )]
     dependencies: bool,
+    #[arg(
+        long,
+        value_name = "EXTRA",
+        value_delimiter = ',',
+        help = "display only these extras' dependencies",
+        long_help = "a comma separated list of extras whose dependencies are displayed, along with\n\
+                     the dependencies that are always required. Implies --dependencies. The names of\n\
+                     the other available extras are still displayed"
+    )]
+    extras: Vec<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["versions", "diff"],
+        help = "display dependencies as requirements",
+        long_help = "instead of displaying project details, write the project's dependencies as\n\
+                     requirement lines, suitable for a requirements.txt file"
+    )]
+    requirements: bool,
     #[arg(
         long,
         short = 'r',
@@ -120,11 +490,13 @@ struct Cli {
     #[arg(
         long,
         short = 'p',
+        action = clap::ArgAction::Count,
         help = "display the project's importable packages",
         long_help = "display the project's importable top-level names. Not displayed under any\n\
-                     verbosity level"
+                     verbosity level. This option can be passed up to 2 times, if passed twice each\n\
+                     name is annotated as a package, module, or namespace package"
     )]
-    packages: bool,
+    packages: u8,
     #[arg(
         long,
         short = 'e',
@@ -133,6 +505,26 @@ struct Cli {
                      verbosity level"
     )]
     executables: bool,
+    #[arg(
+        long,
+        short = 'g',
+        help = "display the project's entry points",
+        long_help = "display the project's entry points, grouped by entry-point group, other than its\n\
+                     console and gui scripts. Not displayed under any verbosity level"
+    )]
+    entry_points: bool,
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "FIELD,...",
+        help = "display only these details, in this order",
+        long_help = "display only the details named in the comma separated list, in the order they\n\
+                     are listed, instead of the details selected by verbosity and the other options.\n\
+                     Options such as --artifacts, --readme, and --packages still set how much detail\n\
+                     a listed section shows. deps may be used for dependencies"
+    )]
+    fields: Vec<Field>,
     #[arg(
         long,
         short = 'v',
@@ -149,7 +541,8 @@ struct Cli {
         help = "display less project details",
         long_help = "disable displaying any extra project details. This option can be passed up to 2\n\
                      times, if passed twice and no other details are selected, the command will output\n\
-                     nothing. This option overrides verbosity, but not explicit project detail options",
+                     nothing, but will still exit with a failure if the project does not exist. This\n\
+                     option overrides verbosity, but not explicit project detail options",
     )]
     quiet: u8,
 
@@ -161,9 +554,340 @@ struct Cli {
         long_help = "select the output format:\n\
                      pretty: write key-data using tables and colors directly to stdout\n\
                      interactive: write key-data using tables and colors to an alternate screen.\n\
-                     \t\tthis mode can accept further command to update the display interactively",
+                     \t\tthis mode can accept further command to update the display interactively\n\
+                     json: write key-data as a JSON object\n\
+                     line: write only the name, version, summary, and license on a single line\n\
+                     yaml: write key-data as a YAML document",
     )]
     format: Format,
+    #[arg(
+        long,
+        help = "print a JSON Schema of the json and yaml output, then exit",
+        long_help = "print a JSON Schema describing the objects written by the json and yaml formats,\n\
+                     then exit without looking up any project"
+    )]
+    json_schema: bool,
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with = "project",
+        help = "print NAME as the index normalizes it, then exit",
+        long_help = "print the PEP 503 normalized form of NAME, which is how kayak looks it up on the\n\
+                     index, then exit without looking up any project. A name that is not a valid\n\
+                     project name is an error"
+    )]
+    normalize_name: Option<String>,
+    #[arg(
+        long,
+        short = 'o',
+        value_name = "FILE",
+        help = "write output to FILE instead of stdout",
+        long_help = "write the text, pretty, json, or yaml output to FILE instead of stdout. Unless\n\
+                     --color always is given, the output is written without styling"
+    )]
+    output: Option<PathBuf>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ColorChoice::Auto,
+        help = "when to style output",
+        long_help = "select when output is styled:\n\
+                     auto: style output written to a terminal, but not to a pipe or an --output file\n\
+                     always: always style output\n\
+                     never: never style output"
+    )]
+    color: ColorChoice,
+    #[arg(
+        long,
+        visible_alias = "ascii",
+        help = "mark URLs with ASCII labels instead of emoji",
+        long_help = "mark each URL with a short ASCII label, such as [home] or [bug], instead of an\n\
+                     emoji icon that some terminals and fonts cannot display. This is the default\n\
+                     when output is not written to a terminal"
+    )]
+    no_emoji: bool,
+    #[arg(
+        long,
+        value_name = "COLS",
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "lay out details COLS columns wide",
+        long_help = "lay out the pretty format, and wrap styled readmes, COLS columns wide instead of\n\
+                     as wide as the terminal. Output that is not written to a terminal is otherwise\n\
+                     80 columns wide"
+    )]
+    width: Option<u16>,
+    #[arg(
+        long,
+        value_name = "COMMAND",
+        help = "page long readmes through COMMAND",
+        long_help = "when the text output includes a readme and is too long for the terminal, page it\n\
+                     through COMMAND. Defaults to $PAGER, or less if that is not set. Output is never\n\
+                     paged when it is not written to a terminal, and an empty COMMAND disables paging"
+    )]
+    pager: Option<String>,
+    #[arg(
+        long,
+        help = "ignore the config file",
+        long_help = "do not read default options from the config file, kayak/config.ini under\n\
+                     $XDG_CONFIG_HOME or ~/.config"
+    )]
+    no_config: bool,
+    #[arg(
+        long,
+        help = "report why details may differ from the index",
+        long_help = "once done, report to stderr any conditions that made the displayed details differ\n\
+                     from what the index has, such as classifiers that are not recognized, wheels with\n\
+                     names that cannot be parsed, distributions that could not be inspected, or requests\n\
+                     that were redirected elsewhere"
+    )]
+    warnings: bool,
+    #[arg(
+        long,
+        help = "fetch the list of projects on the index again",
+        long_help = "fetch the list of every project on the index again, even if a recent one is\n\
+                     cached. The list is used to suggest projects, and is otherwise only fetched once\n\
+                     a day and kept under $XDG_CACHE_HOME/kayak or ~/.cache/kayak. Without a project\n\
+                     to look up, kayak exits once the list is cached"
+    )]
+    refresh_index: bool,
+    #[arg(
+        long,
+        requires = "project",
+        conflicts_with_all = ["from_url", "from_file", "inspect_url", "provides", "projects_file", "versions"],
+        help = "print the JSON api response as it was sent",
+        long_help = "instead of displaying project details, pretty-print the whole JSON api response\n\
+                     for the project, or for the version if one is given. This includes metadata that\n\
+                     is otherwise never displayed, such as the last serial or a project's\n\
+                     vulnerabilities"
+    )]
+    raw_metadata: bool,
+}
+
+fn parse_platform(tag: &str) -> Result<distribution::Platform, String> {
+    distribution::Platform::from_tag(tag).ok_or(format!("unrecognized platform tag {tag:?}"))
+}
+
+fn parse_date_format(format: &str) -> Result<String, String> {
+    if ui::is_date_format(format) {
+        Ok(format.to_string())
+    } else {
+        Err(format!("{format:?} is not a valid strftime format"))
+    }
+}
+
+fn parse_proxy(proxy: &str) -> Result<String, String> {
+    ureq::Proxy::new(proxy)
+        .map(|_| proxy.to_string())
+        .map_err(|e| format!("{proxy:?} is not a valid proxy: {e}"))
+}
+
+fn parse_python_version(version: &str) -> Result<(u32, u32), String> {
+    distribution::parse_python_version(version)
+        .ok_or(format!("{version:?} is not a Python version like 3.11"))
+}
+
+/// Config file options that can be given a default, other than index, format, date-format, and color
+const CONFIG_FLAGS: [&str; 18] = [
+    "time",
+    "local-time",
+    "relative-time",
+    "summary",
+    "license",
+    "downloads",
+    "people",
+    "urls",
+    "keywords",
+    "classifiers",
+    "describe-tags",
+    "dependencies",
+    "executables",
+    "entry-points",
+    "pre",
+    "warnings",
+    "no-emoji",
+    "offline",
+];
+const CONFIG_COUNTS: [&str; 5] = ["artifacts", "readme", "packages", "verbose", "quiet"];
+
+/// Whether the options a and b cannot be used together, as clap checks for the command line
+fn options_conflict(command: &clap::Command, a: &str, b: &str) -> bool {
+    let conflicts_with = |a: &str, b: &str| {
+        command
+            .get_arguments()
+            .find(|arg| arg.get_id() == a)
+            .is_some_and(|arg| {
+                command
+                    .get_arg_conflicts_with(arg)
+                    .iter()
+                    .any(|c| c.get_id() == b)
+            })
+    };
+    let (a, b) = (a.replace('-', "_"), b.replace('-', "_"));
+    conflicts_with(&a, &b) || conflicts_with(&b, &a)
+}
+
+/// Fill in any option not given on the command line from the config file
+///
+/// A config option is also left out when the command line gives one it conflicts with, as the
+/// command line always wins, and --verbose and --quiet are taken from the config file only
+/// when neither is given. Config options that conflict with each other are an error, as clap
+/// has already checked the command line before the config file is read.
+fn apply_config(cli: &mut Cli, matches: &ArgMatches, config: &Config) -> Result<()> {
+    let known = CONFIG_FLAGS
+        .iter()
+        .chain(CONFIG_COUNTS.iter())
+        .chain(
+            [
+                "index",
+                "format",
+                "date-format",
+                "downloads-source",
+                "width",
+                "color",
+                "fields",
+            ]
+            .iter(),
+        )
+        .copied()
+        .collect::<Vec<_>>();
+    config.check_options(&known)?;
+    let command = Cli::command();
+    let from_cli = |option: &str| {
+        matches.value_source(&option.replace('-', "_")) == Some(ValueSource::CommandLine)
+    };
+    let given = command
+        .get_arguments()
+        .map(|arg| arg.get_id().as_str())
+        .filter(|id| from_cli(id))
+        .collect::<Vec<_>>();
+    let overridden = |option: &str| {
+        from_cli(option)
+            || (["verbose", "quiet"].contains(&option)
+                && (from_cli("verbose") || from_cli("quiet")))
+            || given.iter().any(|g| options_conflict(&command, option, g))
+    };
+    // the options taken from the config file, to check them against each other
+    let mut applied = Vec::new();
+
+    for option in CONFIG_FLAGS {
+        if overridden(option) {
+            continue;
+        }
+        if let Some(value) = config.get_bool(option)? {
+            if value {
+                applied.push(option);
+            }
+            let flag = match option {
+                "time" => &mut cli.time,
+                "local-time" => &mut cli.local_time,
+                "relative-time" => &mut cli.relative_time,
+                "summary" => &mut cli.summary,
+                "license" => &mut cli.license,
+                "downloads" => &mut cli.downloads,
+                "people" => &mut cli.people,
+                "urls" => &mut cli.urls,
+                "keywords" => &mut cli.keywords,
+                "classifiers" => &mut cli.classifiers,
+                "describe-tags" => &mut cli.describe_tags,
+                "dependencies" => &mut cli.dependencies,
+                "executables" => &mut cli.executables,
+                "entry-points" => &mut cli.entry_points,
+                "pre" => &mut cli.pre,
+                "warnings" => &mut cli.warnings,
+                "no-emoji" => &mut cli.no_emoji,
+                "offline" => &mut cli.offline,
+                _ => unreachable!(),
+            };
+            *flag = value;
+        }
+    }
+    for option in CONFIG_COUNTS {
+        if overridden(option) {
+            continue;
+        }
+        if let Some(value) = config.get_count(option)? {
+            if value > 0 {
+                applied.push(option);
+            }
+            let count = match option {
+                "artifacts" => &mut cli.artifacts,
+                "readme" => &mut cli.readme,
+                "packages" => &mut cli.packages,
+                "verbose" => &mut cli.verbose,
+                "quiet" => &mut cli.quiet,
+                _ => unreachable!(),
+            };
+            *count = value;
+        }
+    }
+    if let (false, Some(index)) = (overridden("index"), config.get("index")) {
+        cli.index = index.to_string();
+    }
+    if let (false, Some(format)) = (overridden("format"), config.get("format")) {
+        cli.format =
+            Format::from_str(format, true).map_err(|e| anyhow!("config option format: {e}"))?;
+    }
+    if let (false, Some(format)) = (overridden("date-format"), config.get("date-format")) {
+        cli.date_format =
+            Some(parse_date_format(format).map_err(|e| anyhow!("config option date-format: {e}"))?);
+    }
+    if let (false, Some(source)) = (
+        overridden("downloads-source"),
+        config.get("downloads-source"),
+    ) {
+        cli.downloads_source = Some(source.to_string());
+    }
+    if let (false, Some(width)) = (overridden("width"), config.get("width")) {
+        cli.width = Some(
+            width
+                .parse()
+                .ok()
+                .filter(|w| *w > 0)
+                .ok_or_else(|| anyhow!("config option width: {width:?} is not a width"))?,
+        );
+    }
+    if let (false, Some(fields)) = (overridden("fields"), config.get("fields")) {
+        cli.fields = fields
+            .split(',')
+            .map(|f| Field::from_str(f.trim(), true))
+            .collect::<Result<_, _>>()
+            .map_err(|e| anyhow!("config option fields: {e}"))?;
+    }
+    if let (false, Some(color)) = (overridden("color"), config.get("color")) {
+        cli.color =
+            ColorChoice::from_str(color, true).map_err(|e| anyhow!("config option color: {e}"))?;
+    }
+    applied.extend(
+        [
+            "index",
+            "format",
+            "date-format",
+            "downloads-source",
+            "width",
+            "fields",
+            "color",
+        ]
+        .into_iter()
+        .filter(|option| !overridden(option) && config.get(option).is_some()),
+    );
+    for (i, a) in applied.iter().enumerate() {
+        if let Some(b) = applied[i + 1..]
+            .iter()
+            .find(|b| options_conflict(&command, a, b))
+        {
+            return Err(anyhow!(
+                "config options {a} and {b} cannot be used together"
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(ValueEnum, Debug, Clone, PartialEq)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -172,47 +896,349 @@ enum Format {
     Text,
     Pretty,
     Interactive,
-    //Json,
+    Json,
+    Line,
+    Yaml,
+}
+
+/// Exit statuses that scripts can rely on
+///
+/// clap also exits with Usage when the command line cannot be parsed
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Exit {
+    Failure = 1,
+    Usage = 2,
+    /// The project, version, or distribution does not exist on the index
+    NotFound = 3,
+    /// The index could not be reached, or failed to answer; trying again later may succeed
+    Network = 4,
+    /// The inspected wheel does not provide what --has-import or --has-command asked about
+    NotProvided = 5,
 }
 
 #[derive(Debug)]
-pub struct DisplayFields {
-    pub name: bool,
-    pub versions: bool,
-    pub time: bool,
-    pub summary: bool,
-    pub license: bool,
-    pub urls: bool,
-    pub keywords: bool,
-    pub classifiers: bool,
-    pub artifacts: u8,
-    pub dependencies: bool,
-    pub readme: u8,
-    pub packages: bool,
-    pub executables: bool,
-}
-
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+struct NotProvided(String);
+
+impl fmt::Display for NotProvided {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NotProvided: {}", self.0)
+    }
+}
+
+impl std::error::Error for NotProvided {}
+
+fn exit_status(err: &anyhow::Error) -> Exit {
+    for cause in err.chain() {
+        if cause.is::<NotProvided>() {
+            return Exit::NotProvided;
+        }
+        if let Some(err) = cause.downcast_ref::<warehouse::Error>() {
+            return match err {
+                warehouse::Error::NotFound
+                | warehouse::Error::NoValidVersion
+                | warehouse::Error::NoReleases => Exit::NotFound,
+                warehouse::Error::InvalidName
+                | warehouse::Error::InvalidVersion
+                | warehouse::Error::UnsupportedIndex => Exit::Usage,
+                warehouse::Error::DigestMismatch
+                | warehouse::Error::MetadataOnly
+                | warehouse::Error::Redirected(_)
+                | warehouse::Error::Offline(_)
+                | warehouse::Error::UnsafeFilename(_)
+                | warehouse::Error::AlreadyExists(_)
+                | warehouse::Error::NoDigest(_) => Exit::Failure,
+            };
+        }
+        if let Some(distribution::Error::InvalidPackageName(_)) = cause.downcast_ref() {
+            return Exit::Usage;
+        }
+        if let Some(err) = cause.downcast_ref::<ureq::Error>() {
+            return match err {
+                ureq::Error::Status(404, _) => Exit::NotFound,
+                ureq::Error::Status(status, _) if *status >= 500 => Exit::Network,
+                ureq::Error::Status(_, _) => Exit::Failure,
+                ureq::Error::Transport(_) => Exit::Network,
+            };
+        }
+    }
+    Exit::Failure
+}
+
+/// Write the files an inspected wheel installs in format
+fn display_files(
+    format: &Format,
+    package: &package_inspect::Package,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    match format {
+        Format::Text | Format::Pretty => text::display_files(package, display_fields, out),
+        Format::Json => json::display_files(package, display_fields, out),
+        Format::Yaml => yaml::display_files(package, display_fields, out),
+        Format::Interactive | Format::Line => {
+            unreachable!("--files is rejected with --format line or interactive")
+        }
+    }
+}
+
+/// Write how large an inspected wheel is in format
+fn display_size_report(
+    format: &Format,
+    package: &package_inspect::Package,
+    download_size: Option<usize>,
+    largest: usize,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    match format {
+        Format::Text | Format::Pretty => {
+            text::display_size_report(package, download_size, largest, display_fields, out)
+        }
+        Format::Json => {
+            json::display_size_report(package, download_size, largest, display_fields, out)
+        }
+        Format::Yaml => {
+            yaml::display_size_report(package, download_size, largest, display_fields, out)
+        }
+        Format::Interactive | Format::Line => {
+            unreachable!("--size-report is rejected with --format line or interactive")
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let result = run();
+    for warning in warnings::take() {
+        eprintln!("warning: {warning}");
+    }
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            ExitCode::from(exit_status(&err) as u8)
+        }
+    }
+}
+
+fn run() -> Result<()> {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if cli.json_schema {
+        let schema = serde_json::to_string_pretty(&json::schema())?;
+        match &cli.output {
+            Some(path) => fs::write(path, schema + "\n")?,
+            None => println!("{schema}"),
+        }
+        return Ok(());
+    }
+    if let Some(name) = &cli.normalize_name {
+        let normalized = distribution::normalize_package_name(name)
+            .with_context(|| format!("{name:?} is not a valid project name"))?;
+        println!("{normalized}");
+        return Ok(());
+    }
+    // only interactive can start without a project, and json can also report on a
+    // --projects-file, which clap has no way to express
+    if cli.project.is_none()
+        && cli.from_url.is_none()
+        && cli.from_file.is_none()
+        && cli.inspect_url.is_none()
+        && cli.provides.is_none()
+        && !cli.refresh_index
+    {
+        let missing = match cli.format {
+            Format::Interactive if cli.verify_download => {
+                Some("--verify-download requires <PROJECT>, --from-url, or --from-file")
+            }
+            Format::Interactive if cli.download.is_some() => {
+                Some("--download requires <PROJECT>, --from-url, or --from-file")
+            }
+            Format::Interactive if cli.diff.is_some() => {
+                Some("--diff requires <PROJECT>, --from-url, or --from-file")
+            }
+            Format::Interactive if cli.requirements => {
+                Some("--requirements requires <PROJECT>, --from-url, or --from-file")
+            }
+            Format::Interactive => None,
+            Format::Json | Format::Line if cli.projects_file.is_some() => None,
+            Format::Json => Some(
+                "either <PROJECT>, --projects-file, --from-url, or --from-file is required with --format json",
+            ),
+            Format::Line => Some(
+                "either <PROJECT>, --projects-file, --from-url, or --from-file is required with --format line",
+            ),
+            _ => Some("either <PROJECT>, --from-url, or --from-file is required"),
+        };
+        if let Some(missing) = missing {
+            Cli::command()
+                .error(ErrorKind::MissingRequiredArgument, missing)
+                .exit();
+        }
+    }
+    if !cli.no_config {
+        if let Some(path) = config::default_path().filter(|p| p.is_file()) {
+            let config = Config::load(&path)
+                .with_context(|| format!("could not read config file {}", path.display()))?;
+            apply_config(&mut cli, &matches, &config)
+                .with_context(|| format!("invalid config file {}", path.display()))?;
+        }
+    }
+    if cli.warnings {
+        warnings::enable();
+    }
+    if cli.no_redirect {
+        warehouse::forbid_redirects();
+    }
+    if let Some(proxy) = &cli.proxy {
+        warehouse::use_proxy(proxy)?;
+    }
+    if cli.offline {
+        warehouse::go_offline();
+    }
 
     // do sanity checks before making network requests
+    if let Some(p) = &cli.project {
+        let normalized = distribution::normalize_package_name(p)
+            .with_context(|| format!("{p:?} is not a valid project name"))?;
+        if normalized != *p && cli.verbose >= 1 {
+            eprintln!("looking up {p} by its normalized name {normalized}");
+        }
+    };
     if let Some(v) = &cli.package_version {
         Version::parse(v).ok_or(warehouse::Error::InvalidVersion)?;
     };
-    if let Some(d) = &cli.dist {
-        if d != "sdist" {
-            distribution::CompatibilityTag::from_tag(d).ok_or(warehouse::Error::InvalidVersion)?;
-        };
+    if let Some(v) = &cli.diff {
+        Version::parse(v).ok_or(warehouse::Error::InvalidVersion)?;
     };
+    // a list of files has no single line, and nothing to interact with
+    if cli.files && matches!(cli.format, Format::Line | Format::Interactive) {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--files cannot be used with --format line or interactive",
+            )
+            .exit();
+    }
+    if cli.size_report && matches!(cli.format, Format::Line | Format::Interactive) {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--size-report cannot be used with --format line or interactive",
+            )
+            .exit();
+    }
+    // the interactive display takes over the terminal, and has nothing to write to a file
+    if cli.output.is_some() && matches!(cli.format, Format::Interactive) {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--output cannot be used with --format interactive",
+            )
+            .exit();
+    }
+
+    if cli.index != warehouse::PYPI_URI && !cli.offline {
+        // an index that does not describe its api version may still serve everything needed
+        match warehouse::index_is_supported(&cli.index) {
+            Ok(warehouse::SupportLevel::Supported) => (),
+            Ok(warehouse::SupportLevel::SomewhatSupported) => eprintln!(
+                "warning: {} uses a newer API version than this client; some metadata may be dropped",
+                warehouse::redact_index(&cli.index)
+            ),
+            Ok(warehouse::SupportLevel::Unsupported) => Err(warehouse::Error::UnsupportedIndex)?,
+            Err(_) => eprintln!(
+                "warning: could not tell which API version {} uses",
+                warehouse::redact_index(&cli.index)
+            ),
+        }
+    }
+
+    let options = ProjectOptions {
+        index: cli.index.clone(),
+        prereleases: cli.pre,
+        metadata_only: cli.metadata_only,
+        // the best wheel for this host is a guess, so explicit overrides are never second-guessed
+        host: Some(distribution::Host {
+            python: cli.python_version,
+            platform: cli.platform.clone(),
+        }),
+        detect_host: true,
+        cache_dir: kayak::cache::default_dir(),
+        api: cli.api,
+        // pypistats only counts PyPI, and must not learn the names of projects on other indexes
+        downloads_source: cli.downloads_source.clone().or_else(|| {
+            (cli.index == warehouse::PYPI_URI).then(|| warehouse::DOWNLOADS_URI.to_string())
+        }),
+    };
+
+    let mut out: Box<dyn Write> = match &cli.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+
+    if cli.refresh_index {
+        let count = picker::refresh_index_projects(&options)?;
+        let nothing_else = cli.project.is_none()
+            && cli.projects_file.is_none()
+            && cli.from_url.is_none()
+            && cli.from_file.is_none()
+            && cli.inspect_url.is_none()
+            && cli.provides.is_none()
+            && !matches!(cli.format, Format::Interactive);
+        if nothing_else {
+            if cli.quiet == 0 {
+                writeln!(
+                    out,
+                    "cached {count} projects on {}",
+                    warehouse::redact_index(&options.index)
+                )?;
+            }
+            return Ok(());
+        }
+    }
+
+    if let Some(import_name) = &cli.provides {
+        let candidates = if cli.candidates.is_empty() {
+            picker::guess_providers(&options, import_name, 20)?
+        } else {
+            cli.candidates.clone()
+        };
+        let providers = picker::find_providers(import_name, &candidates, &options);
+        if providers.is_empty() {
+            Err(warehouse::Error::NotFound)?;
+        }
+        for provider in providers {
+            writeln!(out, "{provider}")?;
+        }
+        return Ok(());
+    }
+
+    if cli.metadata_only
+        && (cli.artifacts > 0 || cli.packages > 0 || cli.executables || cli.entry_points)
+    {
+        eprintln!(
+            "note: artifacts, packages, executables, and entry points are not displayed with \
+             --metadata-only"
+        );
+    }
 
     // quiet and verbosity are quick ways to turn on/off output
     // map them to real fields here
-    let display_fields = DisplayFields {
+    let mut display_fields = DisplayFields {
         name: cli.quiet < 2,
         versions: cli.versions,
-        time: cli.dist.is_some() || (cli.verbose >= 1 && cli.quiet < 1),
+        sort_by: cli.sort_by,
+        limit: cli.limit,
+        reverse: cli.reverse,
+        stable_only: cli.stable_only,
+        time: cli.dist.is_some() || (cli.verbose >= 1 && cli.quiet < 1) || cli.time,
+        local_time: cli.local_time,
+        date_format: cli.date_format.clone(),
+        relative_time: cli.relative_time,
         summary: cli.quiet < 1 || cli.summary,
         license: cli.verbose >= 1 && cli.quiet < 1 || cli.license,
+        downloads: cli.downloads,
+        people: cli.verbose >= 1 && cli.quiet < 1 || cli.people,
         urls: cli.verbose >= 1 && cli.quiet < 1 || cli.urls,
         keywords: cli.verbose >= 2 && cli.quiet < 1 || cli.keywords,
         classifiers: cli.verbose >= 2 && cli.quiet < 1 || cli.classifiers,
@@ -223,7 +1249,16 @@ fn main() -> Result<()> {
         } else {
             0
         },
-        dependencies: cli.verbose >= 4 && cli.quiet < 1 || cli.dependencies,
+        describe_tags: cli.describe_tags,
+        dependencies: cli.verbose >= 4 && cli.quiet < 1
+            || cli.dependencies
+            || !cli.extras.is_empty(),
+        extras: (!cli.extras.is_empty()).then(|| {
+            cli.extras
+                .iter()
+                .map(|e| distribution::normalize_extra_name(e))
+                .collect()
+        }),
         readme: if cli.readme > 0 {
             cli.readme
         } else if cli.verbose >= 5 && cli.quiet < 1 {
@@ -233,22 +1268,319 @@ fn main() -> Result<()> {
         },
         packages: cli.packages,
         executables: cli.executables,
+        entry_points: cli.entry_points,
+        color: match cli.color {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            // a pipe or a CI log gets plain text, as there is no terminal to style it for
+            ColorChoice::Auto => cli.output.is_none() && io::stdout().is_terminal(),
+        },
+        emoji: !cli.no_emoji && cli.output.is_none() && io::stdout().is_terminal(),
+        width: cli.width,
+        order: None,
     };
+    if !cli.fields.is_empty() {
+        let listed = |field| cli.fields.contains(&field);
+        display_fields.name = listed(Field::Name);
+        display_fields.time = listed(Field::Time);
+        display_fields.license = listed(Field::License);
+        display_fields.people = listed(Field::People);
+        display_fields.summary = listed(Field::Summary);
+        display_fields.downloads = listed(Field::Downloads);
+        display_fields.urls = listed(Field::Urls);
+        display_fields.keywords = listed(Field::Keywords);
+        display_fields.classifiers = listed(Field::Classifiers);
+        display_fields.artifacts = if listed(Field::Artifacts) {
+            cli.artifacts.max(1)
+        } else {
+            0
+        };
+        display_fields.dependencies = listed(Field::Dependencies);
+        display_fields.packages = if listed(Field::Packages) {
+            cli.packages.max(1)
+        } else {
+            0
+        };
+        display_fields.executables = listed(Field::Executables);
+        display_fields.entry_points = listed(Field::EntryPoints);
+        display_fields.readme = if listed(Field::Readme) {
+            cli.readme.max(1)
+        } else {
+            0
+        };
+        display_fields.order = Some(cli.fields.clone());
+    }
+    if cli.metadata_only {
+        display_fields.artifacts = 0;
+        display_fields.packages = 0;
+        display_fields.executables = false;
+        display_fields.entry_points = false;
+    }
+
+    if cli.raw_metadata {
+        if let Some(project) = &cli.project {
+            let response =
+                warehouse::fetch_raw(&options.index, project, cli.package_version.as_deref())?;
+            writeln!(out, "{}", serde_json::to_string_pretty(&response)?)?;
+            return Ok(());
+        }
+    }
+
+    if let Some(url) = &cli.inspect_url {
+        if cli.metadata_only {
+            Err(anyhow!("--inspect-url cannot be used with --metadata-only"))?;
+        }
+        package_inspect::check_wheel_url(url)?;
+        let package = package_inspect::fetch(url)
+            .with_context(|| format!("could not inspect {}", warehouse::redact_index(url)))?;
+        if cli.files {
+            display_files(&cli.format, &package, &display_fields, &mut out)?;
+        } else if cli.size_report {
+            let largest = cli.largest.unwrap_or(0);
+            display_size_report(
+                &cli.format,
+                &package,
+                None,
+                largest,
+                &display_fields,
+                &mut out,
+            )?;
+        } else {
+            text::display_inspected(&package, &display_fields, &mut out)?;
+        }
+        return Ok(());
+    }
+
+    if let Some(path) = &cli.projects_file {
+        if !matches!(cli.format, Format::Json | Format::Line) {
+            Err(anyhow!("--projects-file requires --format json or line"))?;
+        }
+        let listing = if path.as_os_str() == "-" {
+            io::read_to_string(io::stdin())?
+        } else {
+            fs::read_to_string(path)
+                .with_context(|| format!("could not read projects file {}", path.display()))?
+        };
+        let projects = listing
+            .lines()
+            .map(str::trim)
+            .filter(|l| !(l.is_empty() || l.starts_with('#')))
+            .map(|l| {
+                let mut selector = l.split_whitespace().map(str::to_string);
+                Project::new(
+                    selector.next().unwrap_or_default(),
+                    selector.next(),
+                    selector.next(),
+                    options.clone(),
+                )
+            })
+            .collect();
+        if let Format::Line = cli.format {
+            text::display_lines(projects, &mut out)?;
+        } else {
+            json::display_lines(projects, display_fields, &mut out)?;
+        }
+        return Ok(());
+    }
+
+    let mut project = match (&cli.from_url, &cli.from_file) {
+        (Some(url), _) => Some(Project::from_url(url, options.clone())?),
+        (_, Some(path)) => Some(Project::from_file(path, options.clone())?),
+        (None, None) => cli
+            .project
+            .map(|p| Project::new(p, cli.package_version, cli.dist, options.clone())),
+    };
+
+    if cli.verify_download {
+        let distribution = project
+            .as_mut()
+            .ok_or(anyhow!("--verify-download requires a project"))?
+            .distribution()?;
+        distribution.verify()?;
+        eprintln!(
+            "verified {} {}",
+            distribution.filename,
+            ui::format_sha256(distribution)
+        );
+    }
+
+    if let Some(dir) = &cli.download {
+        let distribution = project
+            .as_mut()
+            .ok_or(anyhow!("--download requires a project"))?
+            .distribution()?;
+        let saved = distribution.download(dir)?;
+        if distribution.digests.sha256.is_none() {
+            eprintln!(
+                "warning: the index publishes no sha256 digest for {}, so it was not verified",
+                distribution.filename
+            );
+        }
+        writeln!(out, "{}", saved.display())?;
+        return Ok(());
+    }
+
+    if cli.has_import.is_some() || cli.has_command.is_some() {
+        let project = project
+            .as_mut()
+            .ok_or(anyhow!("--has-import and --has-command require a project"))?;
+        let package = project.import_package()?;
+        let mut provided = Vec::new();
+        if let Some(name) = &cli.has_import {
+            if !package.provides_packages().contains(name) {
+                Err(NotProvided(format!(
+                    "the wheel does not provide the import {name}"
+                )))?;
+            }
+            provided.push(format!("the import {name}"));
+        }
+        if let Some(command) = &cli.has_command {
+            if !package.executables().contains(command) {
+                Err(NotProvided(format!(
+                    "the wheel does not provide the command {command}"
+                )))?;
+            }
+            provided.push(format!("the command {command}"));
+        }
+        if cli.quiet < 2 {
+            let filename = &project.distribution()?.filename;
+            writeln!(out, "{filename} provides {}", provided.join(" and "))?;
+        }
+        return Ok(());
+    }
+
+    if cli.files {
+        let package = project
+            .as_mut()
+            .ok_or(anyhow!("--files requires a project"))?
+            .import_package()?;
+        display_files(&cli.format, package, &display_fields, &mut out)?;
+        return Ok(());
+    }
+
+    if cli.size_report {
+        let project = project
+            .as_mut()
+            .ok_or(anyhow!("--size-report requires a project"))?;
+        // inspecting may pass over a selected sdist for a wheel, so inspect before sizing
+        project.import_package()?;
+        let download_size = project.distribution()?.size;
+        let package = project.import_package()?;
+        let largest = cli.largest.unwrap_or(0);
+        display_size_report(
+            &cli.format,
+            package,
+            Some(download_size),
+            largest,
+            &display_fields,
+            &mut out,
+        )?;
+        return Ok(());
+    }
 
-    let project = cli
-        .project
-        .map(|p| Project::new(p, cli.package_version, cli.dist));
+    if let Some(other_version) = cli.diff {
+        let project = project.ok_or(anyhow!("--diff requires a project"))?;
+        let other = Project::new(
+            project.package_selector(),
+            Some(other_version),
+            None,
+            options,
+        );
+        text::display_diff(project, other, &mut out)?;
+        return Ok(());
+    }
+
+    if cli.requirements {
+        text::display_requirements(
+            project.ok_or(anyhow!("--requirements requires a project"))?,
+            &mut out,
+        )?;
+        return Ok(());
+    }
+
+    // the project must exist, even when none of its details are displayed, so that -qq can be
+    // used to check whether it does. Everything is fetched up front, while the spinner is shown
+    if let (Some(project), false) = (&mut project, matches!(cli.format, Format::Interactive)) {
+        let spinner = matches!(cli.format, Format::Text | Format::Pretty)
+            .then(|| Spinner::start(format!("fetching {}", project.package_selector())));
+        project.prefetch(&display_fields)?;
+        let mut warning = None;
+        if !display_fields.versions {
+            let version = project.version()?;
+            // the Python on the PATH is only looked for when the release restricts it
+            let python = version
+                .requires_python
+                .as_ref()
+                .and_then(|requires_python| {
+                    let host = options.host.clone().unwrap_or_default().or_detected();
+                    Some((requires_python, host.python?))
+                });
+            if let Some((requires_python, (major, minor))) = python {
+                if distribution::admits_python(requires_python, (major, minor)) == Some(false) {
+                    let asked = if cli.python_version.is_some() {
+                        "you asked about"
+                    } else {
+                        "the Python on the PATH is"
+                    };
+                    warning = Some(format!(
+                        "this release requires Python {requires_python}; {asked} {major}.{minor}"
+                    ));
+                }
+            }
+        }
+        drop(spinner);
+        if let (Format::Text, Some(name)) = (&cli.format, project.resolved_name()) {
+            if cli.quiet < 2 {
+                eprintln!("note: resolved `{name}`");
+            }
+        }
+        if let Some(warning) = warning {
+            eprintln!("warning: {warning}");
+        }
+    }
 
     match cli.format {
+        Format::Text
+            if display_fields.readme > 0 && cli.output.is_none() && io::stdout().is_terminal() =>
+        {
+            let mut output = Vec::new();
+            text::display(
+                project.expect("a project is requred to output text"),
+                display_fields,
+                &mut output,
+            )?;
+            let pager = cli
+                .pager
+                .or_else(|| std::env::var("PAGER").ok())
+                .unwrap_or_else(|| "less".to_string());
+            let height = crossterm::terminal::size().map(|(_, h)| h).unwrap_or(24);
+            text::page(&String::from_utf8(output)?, &pager, height, &mut out)?;
+        }
         Format::Text => text::display(
             project.expect("a project is requred to output text"),
             display_fields,
+            &mut out,
         )?,
         Format::Pretty => pretty::display(
             project.expect("a project is requred to pretty print text"),
             display_fields,
+            &mut out,
+        )?,
+        Format::Json => json::display(
+            project.expect("a project is requred to output json"),
+            display_fields,
+            &mut out,
+        )?,
+        Format::Line => text::display_line(
+            project.expect("a project is requred to output a line"),
+            &mut out,
+        )?,
+        Format::Yaml => yaml::display(
+            project.expect("a project is requred to output yaml"),
+            display_fields,
+            &mut out,
         )?,
-        Format::Interactive => interactive::run(project, display_fields)?,
+        Format::Interactive => interactive::run(project, display_fields, options)?,
     };
 
     Ok(())
@@ -1,17 +1,21 @@
+use crate::distribution::{normalize_package_name, WheelName};
+use crate::warehouse;
 use anyhow::{anyhow, Result};
 use csv;
 use ini;
 use mail_parser;
-use std::collections::{HashMap, HashSet};
-use std::io::Read;
-use ureq;
+use pep440::Version;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::io::{self, Read};
 use url::Url;
-use zip::read::read_zipfile_from_stream;
+use zip::read::{read_zipfile_from_stream, ZipArchive};
+use zip::result::ZipError;
 
-fn dist_filename(entry: &str) -> Option<&str> {
+fn dist_filename(entry: &str) -> Option<(&str, &str)> {
     if let Some((dir, name)) = entry.split_once('/') {
         if dir.ends_with(".dist-info") {
-            Some(name)
+            Some((dir, name))
         } else {
             None
         }
@@ -20,6 +24,18 @@ fn dist_filename(entry: &str) -> Option<&str> {
     }
 }
 
+/// Whether a .dist-info directory belongs to the distribution named by the wheel's filename
+fn is_dist_dir_for(dir: &str, wheel: &WheelName) -> bool {
+    let Some((name, version)) = dir
+        .strip_suffix(".dist-info")
+        .and_then(|d| d.rsplit_once('-'))
+    else {
+        return false;
+    };
+    normalize_package_name(name).ok() == normalize_package_name(&wheel.distribution).ok()
+        && Version::parse(version).as_ref() == Some(&wheel.version)
+}
+
 fn is_dist_dir(entry: &str) -> bool {
     if let Some((dir, _)) = entry.split_once('/') {
         dir.ends_with(".dist-info")
@@ -48,27 +64,167 @@ fn is_data_dir(entry: &str) -> bool {
     }
 }
 
-pub fn fetch(wheel_url: &str) -> Result<Package> {
-    Url::parse(wheel_url)?;
-    let mut record: Result<Record> = Err(anyhow!("no RECORD file found in distribution"));
-    let mut metadata: Result<Metadata> = Err(anyhow!("no METADATA file found in distribution"));
-    let mut entry_points: Option<EntryPoints> = None;
-    let mut wheel = ureq::get(wheel_url).call()?.into_reader();
-    while let Some(zipfile) = read_zipfile_from_stream(&mut wheel)? {
-        if let Some(name) = dist_filename(zipfile.name()) {
-            if name == "RECORD" {
-                record = Record::from_file(zipfile);
-            } else if name == "METADATA" {
-                metadata = Metadata::from_file(zipfile);
-            } else if name == "entry_points.txt" {
-                entry_points = Some(EntryPoints::from_file(zipfile)?);
-            };
+/// The path a RECORD entry is importable from once installed, if it is importable at all
+///
+/// Files under the .data directory's purelib and platlib are installed to the same place as the
+/// wheel's root, while its other directories (scripts, headers, data) are not importable
+fn import_path(entry: &str) -> Option<&str> {
+    match install_path(entry)? {
+        (InstallScheme::SitePackages, path) if !is_dist_dir(path) => Some(path),
+        _ => None,
+    }
+}
+
+/// Where a RECORD entry is installed to, and the path it is installed at within that location
+///
+/// The .data directory's purelib and platlib are installed alongside the wheel's root and its
+/// .dist-info directory, while scripts, headers, and data each have their own location
+fn install_path(entry: &str) -> Option<(InstallScheme, &str)> {
+    if !is_data_dir(entry) {
+        return Some((InstallScheme::SitePackages, entry));
+    }
+    let (scheme, path) = data_filename(entry)?.split_once('/')?;
+    let scheme = match scheme {
+        "purelib" | "platlib" => InstallScheme::SitePackages,
+        "scripts" => InstallScheme::Scripts,
+        "headers" => InstallScheme::Headers,
+        "data" => InstallScheme::Data,
+        _ => return None,
+    };
+    Some((scheme, path))
+}
+
+/// The metadata files found in one .dist-info directory
+#[derive(Default)]
+struct DistInfo {
+    record: Option<Result<Record>>,
+    metadata: Option<Result<Metadata>>,
+    entry_points: Option<EntryPoints>,
+}
+
+impl DistInfo {
+    /// Whether the files required to describe a distribution were found
+    fn is_complete(&self) -> bool {
+        self.record.is_some() && self.metadata.is_some()
+    }
+}
+
+/// Read a file from the wheel into dist_infos, if it is one of the known .dist-info files
+fn collect_dist_info<R: Read>(
+    dist_infos: &mut HashMap<String, DistInfo>,
+    entry: &str,
+    file: R,
+) -> Result<()> {
+    if let Some((dir, name)) = dist_filename(entry) {
+        let dist_info = dist_infos.entry(dir.to_string()).or_default();
+        if name == "RECORD" {
+            dist_info.record = Some(Record::from_file(file));
+        } else if name == "METADATA" {
+            dist_info.metadata = Some(Metadata::from_file(file));
+        } else if name == "entry_points.txt" {
+            dist_info.entry_points = Some(EntryPoints::from_file(file)?);
         };
+    };
+    Ok(())
+}
+
+/// Read the .dist-info files while the wheel is downloaded, without keeping the whole wheel
+///
+/// This relies on each file's local header carrying its size, which is not true of wheels written
+/// by streaming zip writers
+fn read_streamed(wheel_url: &str) -> Result<HashMap<String, DistInfo>> {
+    let mut dist_infos = HashMap::new();
+    let mut wheel = warehouse::get(wheel_url)?.into_reader();
+    while let Some(zipfile) = read_zipfile_from_stream(&mut wheel)? {
+        let entry = zipfile.name().to_string();
+        collect_dist_info(&mut dist_infos, &entry, zipfile)?;
+    }
+    Ok(dist_infos)
+}
+
+/// Read the .dist-info files by downloading the whole wheel and reading its central directory
+///
+/// The wheel is only kept in memory, so nothing else can read or replace it while it is read.
+fn read_downloaded(wheel_url: &str) -> Result<HashMap<String, DistInfo>> {
+    let mut wheel = Vec::new();
+    warehouse::get(wheel_url)?
+        .into_reader()
+        .read_to_end(&mut wheel)?;
+    let mut archive = ZipArchive::new(io::Cursor::new(wheel))?;
+    let mut dist_infos = HashMap::new();
+    for i in 0..archive.len() {
+        let zipfile = archive.by_index(i)?;
+        let entry = zipfile.name().to_string();
+        collect_dist_info(&mut dist_infos, &entry, zipfile)?;
+    }
+    Ok(dist_infos)
+}
+
+/// Check that url can be fetched as a wheel, by its scheme and filename, without fetching it
+pub fn check_wheel_url(url: &str) -> Result<()> {
+    let url = Url::parse(url)?;
+    if url.cannot_be_a_base() || !matches!(url.scheme(), "http" | "https") {
+        return Err(anyhow!("{url} is not a url that can be downloaded"));
     }
+    let filename = url
+        .path_segments()
+        .and_then(|mut s| s.next_back())
+        .unwrap_or_default();
+    WheelName::from_filename(filename)
+        .map_err(|_| anyhow!("{filename:?} is not the filename of a wheel"))?;
+    Ok(())
+}
+
+pub fn fetch(wheel_url: &str) -> Result<Package> {
+    let url = Url::parse(wheel_url)?;
+    let mut dist_infos = match read_streamed(wheel_url) {
+        Ok(dist_infos) if dist_infos.values().any(DistInfo::is_complete) => dist_infos,
+        // streaming stops early, or fails, on files with sizes only in a trailing data descriptor,
+        // but any other failure would only happen again if the wheel were downloaded whole
+        Err(err)
+            if !matches!(
+                err.downcast_ref(),
+                Some(ZipError::InvalidArchive(_) | ZipError::UnsupportedArchive(_))
+            ) =>
+        {
+            return Err(err);
+        }
+        _ => {
+            crate::warnings::warn(format!(
+                "could not read {wheel_url} as it was streamed, so it was downloaded whole"
+            ));
+            read_downloaded(wheel_url)?
+        }
+    };
+    let dist_info = if dist_infos.len() > 1 {
+        // only the .dist-info directory named for this wheel describes it
+        let filename = url
+            .path_segments()
+            .and_then(|mut s| s.next_back())
+            .unwrap_or_default();
+        let wheel = WheelName::from_filename(filename)?;
+        let dir = dist_infos
+            .keys()
+            .find(|dir| is_dist_dir_for(dir, &wheel))
+            .cloned()
+            .ok_or(anyhow!(
+                "distribution contains {} .dist-info directories, but none for {}-{}",
+                dist_infos.len(),
+                wheel.distribution,
+                wheel.version
+            ))?;
+        dist_infos.remove(&dir).unwrap_or_default()
+    } else {
+        dist_infos.into_values().next().unwrap_or_default()
+    };
     Ok(Package {
-        record: record?,
-        metadata: metadata?,
-        entry_points,
+        record: dist_info
+            .record
+            .unwrap_or(Err(anyhow!("no RECORD file found in distribution")))?,
+        metadata: dist_info
+            .metadata
+            .unwrap_or(Err(anyhow!("no METADATA file found in distribution")))?,
+        entry_points: dist_info.entry_points,
     })
 }
 
@@ -77,7 +233,7 @@ struct RecordEntry {
     entry: String,
     algo: String,
     hash: String,
-    size: usize,
+    size: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -100,8 +256,9 @@ impl Record {
                 if r.len() != 3 {
                     return None;
                 };
-                let size = r[2].parse().ok()?;
-                let (algo, hash) = r[1].split_once('=')?;
+                // RECORD lists itself, and may list compiled files, without a hash or size
+                let size = r[2].parse().ok();
+                let (algo, hash) = r[1].split_once('=').unwrap_or_default();
                 Some(RecordEntry {
                     entry: r[0].to_string(),
                     algo: algo.to_string(),
@@ -199,6 +356,19 @@ impl ObjectReference {
     }
 }
 
+impl fmt::Display for ObjectReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.module)?;
+        if let Some(object) = &self.object {
+            write!(f, ":{object}")?;
+        }
+        if let Some(extras) = &self.extras {
+            write!(f, " [{extras}]")?;
+        }
+        Ok(())
+    }
+}
+
 // https://packaging.python.org/en/latest/specifications/entry-points/#file-format
 #[derive(Debug)]
 struct EntryPoints {
@@ -225,6 +395,75 @@ impl EntryPoints {
     }
 }
 
+/// How a top-level import name is provided
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportKind {
+    /// A directory with an __init__ module
+    Package,
+    /// A single file
+    Module,
+    /// A directory without an __init__ module, which may be shared with other distributions
+    Namespace,
+}
+
+impl fmt::Display for ImportKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportKind::Package => write!(f, "package"),
+            ImportKind::Module => write!(f, "module"),
+            ImportKind::Namespace => write!(f, "namespace"),
+        }
+    }
+}
+
+/// How a command is installed onto the PATH
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExecutableKind {
+    /// A file in the wheel's .data/scripts directory
+    Script,
+    /// A console_scripts entry point, which the installer writes a launcher for
+    EntryPoint,
+    /// Both a script and an entry point of the same name, so one overwrites the other
+    ScriptAndEntryPoint,
+}
+
+impl fmt::Display for ExecutableKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExecutableKind::Script => write!(f, "script"),
+            ExecutableKind::EntryPoint => write!(f, "entry point"),
+            ExecutableKind::ScriptAndEntryPoint => write!(f, "script and entry point"),
+        }
+    }
+}
+
+/// The locations a wheel installs files to, in the order they are displayed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InstallScheme {
+    /// The importable files and the .dist-info directory, alongside other distributions
+    SitePackages,
+    /// Executables, installed onto the PATH
+    Scripts,
+    /// C headers
+    Headers,
+    /// Anything else, installed relative to the environment's root
+    Data,
+}
+
+impl fmt::Display for InstallScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InstallScheme::SitePackages => write!(f, "site-packages"),
+            InstallScheme::Scripts => write!(f, "scripts"),
+            InstallScheme::Headers => write!(f, "headers"),
+            InstallScheme::Data => write!(f, "data"),
+        }
+    }
+}
+
+/// A file's path within the location it is installed to, and its size if RECORD gives one
+pub type InstalledFile = (String, Option<usize>);
+
 #[derive(Debug)]
 pub struct Package {
     metadata: Metadata,
@@ -233,30 +472,78 @@ pub struct Package {
 }
 
 impl Package {
+    /// The project name given by the distribution's METADATA
+    pub fn name(&self) -> &str {
+        &self.metadata.name
+    }
+
+    /// The version given by the distribution's METADATA
+    pub fn version(&self) -> &str {
+        &self.metadata.version
+    }
+
     /// Returns all top-level import names that this package provides
     ///
     /// this could be package roots, top-level modules, or namespace packages
     pub fn provides_packages(&self) -> HashSet<String> {
-        self.record
+        self.provides_import_kinds().into_keys().collect()
+    }
+
+    /// Returns all top-level import names that this package provides, classified by their kind
+    pub fn provides_import_kinds(&self) -> HashMap<String, ImportKind> {
+        let mut kinds = HashMap::new();
+        for path in self
+            .record
             .entries
             .iter()
-            .map(|r| r.entry.clone())
-            .filter(|r| !(is_dist_dir(r) || is_data_dir(r)))
-            .map(|r| {
-                let top = if let Some((a, _)) = r.split_once('/') {
-                    a
-                } else {
-                    &r
-                };
-                if let Some(package) = top.strip_suffix(".py") {
-                    package.to_string()
-                } else {
-                    top.to_string()
+            .filter_map(|r| import_path(&r.entry))
+        {
+            if let Some((top, rest)) = path.split_once('/') {
+                let kind = kinds
+                    .entry(top.to_string())
+                    .or_insert(ImportKind::Namespace);
+                // PEP-420 namespace packages are directories without an __init__ module
+                if rest == "__init__.py" {
+                    *kind = ImportKind::Package;
                 }
+            } else {
+                let module = path.strip_suffix(".py").unwrap_or(path);
+                kinds.insert(module.to_string(), ImportKind::Module);
+            }
+        }
+        kinds
+    }
+
+    /// Returns every file RECORD lists, grouped by where it is installed, with its size if known
+    ///
+    /// Locations are in the order of [`InstallScheme`], and files are sorted by their path within
+    /// each location. Files in an unknown .data directory are not installed, so are left out.
+    pub fn installed_files(&self) -> Vec<(InstallScheme, Vec<InstalledFile>)> {
+        let mut schemes = BTreeMap::<_, Vec<_>>::new();
+        for record in &self.record.entries {
+            if let Some((scheme, path)) = install_path(&record.entry) {
+                schemes
+                    .entry(scheme)
+                    .or_default()
+                    .push((path.to_string(), record.size));
+            }
+        }
+        schemes
+            .into_iter()
+            .map(|(scheme, mut files)| {
+                files.sort_unstable();
+                (scheme, files)
             })
             .collect()
     }
 
+    /// Returns the total size of every file RECORD lists, which is how much the wheel installs
+    ///
+    /// Files that RECORD gives no size for, such as RECORD itself, are not counted.
+    pub fn installed_size(&self) -> usize {
+        self.record.entries.iter().filter_map(|r| r.size).sum()
+    }
+
     /// Returns all scripts, entry-points, binaries this package provides
     pub fn provides_executables(&self) -> HashSet<String> {
         self.record
@@ -273,6 +560,58 @@ impl Package {
             .collect()
     }
 
+    /// Return every entry-point group, other than the script groups, with the name of each entry
+    /// point and the object it refers to
+    ///
+    /// Groups, and the entry points within them, are sorted by name
+    pub fn entry_point_groups(&self) -> Vec<(String, Vec<(String, String)>)> {
+        let Some(entry_points) = &self.entry_points else {
+            return Vec::new();
+        };
+        let mut groups = entry_points
+            .group
+            .iter()
+            .filter(|(g, _)| *g != "console_scripts" && *g != "gui_scripts")
+            .map(|(g, names)| {
+                let mut names = names
+                    .iter()
+                    .map(|(n, o)| (n.to_string(), o.to_string()))
+                    .collect::<Vec<_>>();
+                names.sort_unstable();
+                (g.to_string(), names)
+            })
+            .collect::<Vec<_>>();
+        groups.sort_unstable();
+        groups
+    }
+
+    /// Return every command the package installs, whether as a script file or a console_scripts
+    /// entry point
+    ///
+    /// Commands are sorted by name, and a command installed both ways is only listed once
+    pub fn executables(&self) -> Vec<String> {
+        self.executable_kinds()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Return every command the package installs, sorted by name, with how it is installed
+    pub fn executable_kinds(&self) -> Vec<(String, ExecutableKind)> {
+        let mut kinds = self
+            .provides_executables()
+            .into_iter()
+            .map(|name| (name, ExecutableKind::Script))
+            .collect::<BTreeMap<_, _>>();
+        for name in self.console_scripts() {
+            kinds
+                .entry(name)
+                .and_modify(|kind| *kind = ExecutableKind::ScriptAndEntryPoint)
+                .or_insert(ExecutableKind::EntryPoint);
+        }
+        kinds.into_iter().collect()
+    }
+
     /// Return the names from the special entry_points group console_scripts
     pub fn console_scripts(&self) -> Vec<String> {
         if let Some(entry_points) = &self.entry_points {
@@ -1,20 +1,262 @@
+use crate::cache;
 use crate::distribution;
 use crate::package_inspect;
+use crate::report::ProjectReport;
 use crate::warehouse;
+use crate::warnings;
+use crate::{Api, DisplayFields};
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use pep440::Version;
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+/// Search candidate projects for those whose automatically selected wheel provides import_name as
+/// a top-level importable name
+///
+/// Candidates that have no wheel, or otherwise cannot be inspected, are skipped.
+pub fn find_providers<'a>(
+    import_name: &str,
+    candidates: &'a [String],
+    options: &ProjectOptions,
+) -> Vec<&'a String> {
+    candidates
+        .iter()
+        .filter(|candidate| {
+            Project::new(candidate.to_string(), None, None, options.clone())
+                .import_package()
+                .is_ok_and(|p| p.provides_packages().contains(import_name))
+        })
+        .collect()
+}
+
+/// Guess which projects on the index might provide import_name
+///
+/// Inspecting every project on an index is not feasible, so only projects whose normalized name
+/// contains the normalized import name are returned, closest names first, up to limit projects.
+pub fn guess_providers(
+    options: &ProjectOptions,
+    import_name: &str,
+    limit: usize,
+) -> Result<Vec<String>> {
+    let import_name = distribution::normalize_package_name(import_name)?;
+    let mut candidates = index_projects(options)?
+        .iter()
+        .filter(|p| distribution::normalize_package_name(p).is_ok_and(|p| p.contains(&import_name)))
+        .cloned()
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|p| (p.len(), p.to_string()));
+    candidates.truncate(limit);
+    Ok(candidates)
+}
+
+/// The most versions listed when a requested version does not exist, newest first
+const MAX_LISTED_VERSIONS: usize = 10;
+
+/// The most edits a misspelled project name may be from a suggested one
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Every project on each index, kept for the rest of the session once retrieved
+static INDEX_PROJECTS: OnceLock<Mutex<HashMap<String, Arc<Vec<String>>>>> = OnceLock::new();
+
+/// Every project on the index, from the session, then the cache directory, then the index itself
+fn index_projects(options: &ProjectOptions) -> Result<Arc<Vec<String>>> {
+    let mut session = INDEX_PROJECTS.get_or_init(Default::default).lock().unwrap();
+    if let Some(projects) = session.get(&options.index) {
+        return Ok(projects.clone());
+    }
+    // a stale list is better than none when the index cannot be asked for a fresh one
+    let ttl = if warehouse::is_offline() {
+        Duration::MAX
+    } else {
+        cache::PROJECTS_TTL
+    };
+    let cached = options
+        .cache_dir
+        .as_deref()
+        .and_then(|dir| cache::read_projects(dir, &options.index, ttl));
+    let projects = Arc::new(match cached {
+        Some(projects) => projects,
+        None => fetch_index_projects(options)?,
+    });
+    session.insert(options.index.to_string(), Arc::clone(&projects));
+    Ok(projects)
+}
+
+/// Fetch every project on the index, replacing any list in the cache directory
+///
+/// Failing to write the cache is not an error, the list will just be fetched again next session.
+fn fetch_index_projects(options: &ProjectOptions) -> Result<Vec<String>> {
+    let mut projects = warehouse::fetch_projects(&options.index)?
+        .into_iter()
+        .collect::<Vec<_>>();
+    projects.sort_unstable();
+    if let Some(dir) = &options.cache_dir {
+        if let Err(e) = cache::write_projects(dir, &options.index, &projects) {
+            warnings::warn(format!("could not cache the projects on the index: {e}"));
+        }
+    }
+    Ok(projects)
+}
+
+/// Fetch every project on the index again, even if a recent list is cached, returning how many
+/// there are
+pub fn refresh_index_projects(options: &ProjectOptions) -> Result<usize> {
+    let projects = Arc::new(fetch_index_projects(options)?);
+    let count = projects.len();
+    INDEX_PROJECTS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .insert(options.index.to_string(), projects);
+    Ok(count)
+}
+
+/// Suggest the projects on the index whose names are closest to name, closest first
+///
+/// Only names within a few edits of name are considered, and at most limit are returned.
+pub fn suggest_projects(options: &ProjectOptions, name: &str, limit: usize) -> Result<Vec<String>> {
+    // a cheap approximation of normalization, this is compared against every project on the index
+    let fold = |n: &str| n.to_ascii_lowercase().replace(['_', '.'], "-");
+    let name = fold(name);
+    let projects = index_projects(options)?;
+    let mut suggestions = projects
+        .iter()
+        .filter(|p| p.len().abs_diff(name.len()) <= MAX_SUGGESTION_DISTANCE)
+        .map(|p| (strsim::levenshtein(&name, &fold(p)), p))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .collect::<Vec<_>>();
+    suggestions.sort();
+    Ok(suggestions
+        .into_iter()
+        .take(limit)
+        .map(|(_, p)| p.to_string())
+        .collect())
+}
+
+/// Whether url is a wheel whose filename can be parsed, warning about wheels that cannot be
+fn is_parseable_wheel(url: &warehouse::DistributionUrl) -> bool {
+    if url.packagetype != "bdist_wheel" {
+        return false;
+    }
+    let parsed = distribution::WheelName::from_filename(&url.filename).is_ok();
+    if !parsed {
+        warnings::warn(format!(
+            "skipped wheel with unparseable name {}",
+            url.filename
+        ));
+    }
+    parsed
+}
+
+/// Settings that control how a Project selects its metadata, shared across every lookup made in
+/// a session
+#[derive(Clone, Debug)]
+pub struct ProjectOptions {
+    /// The package index to retrieve metadata from
+    pub index: String,
+    /// Allow pre-releases to be automatically selected
+    pub prereleases: bool,
+    /// Never select or inspect a distribution, only ever retrieve project metadata
+    pub metadata_only: bool,
+    /// Prefer wheels that can be installed into this environment, when no distribution is given
+    pub host: Option<distribution::Host>,
+    /// Guess the parts of host that are not given from this machine, once a wheel is picked
+    pub detect_host: bool,
+    /// Where the list of every project on the index is kept between sessions, if anywhere
+    pub cache_dir: Option<PathBuf>,
+    /// The API of the index that projects are looked up with
+    pub api: Api,
+    /// Where recent downloads are counted, a url like [`warehouse::DOWNLOADS_URI`], if anywhere
+    pub downloads_source: Option<String>,
+}
+
+impl Default for ProjectOptions {
+    fn default() -> Self {
+        ProjectOptions {
+            index: warehouse::PYPI_URI.to_string(),
+            prereleases: false,
+            metadata_only: false,
+            host: None,
+            detect_host: false,
+            cache_dir: None,
+            api: Api::Json,
+            downloads_source: None,
+        }
+    }
+}
+
+/// How many candidate versions are fetched at once when automatically selecting a version
+const CONCURRENT_VERSION_FETCHES: usize = 4;
+
+/// Fetch the first of candidates, which are ordered greatest first, that is valid and not yanked
+///
+/// Candidates are fetched concurrently in small batches, as the greatest few versions may have
+/// been yanked, but a later batch is only fetched if no earlier version was selected. A candidate
+/// the index has no metadata for is skipped, but any other failure to fetch one is returned, as a
+/// lesser version must not be shown in place of one that could not be fetched.
+fn fetch_greatest_valid_version(
+    index: &str,
+    package: &str,
+    candidates: &[Version],
+) -> Result<Option<warehouse::PackageVersion>> {
+    for batch in candidates.chunks(CONCURRENT_VERSION_FETCHES) {
+        let fetched = thread::scope(|scope| {
+            batch
+                .iter()
+                .map(|v| {
+                    scope.spawn(move || {
+                        warehouse::PackageVersion::fetch(index, package, &v.to_string())
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|fetch| fetch.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        for version in fetched {
+            match version {
+                Ok(version) if !version.yanked => return Ok(Some(version)),
+                Ok(_) => (),
+                Err(err) if is_not_found(&err) => (),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn is_not_found(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<warehouse::Error>(),
+        Some(warehouse::Error::NotFound)
+    )
+}
+
+/// Whether any of display_fields can only be found by inspecting a distribution
+fn inspects(display_fields: &DisplayFields) -> bool {
+    display_fields.packages > 0 || display_fields.executables || display_fields.entry_points
+}
 
 // lazy loader for project metadata types
 pub struct Project {
     package_selector: String,
     version_selector: Option<String>,
     distribution_selector: Option<String>,
+    options: ProjectOptions,
     package: Option<warehouse::Package>,
     version: Option<warehouse::PackageVersion>,
     distribution: Option<warehouse::DistributionUrl>,
-    import_package: Option<package_inspect::Package>,
+    import_package: Option<Result<package_inspect::Package, String>>,
+    /// Where the metadata was read from, when it was not fetched from the index
+    origin: Option<String>,
+    /// The recent downloads, once looked up, if they could be
+    downloads: Option<Option<warehouse::Downloads>>,
 }
 
 impl Project {
@@ -22,15 +264,64 @@ impl Project {
         user_package: String,
         user_version: Option<String>,
         user_distribution: Option<String>,
+        options: ProjectOptions,
     ) -> Self {
         Project {
             package_selector: user_package,
             version_selector: user_version,
             distribution_selector: user_distribution,
+            options,
             package: None,
             version: None,
             distribution: None,
             import_package: None,
+            origin: None,
+            downloads: None,
+        }
+    }
+
+    /// Look up the project described by the JSON api response at url, rather than at the path its
+    /// name has on the index
+    ///
+    /// Anything the response does not include, such as every version of a project when the url is
+    /// for one of its versions, is still retrieved from the index.
+    pub fn from_url(url: &str, options: ProjectOptions) -> Result<Self> {
+        let (package, version) = warehouse::fetch_url(url)?;
+        let mut project = Self::from_metadata(package, version, options);
+        project.origin = Some(warehouse::redact_index(url));
+        Ok(project)
+    }
+
+    /// Look up the project described by a saved JSON api response, as [`Project::from_url`] does
+    pub fn from_file(path: &Path, options: ProjectOptions) -> Result<Self> {
+        let (package, version) = warehouse::read_file(path)
+            .with_context(|| format!("could not read metadata from {}", path.display()))?;
+        let mut project = Self::from_metadata(package, version, options);
+        project.origin = Some(path.display().to_string());
+        Ok(project)
+    }
+
+    fn from_metadata(
+        package: Option<warehouse::Package>,
+        version: Option<warehouse::PackageVersion>,
+        options: ProjectOptions,
+    ) -> Self {
+        let name = match (&package, &version) {
+            (_, Some(version)) => version.name.clone(),
+            (Some(package), None) => package.name.clone(),
+            (None, None) => unreachable!("a response is always read as some metadata"),
+        };
+        Project {
+            package_selector: name,
+            version_selector: version.as_ref().map(|v| v.version.clone()),
+            distribution_selector: None,
+            options,
+            package,
+            version,
+            distribution: None,
+            import_package: None,
+            origin: None,
+            downloads: None,
         }
     }
 
@@ -42,136 +333,409 @@ impl Project {
         self.distribution.is_some()
     }
 
+    pub fn is_package_fetched(&self) -> bool {
+        self.package.is_some()
+    }
+
+    pub fn is_version_fetched(&self) -> bool {
+        self.version.is_some()
+    }
+
+    /// Whether inspecting the distribution has been tried, successfully or not
+    pub fn is_package_inspected(&self) -> bool {
+        self.import_package.is_some()
+    }
+
     pub fn package(&mut self) -> Result<&warehouse::Package> {
         if self.package.is_none() {
-            self.package = Some(warehouse::Package::fetch(
-                warehouse::PYPI_URI,
-                &self.package_selector,
-            )?)
+            let fetch = match self.options.api {
+                Api::Json => warehouse::Package::fetch,
+                Api::Simple => warehouse::Package::fetch_simple,
+            };
+            self.package = Some(
+                fetch(&self.options.index, &self.package_selector)
+                    .map_err(|err| self.explain_not_found(err))?,
+            )
         }
         Ok(self.package.as_ref().unwrap())
     }
 
     pub fn version(&mut self) -> Result<&warehouse::PackageVersion> {
+        if self.version.is_none() && self.options.api == Api::Simple {
+            // the simple api has no more to say about a version than the project's files do
+            self.version = Some(if let Some(version) = self.version_selector.clone() {
+                self.check_version_exists(&version)?;
+                warehouse::PackageVersion::from_release(self.package()?, &version)
+                    .ok_or(warehouse::Error::NotFound)?
+            } else {
+                let prereleases = self.options.prereleases;
+                let package = self.package()?;
+                if !package.has_releases() {
+                    return Err(warehouse::Error::NoReleases)?;
+                }
+                package
+                    .candidate_versions(prereleases)
+                    .iter()
+                    .rev()
+                    .filter_map(|v| {
+                        warehouse::PackageVersion::from_release(package, &v.normalize())
+                    })
+                    .find(|v| !v.yanked)
+                    .ok_or(warehouse::Error::NoValidVersion)?
+            });
+        }
         if self.version.is_none() {
-            self.version = if let Some(version) = &self.version_selector {
-                Some(warehouse::PackageVersion::fetch(
-                    warehouse::PYPI_URI,
+            self.version = if let Some(version) = self.version_selector.clone() {
+                // a project that is already fetched says whether the version exists, for free
+                if self.package.is_some() {
+                    self.check_version_exists(&version)?;
+                }
+                match warehouse::PackageVersion::fetch(
+                    &self.options.index,
                     &self.package_selector,
-                    version,
-                )?)
+                    &version,
+                ) {
+                    Ok(version) => Some(version),
+                    Err(err) => {
+                        // the version may be missing, or the whole project may be
+                        if is_not_found(&err) {
+                            self.package()?;
+                            self.check_version_exists(&version)?;
+                        }
+                        return Err(err);
+                    }
+                }
             } else {
+                let prereleases = self.options.prereleases;
+                let package = self.package()?;
+                if !package.has_releases() {
+                    return Err(warehouse::Error::NoReleases)?;
+                }
+                let mut candidates = package.candidate_versions(prereleases);
+                candidates.reverse();
                 Some(
-                    self.package()?
-                        .ordered_versions()
-                        .iter()
-                        .rev()
-                        .filter_map(|v| {
-                            warehouse::PackageVersion::fetch(
-                                warehouse::PYPI_URI,
-                                &self.package_selector,
-                                &v.to_string(),
-                            )
-                            .ok()
-                        })
-                        .find(|v| !v.yanked)
-                        .ok_or(warehouse::Error::NotFound)?,
+                    fetch_greatest_valid_version(
+                        &self.options.index,
+                        &self.package_selector,
+                        &candidates,
+                    )?
+                    .ok_or(warehouse::Error::NoValidVersion)?,
                 )
             };
         }
         Ok(self.version.as_ref().unwrap())
     }
 
+    /// Fail if the fetched project has no version equal to version, listing those it does have
+    fn check_version_exists(&mut self, version: &str) -> Result<()> {
+        let Some(requested) = Version::parse(version) else {
+            return Err(warehouse::Error::InvalidVersion)?;
+        };
+        let package = self.package()?;
+        if !package.has_releases() {
+            let context = format!("version {version} not found");
+            return Err(anyhow::Error::from(warehouse::Error::NoReleases).context(context));
+        }
+        let mut versions = package.ordered_versions();
+        if versions.contains(&requested) {
+            return Ok(());
+        }
+        versions.reverse();
+        let mut available = versions
+            .iter()
+            .take(MAX_LISTED_VERSIONS)
+            .map(Version::normalize)
+            .collect::<Vec<_>>();
+        if versions.len() > MAX_LISTED_VERSIONS {
+            available.push(format!("and {} more", versions.len() - MAX_LISTED_VERSIONS));
+        }
+        let context = if available.is_empty() {
+            format!("version {version} not found; this project has no versions")
+        } else {
+            format!(
+                "version {version} not found; available: {}",
+                available.join(", ")
+            )
+        };
+        Err(anyhow::Error::from(warehouse::Error::NotFound).context(context))
+    }
+
     pub fn distribution(&mut self) -> Result<&warehouse::DistributionUrl> {
+        if self.options.metadata_only {
+            return Err(warehouse::Error::MetadataOnly)?;
+        }
         if self.distribution.is_none() {
             self.distribution = if let Some(distribution) = &self.distribution_selector {
-                eprintln!("{:?}", distribution);
                 if distribution == "sdist" {
                     self.select_sdist()
                 } else {
-                    self.select_bdist()
+                    self.select_bdist()?
                 }
             } else {
                 self.pick_best_bdist()
             }
         }
-        self.distribution
-            .as_ref()
-            .ok_or(distribution::Error::InvalidWheelName.into())
+        if self.distribution.is_none() {
+            let selector = self.distribution_selector.clone();
+            let missing = match selector.as_deref() {
+                Some("sdist") => "no sdist".to_string(),
+                Some(tag) => format!("no wheel matching {tag}"),
+                None => "no wheel to select".to_string(),
+            };
+            // a version that fails to load is its own, better, explanation
+            let context = match self.available_distributions(selector.as_deref()) {
+                Ok(available) if available.is_empty() => {
+                    format!("{missing}; this version has no distributions")
+                }
+                Ok(available) => format!("{missing}; available: {}", available.join(", ")),
+                Err(_) => missing,
+            };
+            return Err(anyhow::Error::from(warehouse::Error::NotFound).context(context));
+        }
+        Ok(self.distribution.as_ref().unwrap())
+    }
+
+    /// The compatibility tags of this version's wheels, and sdist if it has one
+    ///
+    /// When near is given the tags most like it are first, otherwise they are in order.
+    fn available_distributions(&mut self, near: Option<&str>) -> Result<Vec<String>> {
+        let version = self.version()?;
+        let mut tags = version
+            .urls
+            .iter()
+            .filter_map(|u| distribution::WheelName::from_filename(&u.filename).ok())
+            .map(|w| w.compatibility_tag.to_string())
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+        if version.urls.iter().any(|u| u.packagetype == "sdist") {
+            tags.push("sdist".to_string());
+        }
+        if let Some(near) = near {
+            let near = near.trim().to_ascii_lowercase();
+            tags.sort_by_key(|t| strsim::levenshtein(&near, &t.to_ascii_lowercase()));
+        }
+        Ok(tags)
+    }
+
+    /// The project's recent downloads, or None when no source is given or it cannot say
+    ///
+    /// Download counts are only ever an extra, so failing to look them up is only a warning.
+    pub fn downloads(&mut self) -> Option<&warehouse::Downloads> {
+        if self.downloads.is_none() {
+            let downloads = self.options.downloads_source.as_deref().and_then(|source| {
+                warehouse::Downloads::fetch(source, &self.package_selector)
+                    .inspect_err(|e| {
+                        warnings::warn(format!(
+                            "could not count the downloads of {}: {e}",
+                            self.package_selector
+                        ))
+                    })
+                    .ok()
+            });
+            self.downloads = Some(downloads);
+        }
+        self.downloads.as_ref().unwrap().as_ref()
     }
 
     pub fn import_package(&mut self) -> Result<&package_inspect::Package> {
         if self.import_package.is_none() {
-            if self.distribution_selector == Some("sdist".to_string()) {
-                // cannot extract package from a source distribution
-                return Err(warehouse::Error::InvalidName)?;
-            } else if self.distribution()?.packagetype == "sdist" {
-                // select a new distribution
-                self.distribution = None;
-                if self.distribution()?.packagetype == "sdist" {
-                    // maybe there are no wheels
-                    return Err(warehouse::Error::InvalidName)?;
+            match self.inspect() {
+                Ok(package) => self.import_package = Some(Ok(package)),
+                Err(err) => {
+                    // remember the failure, so that every later display does not download again
+                    self.import_package = Some(Err(format!("{err:#}")));
+                    return Err(err);
                 }
             }
-            self.import_package = Some(package_inspect::fetch(&self.distribution()?.url)?);
         }
-        Ok(self.import_package.as_ref().unwrap())
+        match self.import_package.as_ref().unwrap() {
+            Ok(package) => Ok(package),
+            Err(err) => Err(anyhow!("{err}")),
+        }
+    }
+
+    fn inspect(&mut self) -> Result<package_inspect::Package> {
+        if self.distribution_selector == Some("sdist".to_string()) {
+            // cannot extract package from a source distribution
+            warnings::warn(
+                "an sdist cannot be inspected for packages, executables, or entry points",
+            );
+            return Err(warehouse::Error::InvalidName)?;
+        }
+        // a wheel that was not selected is explained by the selection error, not by the sdist
+        if self.distribution().is_err()
+            && self.version().is_ok_and(|v| {
+                v.urls.iter().all(|u| {
+                    u.packagetype != "bdist_wheel"
+                        || distribution::WheelName::from_filename(&u.filename).is_err()
+                }) && v.urls.iter().any(|u| u.packagetype == "sdist")
+            })
+        {
+            warnings::warn(
+                "this version has no wheel, only an sdist, which cannot be inspected for \
+                 packages, executables, or entry points",
+            );
+        }
+        package_inspect::fetch(&self.distribution()?.url)
+    }
+
+    /// Fetch everything that display_fields would display, so that displaying it never blocks
+    ///
+    /// Only a project, version, or distribution that does not exist is an error; a distribution
+    /// that cannot be inspected is left for the display to report.
+    pub fn prefetch(&mut self, display_fields: &DisplayFields) -> Result<()> {
+        if display_fields.versions {
+            self.package()?;
+            return Ok(());
+        }
+        self.version()?;
+        if self.distribution_selector.is_some() {
+            self.distribution()?;
+        }
+        if inspects(display_fields) && !self.options.metadata_only {
+            let _ = self.import_package();
+        }
+        if display_fields.downloads {
+            self.downloads();
+        }
+        Ok(())
+    }
+
+    /// Whether prefetch would have to wait on the index or a distribution download
+    pub fn needs_fetch(&self, display_fields: &DisplayFields) -> bool {
+        if display_fields.versions {
+            !self.is_package_fetched()
+        } else {
+            !self.is_version_fetched()
+                || (inspects(display_fields)
+                    && !self.options.metadata_only
+                    && !self.is_package_inspected())
+                || (display_fields.downloads && self.downloads.is_none())
+        }
+    }
+
+    /// Add suggestions of similarly named projects to an error for a project that does not exist
+    fn explain_not_found(&self, err: anyhow::Error) -> anyhow::Error {
+        if !is_not_found(&err) {
+            return err;
+        }
+        match suggest_projects(&self.options, &self.package_selector, 3) {
+            Ok(suggestions) if !suggestions.is_empty() => err.context(format!(
+                "NotFound: there is no project named {}, did you mean {}?",
+                self.package_selector,
+                suggestions
+                    .iter()
+                    .map(|s| format!("`{s}`"))
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            )),
+            _ => err,
+        }
     }
 
+    /// Resolve every detail of the selected version into a single report
+    pub fn report(&mut self) -> Result<ProjectReport> {
+        ProjectReport::resolve(self)
+    }
+
+    /// Select the version's sdist, preferring the standard .tar.gz and then the newest upload
     fn select_sdist(&mut self) -> Option<warehouse::DistributionUrl> {
         self.version()
             .ok()?
             .urls
             .iter()
-            .find(|u| u.packagetype == "sdist")
-            .to_owned()
+            .filter(|u| u.packagetype == "sdist")
+            .max_by_key(|u| {
+                (
+                    u.filename.to_ascii_lowercase().ends_with(".tar.gz"),
+                    u.uploaded(),
+                )
+            })
             .cloned()
     }
 
-    fn select_bdist(&mut self) -> Option<warehouse::DistributionUrl> {
-        let requested =
-            distribution::CompatibilityTag::from_tag(self.distribution_selector.as_ref()?)?;
-        self.version()
-            .ok()?
+    /// Select the wheel whose tag is the selector, or else whose filename or tag contains it
+    ///
+    /// Of several builds of the same wheel the newest is selected, but a selector that is part of
+    /// wheels with different tags is an error, listing them.
+    fn select_bdist(&mut self) -> Result<Option<warehouse::DistributionUrl>> {
+        let Some(selector) = self.distribution_selector.clone() else {
+            return Ok(None);
+        };
+        let Ok(version) = self.version() else {
+            return Ok(None);
+        };
+        let wheels = version
             .urls
             .iter()
-            .filter(|u| u.packagetype == "bdist_wheel")
-            .filter(|u| distribution::WheelName::from_filename(&u.filename).is_ok())
-            .filter(|u| {
-                distribution::WheelName::from_filename(&u.filename)
-                    .unwrap()
-                    .compatibility_tag
-                    == requested
+            .filter(|u| is_parseable_wheel(u))
+            .map(|u| {
+                (
+                    distribution::WheelName::from_filename(&u.filename).unwrap(),
+                    u,
+                )
             })
-            .max_by(|a, b| {
-                distribution::WheelName::from_filename(&a.filename)
-                    .unwrap()
-                    .build_tag
-                    .unwrap_or(distribution::BuildTag {
-                        number: 0,
-                        string: String::default(),
-                    })
-                    .cmp(
-                        &distribution::WheelName::from_filename(&b.filename)
-                            .unwrap()
-                            .build_tag
-                            .unwrap_or(distribution::BuildTag {
-                                number: 0,
-                                string: String::default(),
-                            }),
-                    )
+            .collect::<Vec<_>>();
+        let newest = |matched: Vec<&(distribution::WheelName, &warehouse::DistributionUrl)>| {
+            matched
+                .into_iter()
+                .max_by_key(|(wheel, _)| wheel.build_tag.as_ref())
+                .map(|(_, u)| (*u).clone())
+        };
+        if let Some(requested) = distribution::CompatibilityTag::from_tag_lenient(&selector) {
+            let exact = wheels
+                .iter()
+                .filter(|(wheel, _)| wheel.compatibility_tag.matches(&requested))
+                .collect::<Vec<_>>();
+            if !exact.is_empty() {
+                return Ok(newest(exact));
+            }
+        }
+        let partial = selector.trim().to_ascii_lowercase();
+        let matched = wheels
+            .iter()
+            .filter(|(wheel, u)| {
+                u.filename.to_ascii_lowercase().contains(&partial)
+                    || wheel
+                        .compatibility_tag
+                        .to_string()
+                        .to_ascii_lowercase()
+                        .contains(&partial)
             })
-            .to_owned()
-            .cloned()
+            .collect::<Vec<_>>();
+        let mut tags = matched
+            .iter()
+            .map(|(wheel, _)| wheel.compatibility_tag.to_string())
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+        if tags.len() > 1 {
+            return Err(anyhow!(
+                "{selector} matches wheels with different tags; choose one of: {}",
+                tags.join(", ")
+            ));
+        }
+        Ok(newest(matched))
     }
 
     fn pick_best_bdist(&mut self) -> Option<warehouse::DistributionUrl> {
+        let host = if self.options.detect_host {
+            Some(self.options.host.clone().unwrap_or_default().or_detected())
+        } else {
+            self.options.host.clone()
+        };
+        // a wheel for this host is preferred, the more specific the better, as an installer would
+        let host_preference = |tag: &distribution::CompatibilityTag| match &host {
+            Some(host) if host.supports(tag) && !tag.for_any_platform() => 2,
+            Some(host) if host.supports(tag) => 1,
+            _ => 0,
+        };
         self.version()
             .ok()?
             .urls
             .iter()
-            .filter(|u| u.packagetype == "bdist_wheel")
-            .filter(|u| distribution::WheelName::from_filename(&u.filename).is_ok())
+            .filter(|u| is_parseable_wheel(u))
             .max_by(|a, b| {
                 let a_wheel = distribution::WheelName::from_filename(&a.filename)
                     .unwrap()
@@ -179,7 +743,10 @@ impl Project {
                 let b_wheel = distribution::WheelName::from_filename(&b.filename)
                     .unwrap()
                     .compatibility_tag;
-                if a_wheel.is_universal() {
+                let by_host = host_preference(&a_wheel).cmp(&host_preference(&b_wheel));
+                if by_host.is_ne() {
+                    by_host
+                } else if a_wheel.is_universal() {
                     Ordering::Greater
                 } else if b_wheel.is_universal() {
                     Ordering::Less
@@ -207,6 +774,20 @@ impl Project {
         self.package_selector.clone()
     }
 
+    /// The project's name as the index gives it, if it is not the project that was asked for
+    ///
+    /// The index resolves a name to its project however it is capitalized or separated, which is
+    /// still the same name, and may follow a rename, which is not. Only metadata that has already
+    /// been fetched is consulted.
+    pub fn resolved_name(&self) -> Option<&str> {
+        let name = match (&self.version, &self.package) {
+            (Some(version), _) => &version.name,
+            (None, Some(package)) => &package.name,
+            (None, None) => return None,
+        };
+        (!distribution::same_package_name(name, &self.package_selector)).then_some(name.as_str())
+    }
+
     pub fn version_selector(&self) -> Option<String> {
         self.version_selector.clone()
     }
@@ -214,4 +795,23 @@ impl Project {
     pub fn distribution_selector(&self) -> Option<String> {
         self.distribution_selector.clone()
     }
+
+    pub fn options(&self) -> &ProjectOptions {
+        &self.options
+    }
+
+    /// Where the project's metadata comes from: the url or file it was read from, or else the
+    /// index it is fetched from
+    pub fn source(&self) -> String {
+        match &self.origin {
+            Some(origin) => origin.clone(),
+            None if self.options.api == Api::Simple => {
+                format!(
+                    "{} (simple API)",
+                    warehouse::redact_index(&self.options.index)
+                )
+            }
+            None => warehouse::redact_index(&self.options.index),
+        }
+    }
 }
@@ -0,0 +1,74 @@
+use crate::picker::Project;
+use crate::ui::json::{report_artifact, ArtifactReport, UrlReport};
+use crate::ui::list_urls;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Every detail kayak knows about a single project version, fully resolved
+///
+/// Packages and executables can only be discovered by inspecting a wheel, so they are None when
+/// the version has no wheel that could be inspected.
+#[derive(Debug, Serialize)]
+pub struct ProjectReport {
+    pub name: String,
+    pub version: String,
+    pub yanked: bool,
+    pub yanked_reason: Option<String>,
+    pub summary: Option<String>,
+    pub license: Option<String>,
+    pub urls: Vec<UrlReport>,
+    pub keywords: Vec<String>,
+    pub classifiers: Vec<String>,
+    pub requires_python: Option<String>,
+    pub dependencies: Vec<String>,
+    pub artifacts: Vec<ArtifactReport>,
+    pub packages: Option<Vec<String>>,
+    pub executables: Option<Vec<String>>,
+}
+
+/// The details level at which every field of an artifact is reported
+const ALL_ARTIFACT_DETAILS: u8 = u8::MAX;
+
+impl ProjectReport {
+    /// Fetch and inspect everything needed to describe the version selected by project
+    pub fn resolve(project: &mut Project) -> Result<Self> {
+        let version = project.version()?;
+        let mut report = ProjectReport {
+            name: version.name.clone(),
+            version: version.version.clone(),
+            yanked: version.yanked,
+            yanked_reason: version.yanked_reason.clone(),
+            summary: version.summary.clone(),
+            license: version.license.clone(),
+            urls: list_urls(version)
+                .into_iter()
+                .map(|(label, url)| UrlReport { label, url })
+                .collect(),
+            keywords: version.keywords(),
+            classifiers: version.classifiers.clone(),
+            requires_python: version.requires_python.clone(),
+            dependencies: version.requires_dist.clone(),
+            artifacts: version
+                .urls
+                .iter()
+                .map(|u| report_artifact(u, ALL_ARTIFACT_DETAILS))
+                .collect(),
+            packages: None,
+            executables: None,
+        };
+
+        // distribution only fails when nothing may be, or can be, selected
+        if project
+            .distribution()
+            .is_ok_and(|d| d.packagetype == "bdist_wheel")
+        {
+            let package = project.import_package()?;
+            let mut packages = package.provides_packages().into_iter().collect::<Vec<_>>();
+            packages.sort_unstable();
+            report.packages = Some(packages);
+            report.executables = Some(package.executables());
+        }
+
+        Ok(report)
+    }
+}
@@ -1,24 +1,280 @@
-use crate::warehouse::DistributionUrl;
+use crate::distribution::{is_prerelease, split_extras, CompatibilityTag};
+use crate::package_inspect::{self, InstallScheme};
+use crate::warehouse::{DistributionUrl, Downloads, Package, PackageVersion};
+use crate::{DisplayFields, SortBy};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, Utc};
+use pep440::Version;
+use regex::Regex;
+use std::io::{self, IsTerminal, Write};
+use std::iter;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::LazyLock;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 pub mod interactive;
+pub mod json;
 pub mod pretty;
 pub mod text;
+pub mod yaml;
 
-/// Determine an appropriate icon for the url type
+/// The kinds of project url that are given their own icon, in the order they are listed
 /// pypi.org implements icons for some url types
 /// https://github.com/pypi/warehouse/blob/main/warehouse/templates/packaging/detail.html#L20
-fn iconify_url(url: (&String, &String)) -> String {
-    match url.0.to_ascii_lowercase().as_str() {
-        "package index" => "📦".to_string(),
-        "download" => "⇩".to_string(),
-        "home" | "homepage" | "home page" => "🏠".to_string(),
-        "changelog" | "change log" | "changes" | "release notes" | "news" | "what's new"
-        | "history" => "📜".to_string(),
-        "docs" | "documentation" => "📄".to_string(),
-        "bug" | "issue" | "tracker" | "report" => "🐞".to_string(),
-        "funding" | "donate" | "donation" | "sponsor" => "💸".to_string(),
-        "mastodon" => "🐘".to_string(),
-        _ => "🔗".to_string(),
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum UrlCategory {
+    PackageIndex,
+    Home,
+    Docs,
+    Source,
+    Changelog,
+    Issues,
+    Download,
+    Funding,
+    Mastodon,
+}
+
+/// Canonicalize a free-form project url label into one of the known url categories
+///
+/// Labels are compared ignoring case, spacing and punctuation, so "Bug Tracker", "bug-tracker"
+/// and "BugTracker" are all the same label. A label that is not a known one as a whole is
+/// categorized by the first of its words that names a category, so "Docker" is not docs and
+/// "Report a problem" is not a repository.
+fn categorize_url_label(label: &str) -> Option<UrlCategory> {
+    let words = label_words(label);
+    match words.concat().as_str() {
+        "packageindex" => Some(UrlCategory::PackageIndex),
+        "download" | "downloads" => Some(UrlCategory::Download),
+        "home" | "homepage" | "website" | "web" => Some(UrlCategory::Home),
+        "changelog" | "changes" | "releasenotes" | "news" | "whatsnew" | "history" => {
+            Some(UrlCategory::Changelog)
+        }
+        "docs" | "doc" | "documentation" => Some(UrlCategory::Docs),
+        "source" | "sourcecode" | "code" | "repository" | "repo" | "github" | "gitlab"
+        | "bitbucket" | "codeberg" => Some(UrlCategory::Source),
+        "bug" | "bugs" | "issue" | "issues" | "tracker" | "report" | "bugtracker"
+        | "issuetracker" => Some(UrlCategory::Issues),
+        "funding" | "fund" | "donate" | "donation" | "donations" | "sponsor" | "sponsors" => {
+            Some(UrlCategory::Funding)
+        }
+        "mastodon" => Some(UrlCategory::Mastodon),
+        _ => words.iter().find_map(|word| match word.as_str() {
+            "changelog" => Some(UrlCategory::Changelog),
+            "doc" | "docs" | "documentation" => Some(UrlCategory::Docs),
+            "issue" | "issues" | "bug" | "bugs" | "tracker" => Some(UrlCategory::Issues),
+            "source" | "repo" | "repository" => Some(UrlCategory::Source),
+            "fund" | "funding" | "sponsor" | "sponsors" | "donate" | "donation" | "donations" => {
+                Some(UrlCategory::Funding)
+            }
+            _ => None,
+        }),
+    }
+}
+
+/// The lowercased words of a url label, split on anything but letters and digits, and where a
+/// lowercase letter is followed by an uppercase one
+fn label_words(label: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous = ' ';
+    for c in label.chars() {
+        let boundary = !c.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase());
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if c.is_alphanumeric() {
+            word.extend(c.to_lowercase());
+        }
+        previous = c;
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Determine an appropriate icon for the url type, or a short ASCII label if not emoji
+///
+/// This is the only place url labels are mapped to icons, every output format should use it
+fn iconify_url(label: &str, emoji: bool) -> &'static str {
+    if !emoji {
+        return match categorize_url_label(label) {
+            Some(UrlCategory::PackageIndex) => "[index]",
+            Some(UrlCategory::Download) => "[download]",
+            Some(UrlCategory::Home) => "[home]",
+            Some(UrlCategory::Changelog) => "[changes]",
+            Some(UrlCategory::Docs) => "[docs]",
+            Some(UrlCategory::Source) => "[source]",
+            Some(UrlCategory::Issues) => "[bug]",
+            Some(UrlCategory::Funding) => "[funding]",
+            Some(UrlCategory::Mastodon) => "[mastodon]",
+            None => "[link]",
+        };
+    }
+    match categorize_url_label(label) {
+        Some(UrlCategory::PackageIndex) => "📦",
+        Some(UrlCategory::Download) => "⇩",
+        Some(UrlCategory::Home) => "🏠",
+        Some(UrlCategory::Changelog) => "📜",
+        Some(UrlCategory::Docs) => "📄",
+        Some(UrlCategory::Source) => "🐙",
+        Some(UrlCategory::Issues) => "🐞",
+        Some(UrlCategory::Funding) => "💸",
+        Some(UrlCategory::Mastodon) => "🐘",
+        None => "🔗",
+    }
+}
+
+/// Reduce a url to the parts that decide where it points
+///
+/// Only the host is compared ignoring case, as paths and queries may be case-sensitive.
+fn url_target(url: &str) -> String {
+    let url = url.trim();
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url)
+        .trim_end_matches('/');
+    let (host, path) = url.split_at(url.find(['/', '?', '#']).unwrap_or(url.len()));
+    format!("{}{path}", host.to_lowercase())
+}
+
+/// The order urls are listed in: by category, then uncategorized urls, each alphabetically by label
+fn url_order(label: &str) -> (bool, Option<UrlCategory>, String) {
+    let category = categorize_url_label(label);
+    // None sorts before any category, so the uncategorized are put last first
+    (category.is_none(), category, label.to_lowercase())
+}
+
+/// List the urls of a project version to display, the package index first
+///
+/// A url listed under several labels is only listed once, preferring a label that has its own
+/// category over one that does not. The legacy docs and download urls are considered last, so that
+/// any label the project gave the same url is kept. Project urls are not stored in any order, so
+/// they are always listed in the order of url_order to display the same every time.
+pub(crate) fn list_urls(version: &PackageVersion) -> Vec<(String, String)> {
+    // distutils wrote "UNKNOWN" for any url that was not given
+    let legacy = |label: &str, url: &Option<String>| {
+        url.as_ref()
+            .filter(|u| !u.trim().is_empty() && u.trim() != "UNKNOWN")
+            .map(|u| (label.to_string(), u.to_string()))
+    };
+    let mut project_urls = version
+        .project_urls
+        .iter()
+        .map(|(l, u)| (l.to_string(), u.to_string()))
+        .collect::<Vec<_>>();
+    project_urls.sort_by_cached_key(|(label, url)| (url_order(label), url.clone()));
+    let mut urls: Vec<(String, String)> = Vec::new();
+    for (label, url) in iter::once(("Package Index".to_string(), version.project_url.clone()))
+        .chain(project_urls)
+        .chain(legacy("Documentation", &version.docs_url))
+        .chain(legacy("Download", &version.download_url))
+    {
+        match urls
+            .iter_mut()
+            .find(|(_, u)| url_target(u) == url_target(&url))
+        {
+            Some(listed) => {
+                if categorize_url_label(&listed.0).is_none()
+                    && categorize_url_label(&label).is_some()
+                {
+                    *listed = (label, url);
+                }
+            }
+            None => urls.push((label, url)),
+        }
+    }
+    urls.sort_by_cached_key(|(label, _)| url_order(label));
+    urls
+}
+
+/// The dependencies of a project version, grouped by the extra that requires them
+struct DependencyGroups {
+    /// Dependencies that are always required
+    base: Vec<String>,
+    /// Dependencies of each displayed extra, in the order the extras are first required
+    extras: Vec<(String, Vec<String>)>,
+    /// Extras that are available but not displayed
+    hidden: Vec<String>,
+}
+
+/// Group the dependencies of version by extra, displaying only the selected extras if any are
+fn group_dependencies(version: &PackageVersion, selected: Option<&[String]>) -> DependencyGroups {
+    let mut groups = DependencyGroups {
+        base: Vec::new(),
+        extras: Vec::new(),
+        hidden: Vec::new(),
+    };
+    for requires_dist in &version.requires_dist {
+        let (extras, requirement) = split_extras(requires_dist);
+        if extras.is_empty() {
+            groups.base.push(requirement);
+            continue;
+        }
+        for extra in extras {
+            if selected.is_some_and(|s| !s.contains(&extra)) {
+                if !groups.hidden.contains(&extra) {
+                    groups.hidden.push(extra);
+                }
+            } else if let Some((_, requirements)) =
+                groups.extras.iter_mut().find(|(e, _)| *e == extra)
+            {
+                requirements.push(requirement.clone());
+            } else {
+                groups.extras.push((extra, vec![requirement.clone()]));
+            }
+        }
+    }
+    groups
+}
+
+/// Order python tags by implementation, then by version, so that cp39 comes before cp310
+fn python_tag_order(tag: &str) -> (&str, u32, &str) {
+    let implementation = tag.trim_end_matches(|c: char| c.is_ascii_digit());
+    let version = &tag[implementation.len()..];
+    (implementation, version.parse().unwrap_or(0), tag)
+}
+
+/// Name a kind of wheel, and the pythons it is for unless that is just any Python 3
+fn describe_wheels(kind: &str, tags: &[CompatibilityTag]) -> Option<String> {
+    if tags.is_empty() {
+        return None;
+    }
+    let plural = if tags.len() > 1 { "s" } else { "" };
+    let mut pythons = tags
+        .iter()
+        .flat_map(|t| t.python_tags())
+        .collect::<Vec<_>>();
+    pythons.sort_unstable_by_key(|p| python_tag_order(p));
+    pythons.dedup();
+    if pythons.is_empty() || pythons == ["py3"] {
+        Some(format!("{kind}{plural}"))
+    } else {
+        Some(format!("{kind}{plural} for {}", pythons.join(", ")))
+    }
+}
+
+/// Archive formats an sdist has been uploaded in, besides the standard .tar.gz
+const SDIST_FORMATS: [&str; 5] = [".zip", ".tar.bz2", ".tar.xz", ".tgz", ".tar"];
+
+/// How an sdist is listed: as just sdist when it is the standard .tar.gz, otherwise with its format
+///
+/// A release can have sdists in several formats, which would otherwise be listed the same.
+pub fn sdist_label(filename: &str) -> String {
+    let filename = filename.to_ascii_lowercase();
+    match SDIST_FORMATS.iter().find(|f| filename.ends_with(*f)) {
+        Some(format) => format!("sdist ({})", format.trim_start_matches('.')),
+        None => "sdist".to_string(),
+    }
+}
+
+/// The published sha256 digest of artifact, as listed with the most artifact details
+pub fn format_sha256(artifact: &DistributionUrl) -> String {
+    match &artifact.digests.sha256 {
+        Some(sha256) => format!("sha256={sha256}"),
+        None => "no sha256".to_string(),
     }
 }
 
@@ -28,35 +284,387 @@ where
 {
     let mut sdist: u16 = 0;
     let mut universal: u16 = 0;
-    let mut pure: u16 = 0;
-    let mut plat: u16 = 0;
+    let mut pure = Vec::new();
+    let mut plat = Vec::new();
+    let mut legacy: u16 = 0;
+    let mut unrecognized: u16 = 0;
     for artifact in artifacts {
         if artifact.packagetype == "sdist" {
             sdist += 1;
         } else if artifact.packagetype == "bdist_wheel" {
-            let compat = artifact.filename().unwrap().compatibility_tag;
-            if compat.is_universal() {
-                universal += 1;
-            } else if compat.is_pure() {
-                pure += 1;
-            } else {
-                plat += 1;
+            // an index may not validate filenames, so a wheel's tag cannot always be known
+            match artifact.filename().map(|f| f.compatibility_tag) {
+                Ok(compat) if compat.is_universal() => universal += 1,
+                Ok(compat) if compat.is_pure() => pure.push(compat),
+                Ok(compat) => plat.push(compat),
+                Err(_) => unrecognized += 1,
             }
+        } else {
+            // eggs, wininst installers, and other types no longer accepted by PyPI
+            legacy += 1;
         }
     }
 
     [
-        (sdist > 0).then_some("sdist"),
-        (universal > 0).then_some("universal wheel"),
-        (pure > 1)
-            .then_some("pure wheels")
-            .or((pure > 0).then_some("pure wheel")),
-        (plat > 1)
-            .then_some("platform-specific wheels")
-            .or((plat > 0).then_some("platform-specific wheel")),
+        (sdist > 1)
+            .then(|| "sdists".to_string())
+            .or((sdist > 0).then(|| "sdist".to_string())),
+        (universal > 0).then(|| "universal wheel".to_string()),
+        describe_wheels("pure wheel", &pure),
+        describe_wheels("platform-specific wheel", &plat),
+        (legacy > 1)
+            .then(|| "legacy artifacts".to_string())
+            .or((legacy > 0).then(|| "legacy artifact".to_string())),
+        (unrecognized > 1)
+            .then(|| "unrecognized wheels".to_string())
+            .or((unrecognized > 0).then(|| "unrecognized wheel".to_string())),
     ]
     .into_iter()
     .flatten()
     .collect::<Vec<_>>()
     .join(" and ")
 }
+
+/// Find the time the first of these artifacts was uploaded
+fn earliest_upload_time<'a, A>(artifacts: A) -> Option<DateTime<Utc>>
+where
+    A: Iterator<Item = &'a DistributionUrl>,
+{
+    artifacts.filter_map(DistributionUrl::uploaded).min()
+}
+
+/// How times are displayed when no --date-format is given; the zone is always included
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
+
+/// Whether format is a strftime format chrono can display
+pub fn is_date_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| item == Item::Error)
+}
+
+/// A count with its thousands separated by commas, such as "1,234,567"
+fn format_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// The recent downloads of a project on one line, such as "12 last day, 80 last week, ..."
+pub fn format_downloads(downloads: &Downloads) -> String {
+    format!(
+        "{} last day, {} last week, {} last month",
+        format_count(downloads.last_day),
+        format_count(downloads.last_week),
+        format_count(downloads.last_month)
+    )
+}
+
+/// How long before now time was, such as "3 months ago"
+///
+/// Months are 30 days and years 365 days, which is close enough at the precision displayed. A time
+/// after now can only come from a clock that is off, so it is treated as having just happened.
+pub fn format_age(time: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    const UNITS: [(&str, i64); 6] = [
+        ("year", 365 * 24 * 60 * 60),
+        ("month", 30 * 24 * 60 * 60),
+        ("week", 7 * 24 * 60 * 60),
+        ("day", 24 * 60 * 60),
+        ("hour", 60 * 60),
+        ("minute", 60),
+    ];
+    let seconds = (*now - *time).num_seconds();
+    UNITS
+        .iter()
+        .find(|(_, size)| seconds >= *size)
+        .map(|(unit, size)| match seconds / size {
+            1 => format!("1 {unit} ago"),
+            count => format!("{count} {unit}s ago"),
+        })
+        .unwrap_or_else(|| "just now".to_string())
+}
+
+/// Display time with the --date-format, converted to the local zone if --local-time was given
+///
+/// With --relative-time only the age of time is displayed instead.
+fn format_time(time: &DateTime<Utc>, display_fields: &DisplayFields) -> String {
+    if display_fields.relative_time {
+        return format_age(time, &Utc::now());
+    }
+    let format = display_fields
+        .date_format
+        .as_deref()
+        .unwrap_or(DEFAULT_DATE_FORMAT);
+    if display_fields.local_time {
+        time.with_timezone(&Local).format(format).to_string()
+    } else {
+        time.format(format).to_string()
+    }
+}
+
+/// Display the time artifact was uploaded, or as the index gave it if it cannot be understood
+fn format_upload_time(artifact: &DistributionUrl, display_fields: &DisplayFields) -> String {
+    artifact
+        .uploaded()
+        .map(|time| format_time(&time, display_fields))
+        .unwrap_or_else(|| artifact.upload_time.clone())
+}
+
+/// Whether every file of a release is yanked, which is how a whole version is yanked
+fn is_yanked_release(urls: &[DistributionUrl]) -> bool {
+    !urls.is_empty() && urls.iter().all(|u| u.yanked)
+}
+
+/// A number of bytes in the largest binary unit that keeps it at least 1, such as 1.5 KiB
+fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Up to largest of the files package installs that have a size, biggest first
+fn largest_files(
+    package: &package_inspect::Package,
+    largest: usize,
+) -> Vec<(usize, InstallScheme, String)> {
+    let mut sized = package
+        .installed_files()
+        .into_iter()
+        .flat_map(|(scheme, files)| {
+            files
+                .into_iter()
+                .filter_map(move |(path, size)| Some((size?, scheme, path)))
+        })
+        .collect::<Vec<_>>();
+    // biggest first, then by location and path so that ties are always listed the same way
+    sized.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| (a.1, &a.2).cmp(&(b.1, &b.2))));
+    sized.truncate(largest);
+    sized
+}
+
+/// List all valid versions of package along with the time each was first uploaded
+///
+/// By default the newest versions are listed first, either by comparison order or by upload time
+fn list_versions(
+    package: &Package,
+    display_fields: &DisplayFields,
+) -> Vec<(Version, Option<DateTime<Utc>>, bool)> {
+    let mut versions = package
+        .ordered_releases()
+        .into_iter()
+        .filter(|(v, _)| !(display_fields.stable_only && is_prerelease(v)))
+        .map(|(v, urls)| {
+            (
+                v,
+                earliest_upload_time(urls.iter()),
+                is_yanked_release(urls),
+            )
+        })
+        .collect::<Vec<_>>();
+    if display_fields.sort_by == SortBy::Date {
+        // stable sort keeps versions without any uploads in comparison order
+        versions.sort_by_key(|(_, time, _)| *time);
+    }
+    versions.reverse();
+    if let Some(limit) = display_fields.limit {
+        versions.truncate(limit);
+    }
+    if display_fields.reverse {
+        versions.reverse();
+    }
+    versions
+}
+
+/// Whether line is a reStructuredText section adornment, such as ===== or -----
+fn is_rst_adornment(line: &str) -> bool {
+    let line = line.trim_end();
+    let mut chars = line.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_punctuation() => line.len() >= 3 && chars.all(|a| a == c),
+        _ => false,
+    }
+}
+
+static RST_LITERAL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"``([^`]+)``").unwrap());
+static RST_ROLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r":[a-zA-Z:]+:`([^`<]+?)(?:\s*<[^>]*>)?`").unwrap());
+static RST_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"`([^`<]+?)\s*<([^>]+)>`__?").unwrap());
+static RST_REFERENCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`([^`]+)`__?").unwrap());
+
+/// The directives whose body is a block of code, each followed by the language of the code
+const RST_CODE_DIRECTIVES: [&str; 3] = [".. code-block::", ".. code::", ".. sourcecode::"];
+
+/// Translate the most common reStructuredText markup to markdown
+///
+/// Section titles become headings, in the order their adornment styles are first seen, and code
+/// directives become fenced code blocks. Other directives, comments, and link targets are dropped. Inline literals, emphasis, roles, and
+/// hyperlink references are translated; anything else is left as-is.
+pub fn rst_to_markdown(rst: &str) -> String {
+    let lines = rst.lines().collect::<Vec<_>>();
+    // adornment character and whether it has an overline, in the order first seen
+    let mut styles: Vec<(char, bool)> = Vec::new();
+    let mut markdown = Vec::new();
+    let mut in_directive = false;
+    let mut l = 0;
+    while l < lines.len() {
+        let line = lines[l];
+        let next = lines.get(l + 1).copied().unwrap_or_default();
+        if in_directive {
+            // the body of a directive is indented, and ends at the first unindented line
+            if line.trim().is_empty() || line.starts_with(char::is_whitespace) {
+                l += 1;
+                continue;
+            }
+            in_directive = false;
+        }
+        if let Some(language) = RST_CODE_DIRECTIVES
+            .iter()
+            .find_map(|d| line.strip_prefix(d))
+        {
+            l += 1;
+            // options come straight after the directive, before the blank line and its body
+            while lines
+                .get(l)
+                .is_some_and(|o| o.trim_start().starts_with(':'))
+            {
+                l += 1;
+            }
+            let start = l;
+            while lines
+                .get(l)
+                .is_some_and(|b| b.trim().is_empty() || b.starts_with(char::is_whitespace))
+            {
+                l += 1;
+            }
+            // blank lines after the body separate it from what follows, as they do any paragraph
+            while l > start && lines[l - 1].trim().is_empty() {
+                l -= 1;
+            }
+            let body = &lines[start..l];
+            let body = &body[body
+                .iter()
+                .position(|b| !b.trim().is_empty())
+                .unwrap_or(body.len())..];
+            let indent = body
+                .iter()
+                .filter(|b| !b.trim().is_empty())
+                .map(|b| b.len() - b.trim_start().len())
+                .min()
+                .unwrap_or(0);
+            markdown.push(format!("```{}", language.trim()));
+            markdown.extend(
+                body.iter()
+                    .map(|b| b.get(indent..).unwrap_or("").to_string()),
+            );
+            markdown.push("```".to_string());
+            continue;
+        }
+        if line.starts_with(".. ") || line.trim_end() == ".." {
+            in_directive = true;
+            l += 1;
+            continue;
+        }
+
+        let overline = is_rst_adornment(line);
+        let (title, underline) = if overline {
+            (next, lines.get(l + 2).copied().unwrap_or_default())
+        } else {
+            (line, next)
+        };
+        if !title.trim().is_empty()
+            && !is_rst_adornment(title)
+            && is_rst_adornment(underline)
+            && underline.trim_end().len() >= title.trim().len()
+        {
+            let style = (underline.chars().next().unwrap(), overline);
+            let level = match styles.iter().position(|s| *s == style) {
+                Some(level) => level + 1,
+                None => {
+                    styles.push(style);
+                    styles.len()
+                }
+            };
+            markdown.push(format!("{} {}", "#".repeat(level.min(6)), title.trim()));
+            l += if overline { 3 } else { 2 };
+            continue;
+        }
+
+        let line = RST_LITERAL.replace_all(line, "`$1`");
+        let line = RST_ROLE.replace_all(&line, "`$1`");
+        let line = RST_LINK.replace_all(&line, "[$1]($2)");
+        let line = RST_REFERENCE.replace_all(&line, "$1");
+        // a paragraph ending in :: introduces a literal block
+        let line = match line.strip_suffix("::") {
+            Some(rest) if rest.trim().is_empty() => "".to_string(),
+            Some(rest) => format!("{rest}:"),
+            None => line.to_string(),
+        };
+        markdown.push(line);
+        l += 1;
+    }
+    markdown.join("\n")
+}
+
+/// An animation on stderr while a slow fetch is in flight, erased again once it is dropped
+///
+/// Nothing is drawn when stderr is not a terminal, or if the fetch finishes too soon to notice.
+pub struct Spinner {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Spinner {
+    const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+    pub fn start(message: String) -> Self {
+        if !io::stderr().is_terminal() {
+            return Spinner {
+                stop: None,
+                thread: None,
+            };
+        }
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || {
+            if stopped.recv_timeout(Duration::from_millis(200)) != Err(RecvTimeoutError::Timeout) {
+                return;
+            }
+            for frame in Self::FRAMES.iter().cycle() {
+                eprint!("\r{frame} {message}");
+                let _ = io::stderr().flush();
+                if stopped.recv_timeout(Duration::from_millis(80)) != Err(RecvTimeoutError::Timeout)
+                {
+                    break;
+                }
+            }
+            // erase the whole line
+            eprint!("\r\x1b[2K");
+        });
+        Spinner {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        // disconnecting the channel wakes the thread immediately
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
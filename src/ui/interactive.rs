@@ -1,27 +1,137 @@
-use crate::ui::pretty::render;
-use crate::{DisplayFields, Project};
-use anyhow::Result;
+use crate::distribution;
+use crate::picker::ProjectOptions;
+use crate::ui::json;
+use crate::ui::pretty::{render, render_buffer};
+use crate::ui::{is_yanked_release, list_urls};
+use crate::warehouse;
+use crate::{Api, DisplayFields, Field, Project, SortBy};
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
 use crossterm::event::{self, KeyCode, KeyEventKind, KeyModifiers};
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use crossterm::ExecutableCommand;
+use pep440::Version;
 use ratatui::layout::*;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
-use std::io::stdout;
+use std::fs::OpenOptions;
+use std::io::{self, stdout, Write};
 use std::iter;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
 
-fn encode_cli(project: &mut Project, display_fields: &DisplayFields) -> String {
-    let mut cli = String::from("kayak");
+/// Open url with the platform's default handler, usually a web browser
+///
+/// Only web urls are opened, as the url comes from the index. No shell is involved, so nothing in
+/// the url is ever run as a command.
+fn open_url(url: &str) -> Result<()> {
+    let parsed = url::Url::parse(url)?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(anyhow!("only http and https urls are opened"));
+    }
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    // the handler must not draw over the interactive display
+    command
+        .arg(parsed.as_str())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// A project that was typed in as its name, and optionally a version and distribution
+pub type ProjectInput = (String, Option<String>, Option<String>);
+
+/// Read the name, version, and distribution typed into the prompt, checking each the way the
+/// command line checks them so that a mistake is reported before anything is fetched
+pub fn parse_project_input(input: &str) -> Result<ProjectInput, String> {
+    let mut tokens = input.split_whitespace();
+    let Some(name) = tokens.next() else {
+        return Err("please enter the name of a package".to_string());
+    };
+    if let Err(e) = distribution::normalize_package_name(name) {
+        return Err(match e.downcast_ref::<distribution::Error>() {
+            Some(distribution::Error::InvalidPackageName(reason)) => {
+                format!("{name:?} is not a valid project name: {reason}")
+            }
+            _ => format!("{name:?} is not a valid project name"),
+        });
+    }
+    let version = tokens.next();
+    if let Some(version) = version {
+        if Version::parse(version).is_none() {
+            return Err(format!("{version:?} is not a valid version"));
+        }
+    }
+    // any part of a wheel's filename may select it, so only fetching says whether one does
+    let dist = tokens.next();
+    if let Some(extra) = tokens.next() {
+        return Err(format!(
+            "unexpected {extra:?}, enter only a name, version, and distribution"
+        ));
+    }
+    Ok((
+        name.to_string(),
+        version.map(str::to_string),
+        dist.map(str::to_string),
+    ))
+}
+
+/// The command line that displays the same as project does with display_fields
+pub fn encode_cli(project: &mut Project, display_fields: &DisplayFields) -> String {
+    let mut cli = String::from("kayak ");
+    // the name the index gave is the same project however the name was first typed
+    let name = project
+        .resolved_name()
+        .map(str::to_string)
+        .unwrap_or_else(|| project.package_selector());
+    cli += &name;
+    if project.options().metadata_only {
+        cli += " --metadata-only";
+    }
+    if project.options().index != warehouse::PYPI_URI {
+        cli += " --index ";
+        cli += &warehouse::redact_index(&project.options().index);
+    }
+    if project.options().api == Api::Simple {
+        cli += " --api simple";
+    }
     if display_fields.versions {
-        cli += " versions";
+        cli += " --versions";
+        // the list of versions only ever displays the name and times, so -qq is only the name
         if !display_fields.name {
             cli += " -qq";
         }
+        if display_fields.time {
+            cli += " --time";
+        }
+        if display_fields.sort_by == SortBy::Date {
+            cli += " --sort-by date";
+        }
+        if let Some(limit) = display_fields.limit {
+            cli += &format!(" --limit {limit}");
+        }
+        if display_fields.reverse {
+            cli += " --reverse";
+        }
+        if display_fields.stable_only {
+            cli += " --stable-only";
+        }
     } else {
-        cli += " ";
-        cli += &project.package_selector();
         if let Some(version) = project.version_selector() {
             cli += " ";
             cli += &version;
@@ -40,12 +150,30 @@ fn encode_cli(project: &mut Project, display_fields: &DisplayFields) -> String {
         if !display_fields.time && project.distribution_selector().is_some() {
             cli += " -q";
         }
+        if display_fields.time && project.distribution_selector().is_none() {
+            cli += " --time";
+        }
         if display_fields.summary {
             cli += " --summary";
         }
         if display_fields.license {
             cli += " --license";
         }
+        if display_fields.downloads {
+            cli += " --downloads";
+            let options = project.options();
+            let default_source =
+                (options.index == warehouse::PYPI_URI).then_some(warehouse::DOWNLOADS_URI);
+            if let Some(source) = options.downloads_source.as_deref() {
+                if Some(source) != default_source {
+                    cli += " --downloads-source ";
+                    cli += source;
+                }
+            }
+        }
+        if display_fields.people {
+            cli += " --people";
+        }
         if display_fields.urls {
             cli += " --urls";
         }
@@ -63,8 +191,18 @@ fn encode_cli(project: &mut Project, display_fields: &DisplayFields) -> String {
                 cli += &"a".repeat(display_fields.artifacts.into());
             }
         }
-        if display_fields.dependencies {
-            cli += " --dependencies";
+        if display_fields.describe_tags {
+            cli += " --describe-tags";
+        }
+        // --extras displays the dependencies too, so the selected extras are only exported while
+        // the dependencies are displayed
+        match &display_fields.extras {
+            Some(extras) if display_fields.dependencies => {
+                cli += " --extras ";
+                cli += &extras.join(",");
+            }
+            _ if display_fields.dependencies => cli += " --dependencies",
+            _ => (),
         }
         match display_fields.readme {
             0 => (),
@@ -74,16 +212,166 @@ fn encode_cli(project: &mut Project, display_fields: &DisplayFields) -> String {
                 cli += &"r".repeat(display_fields.readme.into());
             }
         }
-        if display_fields.packages {
-            cli += " --packages";
+        match display_fields.packages {
+            0 => (),
+            1 => cli += " --packages",
+            _ => {
+                cli += " -";
+                cli += &"p".repeat(display_fields.packages.into());
+            }
         }
         if display_fields.executables {
             cli += " --executables";
         }
+        if display_fields.entry_points {
+            cli += " --entry-points";
+        }
+        if let Some(order) = &display_fields.order {
+            // --fields decides what is displayed, so only the fields that are on are listed
+            let enabled = order
+                .iter()
+                .filter(|field| match field {
+                    Field::Name => display_fields.name,
+                    Field::Time => display_fields.time,
+                    Field::License => display_fields.license,
+                    Field::People => display_fields.people,
+                    Field::Summary => display_fields.summary,
+                    Field::Downloads => display_fields.downloads,
+                    Field::Urls => display_fields.urls,
+                    Field::Keywords => display_fields.keywords,
+                    Field::Classifiers => display_fields.classifiers,
+                    Field::Artifacts => display_fields.artifacts > 0,
+                    Field::Dependencies => display_fields.dependencies,
+                    Field::Packages => display_fields.packages > 0,
+                    Field::Executables => display_fields.executables,
+                    Field::EntryPoints => display_fields.entry_points,
+                    Field::Readme => display_fields.readme > 0,
+                })
+                .filter_map(|field| field.to_possible_value())
+                .map(|value| value.get_name().to_string())
+                .collect::<Vec<_>>();
+            if !enabled.is_empty() {
+                cli += " --fields ";
+                cli += &enabled.join(",");
+            }
+        }
+    }
+    if display_fields.relative_time {
+        cli += " --relative-time";
+    }
+    if !display_fields.emoji {
+        cli += " --no-emoji";
+    }
+    if let Some(width) = display_fields.width {
+        cli += &format!(" --width {width}");
+    }
+    if display_fields.local_time {
+        cli += " --local-time";
+    }
+    if let Some(format) = &display_fields.date_format {
+        // single quoted so the shell passes the format through untouched
+        cli += &format!(" --date-format '{}'", format.replace('\'', r"'\''"));
     }
     cli
 }
 
+/// Switch between listing all versions and displaying details, as the v and V keys do
+///
+/// The details are put back exactly as they were when the list was shown, however fields were
+/// toggled while listing versions, while the options for listing versions are kept for next time.
+pub fn switch_versions_view(
+    display_fields: &mut DisplayFields,
+    details: &mut Option<DisplayFields>,
+    versions: bool,
+) {
+    if versions == display_fields.versions {
+        return;
+    }
+    if versions {
+        *details = Some(display_fields.clone());
+        display_fields.versions = true;
+    } else {
+        let listing = display_fields.clone();
+        if let Some(details) = details.take() {
+            *display_fields = details;
+        }
+        display_fields.versions = false;
+        display_fields.sort_by = listing.sort_by;
+        display_fields.limit = listing.limit;
+        display_fields.reverse = listing.reverse;
+        display_fields.stable_only = listing.stable_only;
+    }
+}
+
+/// Apply the interactive command bound to key to display_fields
+///
+/// Returns false if key is not bound to a display field. Keys that would step past the least or
+/// most details are still bound, but do nothing.
+pub fn toggle_field(display_fields: &mut DisplayFields, key: char) -> bool {
+    match key {
+        'n' => display_fields.name = true,
+        'N' => display_fields.name = false,
+        'v' => display_fields.versions = true,
+        'V' => display_fields.versions = false,
+        't' => display_fields.time = true,
+        'T' => display_fields.time = false,
+        's' => display_fields.summary = true,
+        'S' => display_fields.summary = false,
+        'm' => display_fields.downloads = true,
+        'M' => display_fields.downloads = false,
+        'l' => display_fields.license = true,
+        'L' => display_fields.license = false,
+        'w' => display_fields.people = true,
+        'W' => display_fields.people = false,
+        'u' => display_fields.urls = true,
+        'U' => display_fields.urls = false,
+        'k' => display_fields.keywords = true,
+        'K' => display_fields.keywords = false,
+        'c' => display_fields.classifiers = true,
+        'C' => display_fields.classifiers = false,
+        'a' => display_fields.artifacts = (display_fields.artifacts + 1).min(5),
+        'A' => display_fields.artifacts = display_fields.artifacts.saturating_sub(1),
+        'x' => display_fields.describe_tags = true,
+        'X' => display_fields.describe_tags = false,
+        'd' => display_fields.dependencies = true,
+        'D' => display_fields.dependencies = false,
+        'r' => display_fields.readme = (display_fields.readme + 1).min(2),
+        'R' => display_fields.readme = display_fields.readme.saturating_sub(1),
+        'p' => display_fields.packages = (display_fields.packages + 1).min(2),
+        'P' => display_fields.packages = display_fields.packages.saturating_sub(1),
+        'e' => display_fields.executables = true,
+        'E' => display_fields.executables = false,
+        'g' => display_fields.entry_points = true,
+        'G' => display_fields.entry_points = false,
+        _ => return false,
+    }
+    // a field turned on is displayed even if --fields left it out, after the fields listed
+    let shown = match key {
+        'n' => Some(Field::Name),
+        't' => Some(Field::Time),
+        's' => Some(Field::Summary),
+        'm' => Some(Field::Downloads),
+        'l' => Some(Field::License),
+        'w' => Some(Field::People),
+        'u' => Some(Field::Urls),
+        'k' => Some(Field::Keywords),
+        'c' => Some(Field::Classifiers),
+        'a' => Some(Field::Artifacts),
+        'd' => Some(Field::Dependencies),
+        'r' => Some(Field::Readme),
+        'p' => Some(Field::Packages),
+        'e' => Some(Field::Executables),
+        'g' => Some(Field::EntryPoints),
+        _ => None,
+    };
+    if let (Some(field), Some(order)) = (shown, &mut display_fields.order) {
+        if !order.contains(&field) {
+            order.push(field);
+        }
+    }
+    true
+}
+
 fn render_popup(frame: &mut Frame, area: Rect, message: String, is_error: bool) {
     // info pop-up goes "above the fold", error pop-up goes "below the fold"
     let constraints = if is_error {
@@ -123,7 +411,37 @@ fn render_popup(frame: &mut Frame, area: Rect, message: String, is_error: bool)
     );
 }
 
-fn render_menu(frame: &mut Frame, area: Rect) {
+/// The commands listed in the menu bar, each written as its key, then its inverse key in brackets
+pub const MENU_CONTROLS: [&str; 23] = [
+    "<SPACE>: new project",
+    "j: jump to version",
+    "b: browse versions",
+    "/: search",
+    "i: status line",
+    "n[N]: [no] name",
+    "v[V]: [not] all versions",
+    "t[T]: [no] time",
+    "s[S]: [no] summary",
+    "m[M]: [no] downloads",
+    "l[L]: [no] license",
+    "w[W]: [no] people",
+    "u[U]: [no] urls",
+    "o: open url",
+    "k[K]: [no] keywords",
+    "c[C]: [no] classifiers",
+    "a[A]+: [less] artifacts",
+    "x[X]: [no] tag description",
+    "d[D]: [no] dependencies",
+    "r[R]+: [less] readme",
+    "p[P]+: [less] packages",
+    "e[E]: [no] executables",
+    "g[G]: [no] entry points",
+];
+
+/// Shown in place of the versions control while all versions are listed
+const VERSIONS_ACTIVE_CONTROL: &str = "V: back to details";
+
+fn render_menu(frame: &mut Frame, area: Rect, display_fields: &DisplayFields) {
     // anchor the quit and help commands, so they are always visable
     let [controls_area, help_area, quit_area] = Layout::default()
         .direction(Direction::Horizontal)
@@ -141,36 +459,31 @@ fn render_menu(frame: &mut Frame, area: Rect) {
     let help_content = Paragraph::new(String::from("?: help"))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::TOP | Borders::LEFT));
-    let controls_text = [
-        String::from("<SPACE>: new project"),
-        String::from("n[N]: [no] name"),
-        String::from("v[V]: [not] all versions"),
-        String::from("t[T]: [no] time"),
-        String::from("s[S]: [no] summary"),
-        String::from("l[L]: [no] license"),
-        String::from("u[N]: [no] urls"),
-        String::from("k[K]: [no] keywords"),
-        String::from("c[C]: [no] classifiers"),
-        String::from("a[A]+: [less] artifacts"),
-        String::from("d[D]: [no] dependencies"),
-        String::from("r[R]+: [less] readme"),
-        String::from("p[P]: [no] packages"),
-        String::from("e[E]: [no] executables"),
-    ];
+    // the versions control says how to leave the list of all versions while it is shown
+    let controls_text = MENU_CONTROLS.map(|control| match control {
+        "v[V]: [not] all versions" if display_fields.versions => VERSIONS_ACTIVE_CONTROL,
+        _ => control,
+    });
     let controls_areas = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(
             controls_text
                 .iter()
-                .map(|s| Constraint::Max((s.len() + 3).try_into().unwrap()))
+                .map(|s| Constraint::Max((s.len() + 3).try_into().unwrap_or(u16::MAX)))
                 .chain(iter::once(Constraint::Fill(1)))
                 .collect::<Vec<Constraint>>(),
         )
         .split(controls_area);
 
     for (c, control_text) in controls_text.into_iter().enumerate() {
+        let style = if control_text == VERSIONS_ACTIVE_CONTROL {
+            Style::new().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::new()
+        };
         frame.render_widget(
             Paragraph::new(control_text)
+                .style(style)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::TOP | Borders::LEFT)),
             controls_areas[c],
@@ -185,106 +498,227 @@ fn render_menu(frame: &mut Frame, area: Rect) {
     frame.render_widget(quit_content, quit_area);
 }
 
-fn render_interactive_help(frame: &mut Frame, area: Rect) {
-    let controls_text = [
-        [
-            String::from("name"),
-            String::from("on: n off: N"),
-            String::from("display the name and version of the currenly loaded project"),
-        ],
-        [
-            String::from("versions"),
-            String::from("on: v off: V"),
-            String::from("instead of displaying project details, list all versions available"),
-        ],
-        [
-            String::from("time"),
-            String::from("on: t off: T"),
-            String::from("display the project's release timestamp"),
-        ],
-        [
-            String::from("summary"),
-            String::from("on: s off: S"),
-            String::from("display the project's summary"),
-        ],
-        [
-            String::from("license"),
-            String::from("on: l off: L"),
-            String::from("display the project's license and copyright"),
-        ],
-        [
-            String::from("urls"),
-            String::from("on: u off: U"),
-            String::from("display the project's URLs"),
-        ],
-        [
-            String::from("keywords"),
-            String::from("on: k off: K"),
-            String::from("display the project's keywords"),
-        ],
-        [
-            String::from("classifiers"),
-            String::from("on: c off: C"),
-            String::from("display the project's classifiers"),
-        ],
-        [
-            String::from("artifacts"),
-            String::from("more: a less: A"),
-            String::from("display the project's distribution artifacts;  \
-                          initially a summary of artifact flavors is displayed;  \
-                          with more details, all artifacts are displayed with their target platform;  \
-                          with even more deails, links to file downloads are displayed;  \
-                          with the most deails, the timestamp of each file upload is displayed"),
-        ],
-        [
-            String::from("dependencies"),
-            String::from("on: d off: D"),
-            String::from("display the project's dependencies on other projects"),
-        ],
-        [
-            String::from("readme"),
-            String::from("more: r less: R"),
-            String::from("display the project's README;  \
-                          initially the raw text is displayed;  \
-                          with more details, if the readme is of a known MIME type, it will be styled before displaying"),
-        ],
-        [
-            String::from("packages"),
-            String::from("on: p off: P"),
-            String::from("display the project's importable top-level names"),
-        ],
-        [
-            String::from("executables"),
-            String::from("on: e off: E"),
-            String::from("display the project's executable file names"),
-        ],
-        // session commands
-        // TODO: CTRL-C
-        // TODO: ?
-        [
-            String::from("print"),
-            // issue#3 String::from("preview: CTRL-P exit: CTRL-SHIFT-P"),
-            String::from("CTRL-p"),
-            String::from("display the `kayak` command that will recreate the currently displayed project information. \
-                         the `--format` is explicitly left out"),
-                         // issue#3 previewing the print will maintain the current interactive session, while exiting will clear \
-                         // issue#3 the screen and show only the command. \
-        ],
-    ];
+/// The name, keys, and description of every command listed on the help screen
+pub const HELP_CONTROLS: [[&str; 3]; 25] = [
+    [
+        "new project",
+        "<SPACE>",
+        "look up another project;  \
+         enter its name, and optionally a version and distribution",
+    ],
+    [
+        "jump to version",
+        "j",
+        "look up another version of the current project;  \
+         enter a version, or the start of one to load the newest version that matches",
+    ],
+    [
+        "browse versions",
+        "b",
+        "pick another version of the current project from a list of all its versions;  \
+         move with the arrow keys and load the highlighted version with enter",
+    ],
+    [
+        "search",
+        "/",
+        "highlight where the typed text appears in the displayed details, ignoring case;  \
+         the up and down arrow keys scroll between matches and escape clears the highlights",
+    ],
+    [
+        "status line",
+        "i",
+        "show or hide the line above the menu that names the loaded version, the distribution \
+         inspected and whether it was picked for this platform, and where the metadata came from",
+    ],
+    [
+        "name",
+        "on: n off: N",
+        "display the name and version of the currenly loaded project",
+    ],
+    [
+        "versions",
+        "on: v off: V",
+        "instead of displaying project details, list all versions available",
+    ],
+    [
+        "time",
+        "on: t off: T",
+        "display the project's release timestamp;  \
+         when listing all versions, the release timestamp of each version is displayed",
+    ],
+    [
+        "summary",
+        "on: s off: S",
+        "display the project's summary",
+    ],
+    [
+        "downloads",
+        "on: m off: M",
+        "display how many times the project was downloaded in the last day, week, and month;  \
+         nothing is displayed if the downloads source cannot be reached",
+    ],
+    [
+        "license",
+        "on: l off: L",
+        "display the project's license and copyright",
+    ],
+    [
+        "people",
+        "on: w off: W",
+        "display the project's authors and maintainers",
+    ],
+    [
+        "urls",
+        "on: u off: U",
+        "display the project's URLs",
+    ],
+    [
+        "open",
+        "o",
+        "open one of the project's displayed URLs in the default browser;  \
+         each press opens the next URL, starting with the package index",
+    ],
+    [
+        "keywords",
+        "on: k off: K",
+        "display the project's keywords",
+    ],
+    [
+        "classifiers",
+        "on: c off: C",
+        "display the project's classifiers",
+    ],
+    [
+        "artifacts",
+        "more: a less: A",
+        "display the project's distribution artifacts;  \
+         initially a summary of artifact flavors is displayed;  \
+         with more details, all artifacts are displayed with their target platform;  \
+         with even more details, links to file downloads are displayed;  \
+         with yet more details, the timestamp of each file upload is displayed;  \
+         with the most details, the sha256 digest of each file is displayed",
+    ],
+    [
+        "tag description",
+        "on: x off: X",
+        "describe the selected distribution's compatibility tag;  \
+         such as the Python interpreters and platforms it is built for",
+    ],
+    [
+        "dependencies",
+        "on: d off: D",
+        "display the project's dependencies on other projects",
+    ],
+    [
+        "readme",
+        "more: r less: R",
+        "display the project's README;  \
+         initially the raw text is displayed;  \
+         with more details, if the readme is of a known MIME type, it will be styled before displaying",
+    ],
+    [
+        "packages",
+        "more: p less: P",
+        "display the project's importable top-level names;  \
+         with more details, each name is marked as a package, module, or namespace package",
+    ],
+    [
+        "executables",
+        "on: e off: E",
+        "display the project's executable file names",
+    ],
+    [
+        "entry points",
+        "on: g off: G",
+        "display the project's entry points, other than its scripts, grouped by entry-point group",
+    ],
+    // session commands
+    // TODO: CTRL-C
+    // TODO: ?
+    [
+        "print",
+        "CTRL-p",
+        "display the `kayak` command that will recreate the currently displayed project information. \
+         the `--format` is explicitly left out;  \
+         with SHIFT, quit and print the command instead",
+    ],
+    [
+        "export",
+        "CTRL-e",
+        "save the currently displayed project information as JSON, to a file named for the project \
+         in the working directory unless that file already exists;  \
+         with SHIFT, quit and print the JSON instead",
+    ],
+];
+
+/// The displayed details of project as JSON, along with the file CTRL-e saves them to
+///
+/// The file is named for the project and the displayed version, or for its versions when they are
+/// all listed.
+pub fn export_json(
+    project: &mut Project,
+    display_fields: &DisplayFields,
+) -> Result<(PathBuf, String)> {
+    let report = serde_json::to_string_pretty(&json::report(project, display_fields)?)?;
+    let name = distribution::normalize_package_name(&project.package_selector())?;
+    let path = if display_fields.versions {
+        format!("{name}-versions.json")
+    } else {
+        format!("{name}-{}.json", project.version()?.version)
+    };
+    Ok((PathBuf::from(path), report))
+}
+
+/// Save an exported report to path, saying what was done in a message for the session
+///
+/// A file that already exists is never overwritten, it may be an earlier export or something
+/// else entirely that happens to share the name.
+pub fn save_export(path: &Path, report: &str) -> Result<String, String> {
+    let saved = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(report.as_bytes()));
+    match saved {
+        Ok(()) => Ok(format!("saved the displayed details to {}", path.display())),
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => Err(format!(
+            "{} already exists, move it away to save the displayed details there",
+            path.display()
+        )),
+        Err(err) => Err(format!("could not save {}: {err}", path.display())),
+    }
+}
+
+/// The height of one help entry: its borders and up to two lines of description
+const HELP_ROW_HEIGHT: u16 = 4;
+
+/// Which HELP_CONTROLS fit on page of a help screen height rows tall, and how many pages there are
+///
+/// A page past the last is shown as the last page.
+pub fn help_page(height: u16, page: usize) -> (Range<usize>, usize) {
+    let per_page = usize::from((height / HELP_ROW_HEIGHT).max(1));
+    let pages = HELP_CONTROLS.len().div_ceil(per_page);
+    let start = page.min(pages - 1) * per_page;
+    (start..(start + per_page).min(HELP_CONTROLS.len()), pages)
+}
+
+fn render_interactive_help(frame: &mut Frame, area: Rect, page: &mut usize) -> usize {
+    let (controls, pages) = help_page(area.height, *page);
+    *page = (*page).min(pages - 1);
 
     let controls_areas = Layout::default()
         .direction(Direction::Vertical)
-        .constraints(vec![Constraint::Fill(1); controls_text.len()])
+        .constraints(vec![Constraint::Length(HELP_ROW_HEIGHT); controls.len()])
         .split(area);
 
-    for (c, control_text) in controls_text.into_iter().enumerate() {
+    for (c, control_text) in HELP_CONTROLS[controls].iter().enumerate() {
         let control_sections = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Max(16), Constraint::Max(9), Constraint::Fill(1)])
             .split(controls_areas[c]);
-        for (s, segment) in control_text.into_iter().enumerate() {
+        for (s, segment) in control_text.iter().enumerate() {
             frame.render_widget(
-                Paragraph::new(segment)
+                Paragraph::new(*segment)
                     .alignment(Alignment::Center)
                     .wrap(Wrap { trim: true })
                     .block(Block::default().borders(Borders::ALL)),
@@ -292,6 +726,29 @@ fn render_interactive_help(frame: &mut Frame, area: Rect) {
             );
         }
     }
+    pages
+}
+
+fn render_help_menu(frame: &mut Frame, area: Rect, page: usize, pages: usize) {
+    if pages <= 1 {
+        return render_no_commands_menu(frame, area);
+    }
+    let [page_area, close_area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Fill(1)])
+        .areas::<2>(area);
+    frame.render_widget(
+        Paragraph::new(format!("<LEFT>/<RIGHT>: page {} of {pages}", page + 1))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::LEFT | Borders::TOP | Borders::RIGHT)),
+        page_area,
+    );
+    frame.render_widget(
+        Paragraph::new(String::from("ANY OTHER KEY TO CLOSE"))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::LEFT | Borders::TOP | Borders::RIGHT)),
+        close_area,
+    );
 }
 
 fn render_no_commands_menu(frame: &mut Frame, area: Rect) {
@@ -303,6 +760,362 @@ fn render_no_commands_menu(frame: &mut Frame, area: Rect) {
     );
 }
 
+/// The versions that start with input, newest first, except that an exact match always comes first
+///
+/// Input only matches whole segments of a release, so 1 matches 1.0 and 1.1 but not 10.0.
+pub fn matching_versions(versions: &[Version], input: &str) -> Vec<Version> {
+    let input = input.trim();
+    let exact = Version::parse(input);
+    let starts_with = |version: String| {
+        version.strip_prefix(input).is_some_and(|rest| {
+            !(input.ends_with(|c: char| c.is_ascii_digit())
+                && rest.starts_with(|c: char| c.is_ascii_digit()))
+        })
+    };
+    let mut matching = versions
+        .iter()
+        .rev()
+        .filter(|v| starts_with(v.normalize()) || starts_with(v.to_string()))
+        .cloned()
+        .collect::<Vec<_>>();
+    if let Some(exact) = exact.and_then(|e| versions.iter().find(|v| **v == e)) {
+        matching.retain(|v| v != exact);
+        matching.insert(0, exact.clone());
+    }
+    matching
+}
+
+/// A case-insensitive search of the displayed details
+#[derive(Default)]
+struct Search {
+    query: String,
+    /// The match that is scrolled into view
+    current: usize,
+    /// How many matches there were when the details were last drawn
+    found: usize,
+}
+
+impl Search {
+    fn next(&mut self) {
+        if self.found > 0 {
+            self.current = (self.current + 1) % self.found;
+        }
+    }
+
+    fn previous(&mut self) {
+        if self.found > 0 {
+            self.current = (self.current + self.found - 1) % self.found;
+        }
+    }
+}
+
+/// Where query is found in the text drawn to buffer, as the row and columns of each match
+///
+/// Matching ignores case and each row is searched separately, so a match is never split over
+/// lines.
+pub fn find_matches(buffer: &Buffer, query: &str) -> Vec<(u16, Range<u16>)> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let area = buffer.area;
+    let mut matches = Vec::new();
+    for y in 0..area.height {
+        // the lowercased row, and the columns drawn by each of its bytes
+        let mut text = String::new();
+        let mut columns = Vec::new();
+        let mut x = 0;
+        while x < area.width {
+            let symbol = buffer[(area.x + x, area.y + y)].symbol();
+            // a wide character covers the cells after it
+            let width = u16::try_from(Span::raw(symbol).width()).unwrap_or(1).max(1);
+            let lower = symbol.to_lowercase();
+            columns.extend(iter::repeat_n(x..x + width, lower.len()));
+            text += &lower;
+            x += width;
+        }
+        for (start, found) in text.match_indices(&query) {
+            let end = start + found.len() - 1;
+            matches.push((y, columns[start].start..columns[end].end));
+        }
+    }
+    matches
+}
+
+/// Render the details of project scrolled so that the current match of search is in view, with
+/// every match highlighted
+fn render_search(
+    frame: &mut Frame,
+    area: Rect,
+    project: &mut Project,
+    display_fields: &DisplayFields,
+    search: &mut Search,
+) -> Result<(), String> {
+    let details = render_buffer(project, display_fields, area.width)?;
+    let matches = find_matches(&details, &search.query);
+    search.found = matches.len();
+    search.current = search.current.min(matches.len().saturating_sub(1));
+    // like less, the current match is kept a third of the way down the screen
+    let top = matches
+        .get(search.current)
+        .map_or(0, |(row, _)| row.saturating_sub(area.height / 3))
+        .min(details.area.height.saturating_sub(area.height));
+
+    let buffer = frame.buffer_mut();
+    for y in 0..area.height.min(details.area.height - top) {
+        for x in 0..area.width {
+            buffer[(area.x + x, area.y + y)] = details[(x, top + y)].clone();
+        }
+    }
+    for (m, (row, columns)) in matches.into_iter().enumerate() {
+        if !(top..top + area.height).contains(&row) {
+            continue;
+        }
+        let style = if m == search.current {
+            Style::new().black().on_yellow()
+        } else {
+            Style::new().reversed()
+        };
+        buffer.set_style(
+            Rect::new(
+                area.x + columns.start,
+                area.y + row - top,
+                columns.end - columns.start,
+                1,
+            ),
+            style,
+        );
+    }
+    Ok(())
+}
+
+fn render_search_menu(frame: &mut Frame, area: Rect, search: &Search) {
+    let [enter_area, usage_area, quit_area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Max(31),
+            Constraint::Fill(1),
+            Constraint::Max(24),
+        ])
+        .areas::<3>(area);
+
+    let found = match search.found {
+        _ if search.query.is_empty() => String::from("<UP>/<DOWN>: previous/next match"),
+        0 => String::from("no matches"),
+        found => format!("match {} of {found}", search.current + 1),
+    };
+    let enter_content = Paragraph::new(String::from("<ENTER>: keep highlights"))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
+    let usage_content = Paragraph::new(format!("/{}  {found}", search.query))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+    let quit_content = Paragraph::new(String::from("<ESC>: cancel"))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
+
+    frame.render_widget(enter_content, enter_area);
+    frame.render_widget(usage_content, usage_area);
+    frame.render_widget(quit_content, quit_area);
+}
+
+/// Replace project with another version of itself
+///
+/// If the current project was displayed, it is kept as the last good project in case the new
+/// version fails to load.
+pub fn switch_version(
+    project: &mut Option<Project>,
+    last_good_project: &mut Option<Project>,
+    project_loads: bool,
+    version: &Version,
+) {
+    let current = project
+        .take()
+        .expect("a version can only be chosen for a loaded project");
+    *project = Some(Project::new(
+        current.package_selector(),
+        Some(version.normalize()),
+        current.distribution_selector(),
+        current.options().clone(),
+    ));
+    if project_loads {
+        *last_good_project = Some(current);
+    }
+}
+
+/// Every version of a project, newest first, to pick one from
+pub struct VersionPicker {
+    /// Each version and whether all of its files are yanked
+    versions: Vec<(Version, bool)>,
+    /// The version that is currently displayed
+    current: Option<Version>,
+    state: ListState,
+}
+
+impl VersionPicker {
+    pub fn new(project: &mut Project) -> Result<Self> {
+        let versions = project
+            .package()?
+            .ordered_releases()
+            .into_iter()
+            .rev()
+            .map(|(v, urls)| (v, is_yanked_release(urls)))
+            .collect::<Vec<_>>();
+        let current = Version::parse(&project.version()?.version);
+        let selected = versions
+            .iter()
+            .position(|(v, _)| Some(v) == current.as_ref())
+            .or((!versions.is_empty()).then_some(0));
+        Ok(VersionPicker {
+            versions,
+            current,
+            state: ListState::default().with_selected(selected),
+        })
+    }
+
+    /// The highlighted version, which enter loads
+    pub fn selected(&self) -> Option<&Version> {
+        self.state
+            .selected()
+            .and_then(|s| self.versions.get(s))
+            .map(|(v, _)| v)
+    }
+
+    /// Highlight the next newer version, as the up key does
+    pub fn select_previous(&mut self) {
+        self.state.select_previous();
+    }
+
+    /// Highlight the next older version, as the down key does
+    pub fn select_next(&mut self) {
+        self.state.select_next();
+    }
+}
+
+fn render_version_picker(frame: &mut Frame, area: Rect, name: String, picker: &mut VersionPicker) {
+    let area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Max(40),
+            Constraint::Fill(1),
+        ])
+        .split(area)[1];
+    let items = picker
+        .versions
+        .iter()
+        .map(|(version, yanked)| {
+            let mut line = vec![Span::raw(version.normalize())];
+            if *yanked {
+                line.push(Span::styled(" [YANKED]", Style::new().red()));
+            }
+            if Some(version) == picker.current.as_ref() {
+                line.push(Span::styled(" (current)", Style::new().italic()));
+            }
+            ListItem::new(Line::from(line))
+        })
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(name)
+                .borders(Borders::ALL)
+                .border_style(Color::Blue),
+        )
+        .highlight_style(Style::new().reversed())
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut picker.state);
+}
+
+fn render_version_picker_menu(frame: &mut Frame, area: Rect) {
+    let [enter_area, usage_area, quit_area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Max(31),
+            Constraint::Fill(1),
+            Constraint::Max(24),
+        ])
+        .areas::<3>(area);
+
+    let enter_content = Paragraph::new(String::from("<ENTER>: load version"))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
+    let usage_content = Paragraph::new(String::from("<UP>/<DOWN>: move"))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+    let quit_content = Paragraph::new(String::from("<ESC>: cancel"))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
+
+    frame.render_widget(enter_content, enter_area);
+    frame.render_widget(usage_content, usage_area);
+    frame.render_widget(quit_content, quit_area);
+}
+
+fn render_version_prompt(frame: &mut Frame, area: Rect, project: &mut Project, input: &str) {
+    let versions = project
+        .package()
+        .map(|p| matching_versions(&p.ordered_versions(), input))
+        .unwrap_or_default();
+    let area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Max((versions.len() + 3).try_into().unwrap_or(u16::MAX)),
+            Constraint::Fill(1),
+        ])
+        .horizontal_margin(4)
+        .split(area)[1];
+    let lines = iter::once(Line::from(format!("version: {input}")))
+        .chain(versions.iter().enumerate().map(|(v, version)| {
+            // the first match is the one that will be loaded
+            if v == 0 {
+                Line::from(Span::styled(version.normalize(), Style::new().reversed()))
+            } else {
+                Line::from(version.normalize())
+            }
+        }))
+        .collect::<Vec<_>>();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center).block(
+            Block::default()
+                .title(project.package_selector())
+                .borders(Borders::ALL)
+                .border_style(Color::Blue),
+        ),
+        area,
+    );
+}
+
+fn render_version_prompt_menu(frame: &mut Frame, area: Rect) {
+    let [enter_area, usage_area, quit_area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Max(31),
+            Constraint::Fill(1),
+            Constraint::Max(24),
+        ])
+        .areas::<3>(area);
+
+    let enter_content = Paragraph::new(String::from("<ENTER>: load version"))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
+    let usage_content = Paragraph::new(String::from("version or prefix of a version"))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+    let quit_content = Paragraph::new(String::from("<ESC>: cancel"))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP | Borders::LEFT | Borders::RIGHT));
+
+    frame.render_widget(enter_content, enter_area);
+    frame.render_widget(usage_content, usage_area);
+    frame.render_widget(quit_content, quit_area);
+}
+
 fn render_new_project_prompt_menu(frame: &mut Frame, area: Rect) {
     // anchor the quit and enter commands, so they are always visable
     let [enter_area, usage_area, quit_area] = Layout::default()
@@ -351,6 +1164,99 @@ fn render_messages(
     }
 }
 
+/// Fetch everything project needs to be displayed on another thread, so that the display is
+/// never blocked, and send it back when done
+fn spawn_fetch(
+    mut project: Project,
+    display_fields: DisplayFields,
+) -> Receiver<(Project, Result<()>)> {
+    let (send, receive) = mpsc::channel();
+    thread::spawn(move || {
+        let fetched = project.prefetch(&display_fields);
+        // the session may have been quit while waiting
+        let _ = send.send((project, fetched));
+    });
+    receive
+}
+
+fn render_loading_menu(frame: &mut Frame, area: Rect) {
+    frame.render_widget(
+        Paragraph::new(String::from("q[uit]"))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::LEFT | Borders::TOP | Borders::RIGHT)),
+        area,
+    );
+}
+
+/// The smallest screen with room for both the quit and help commands and a line of display
+const MIN_WIDTH: u16 = 24;
+const MIN_HEIGHT: u16 = 4;
+
+fn render_too_small(frame: &mut Frame) {
+    frame.render_widget(
+        Paragraph::new("the terminal is too small, enlarge it or press q to quit")
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        frame.area(),
+    );
+}
+
+/// Split the screen into the main display and, anchored to the bottom, the menu dock
+fn split_dock(area: Rect) -> [Rect; 2] {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Max(2)])
+        .areas::<2>(area)
+}
+
+/// Describe what is on screen: the version loaded, the distribution inspected and whether it was
+/// asked for or picked, and where the metadata came from
+///
+/// Only what has already been fetched is described, so this never waits on the index.
+pub fn status_line(project: &mut Project) -> String {
+    let mut status = Vec::new();
+    if project.is_version_fetched() {
+        if let Ok(version) = project.version() {
+            status.push(format!("{} {}", version.name, version.version));
+        }
+    }
+    if project.is_distribution_loaded() {
+        let chosen = project.distribution_selector().is_some();
+        if let Ok(dist) = project.distribution() {
+            let tag = match dist.filename() {
+                Ok(wheel) => wheel.compatibility_tag.to_string(),
+                Err(_) => dist.packagetype.clone(),
+            };
+            if chosen {
+                status.push(tag);
+            } else {
+                status.push(format!("{tag} (picked for this platform)"));
+            }
+        }
+    }
+    status.push(format!("from {}", project.source()));
+    status.join(" | ")
+}
+
+/// Take a line for the status from the bottom of the display, unless the display is too short
+fn split_status(display: Rect) -> (Rect, Option<Rect>) {
+    if display.height < 2 {
+        return (display, None);
+    }
+    let [display, status] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(1)])
+        .areas::<2>(display);
+    (display, Some(status))
+}
+
+fn render_status(frame: &mut Frame, area: Rect, project: &mut Project) {
+    frame.render_widget(
+        Paragraph::new(status_line(project)).style(Style::new().reversed()),
+        area,
+    );
+}
+
 enum Messages {
     Info(String),
     Error(String),
@@ -358,17 +1264,40 @@ enum Messages {
 }
 
 enum DisplayMode {
-    Help,
+    /// The page of help being shown
+    Help(usize),
     Info(Messages),
     Input(Messages),
+    /// Choosing another version of the loaded project, holding what has been typed so far
+    VersionInput(String),
+    /// Typing a search of the displayed details
+    SearchInput,
+    VersionList(VersionPicker),
+    /// Waiting for the named project to be fetched, which is taken from the session until it is
+    Loading(String, Receiver<(Project, Result<()>)>),
     Normal,
 }
 
-pub fn run(project: Option<Project>, display_fields: DisplayFields) -> Result<()> {
+pub fn run(
+    project: Option<Project>,
+    display_fields: DisplayFields,
+    options: ProjectOptions,
+) -> Result<()> {
     let mut project = project;
     let mut project_loads = false;
     let mut last_good_project: Option<Project> = None;
     let mut display_fields = display_fields;
+    // the details to put back once the list of all versions is left
+    let mut details_fields = None;
+    // which of the displayed urls will be opened next
+    let mut url_cursor = 0;
+    // highlighted in the details until cleared, even after the search is typed
+    let mut search: Option<Search> = None;
+    let mut show_status = true;
+    // how many pages of help there were when help was last drawn, only more than one can be paged
+    let mut help_pages = 1;
+    // printed once the terminal is restored, so that it outlasts the session
+    let mut on_exit = None;
     let mut mode = if project.is_some() {
         DisplayMode::Normal
     } else {
@@ -381,17 +1310,48 @@ pub fn run(project: Option<Project>, display_fields: DisplayFields) -> Result<()
     terminal.clear()?;
 
     loop {
+        if let (DisplayMode::Normal, Some(prj)) = (&mode, &project) {
+            if prj.needs_fetch(&display_fields) {
+                let prj = project.take().expect("the project was just checked");
+                let name = prj.package_selector();
+                mode = DisplayMode::Loading(name, spawn_fetch(prj, display_fields.clone()));
+            }
+        }
+        if let DisplayMode::Loading(_, fetching) = &mode {
+            match fetching.try_recv() {
+                Ok((prj, Ok(()))) => {
+                    project = Some(prj);
+                    mode = DisplayMode::Normal;
+                }
+                Ok((_, Err(err))) => {
+                    project = last_good_project.take();
+                    mode = DisplayMode::Info(Messages::Error(err.to_string()));
+                }
+                Err(TryRecvError::Disconnected) => {
+                    project = last_good_project.take();
+                    mode = DisplayMode::Info(Messages::Error(String::from(
+                        "the project could not be fetched",
+                    )));
+                }
+                Err(TryRecvError::Empty) => (),
+            }
+        }
         terminal.draw(|frame| {
-            // anchor menu to the bottom
-            let [display, dock] = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Fill(1), Constraint::Max(2)])
-                .areas::<2>(frame.area());
-
-            match &mode {
-                DisplayMode::Help => {
-                    render_interactive_help(frame, display);
-                    render_no_commands_menu(frame, dock);
+            if frame.area().width < MIN_WIDTH || frame.area().height < MIN_HEIGHT {
+                return render_too_small(frame);
+            }
+            let [display, dock] = split_dock(frame.area());
+            // the project is taken away while it is loading, so there is nothing to describe
+            let (display, status_area) = if show_status && project.is_some() {
+                split_status(display)
+            } else {
+                (display, None)
+            };
+
+            match &mut mode {
+                DisplayMode::Help(page) => {
+                    help_pages = render_interactive_help(frame, display, page);
+                    render_help_menu(frame, dock, *page, help_pages);
                 }
                 DisplayMode::Info(info) => {
                     render_messages(frame, display, &mut project, &display_fields, info);
@@ -401,11 +1361,43 @@ pub fn run(project: Option<Project>, display_fields: DisplayFields) -> Result<()
                     render_messages(frame, display, &mut project, &display_fields, input);
                     render_new_project_prompt_menu(frame, dock);
                 }
+                DisplayMode::VersionInput(input) => {
+                    let prj = project
+                        .as_mut()
+                        .expect("a version can only be chosen for a loaded project");
+                    let _ = render(frame, display, prj, &display_fields);
+                    render_version_prompt(frame, display, prj, input);
+                    render_version_prompt_menu(frame, dock);
+                }
+                DisplayMode::VersionList(picker) => {
+                    let prj = project
+                        .as_mut()
+                        .expect("a version can only be chosen for a loaded project");
+                    let _ = render(frame, display, prj, &display_fields);
+                    render_version_picker(frame, display, prj.package_selector(), picker);
+                    render_version_picker_menu(frame, dock);
+                }
+                DisplayMode::SearchInput => {
+                    let prj = project
+                        .as_mut()
+                        .expect("only a loaded project can be searched");
+                    let search = search.get_or_insert_with(Search::default);
+                    let _ = render_search(frame, display, prj, &display_fields, search);
+                    render_search_menu(frame, dock, search);
+                }
+                DisplayMode::Loading(name, _) => {
+                    render_popup(frame, display, format!("loading {name}..."), false);
+                    render_loading_menu(frame, dock);
+                }
                 DisplayMode::Normal => {
                     let prj = &mut project
                         .as_mut()
                         .expect("only attempt to render project after a selection has been made");
-                    match render(frame, display, prj, &display_fields) {
+                    let rendered = match &mut search {
+                        Some(search) => render_search(frame, display, prj, &display_fields, search),
+                        None => render(frame, display, prj, &display_fields),
+                    };
+                    match rendered {
                         Ok(()) => {
                             project_loads = true;
                         }
@@ -414,12 +1406,21 @@ pub fn run(project: Option<Project>, display_fields: DisplayFields) -> Result<()
                             mode = DisplayMode::Info(Messages::Error(err.to_string()));
                         }
                     }
-                    render_menu(frame, dock);
+                    render_menu(frame, dock, &display_fields);
                 }
             }
+            if let (Some(area), Some(prj)) = (status_area, &mut project) {
+                render_status(frame, area, prj);
+            }
         })?;
         if event::poll(std::time::Duration::from_millis(16))? {
-            if let event::Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let event::Event::Resize(..) = event {
+                // some terminals leave stale cells behind, so draw everything again at the new size
+                terminal.autoresize()?;
+                terminal.clear()?;
+            }
+            if let event::Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
                     // CTRL-C always quits, check first
                     if let KeyCode::Char('c') = key.code {
@@ -428,13 +1429,27 @@ pub fn run(project: Option<Project>, display_fields: DisplayFields) -> Result<()
                         }
                     }
                     match &mut mode {
-                        DisplayMode::Help => {
-                            mode = if project.is_some() {
-                                DisplayMode::Normal
-                            } else {
-                                DisplayMode::Input(Messages::Info(String::new()))
-                            };
-                        }
+                        DisplayMode::Help(page) => match key.code {
+                            // moving past the last page is undone when the page is drawn
+                            KeyCode::Right
+                            | KeyCode::Down
+                            | KeyCode::PageDown
+                            | KeyCode::Char(' ')
+                                if help_pages > 1 =>
+                            {
+                                *page += 1
+                            }
+                            KeyCode::Left | KeyCode::Up | KeyCode::PageUp if help_pages > 1 => {
+                                *page = page.saturating_sub(1)
+                            }
+                            _ => {
+                                mode = if project.is_some() {
+                                    DisplayMode::Normal
+                                } else {
+                                    DisplayMode::Input(Messages::Info(String::new()))
+                                };
+                            }
+                        },
                         DisplayMode::Info(_) => {
                             mode = if project.is_some() {
                                 DisplayMode::Normal
@@ -469,29 +1484,24 @@ pub fn run(project: Option<Project>, display_fields: DisplayFields) -> Result<()
                                     mode = match user_progress {
                                         Messages::Info(user_input)
                                         | Messages::InfoError((user_input, _)) => {
-                                            let mut requested_project =
-                                                user_input.split_whitespace();
-                                            if let Some(name) = requested_project.next() {
-                                                let version =
-                                                    requested_project.next().map(str::to_string);
-                                                let distribution =
-                                                    requested_project.next().map(str::to_string);
-                                                if project_loads {
-                                                    last_good_project = project;
+                                            match parse_project_input(user_input) {
+                                                Ok((name, version, distribution)) => {
+                                                    if project_loads {
+                                                        last_good_project = project;
+                                                    }
+                                                    project = Some(Project::new(
+                                                        name,
+                                                        version,
+                                                        distribution,
+                                                        options.clone(),
+                                                    ));
+                                                    url_cursor = 0;
+                                                    DisplayMode::Normal
                                                 }
-                                                project = Some(Project::new(
-                                                    name.to_string(),
-                                                    version,
-                                                    distribution,
-                                                ));
-                                                DisplayMode::Normal
-                                            } else {
-                                                DisplayMode::Input(Messages::InfoError((
-                                                    user_input.to_string(),
-                                                    String::from(
-                                                        "please enter the name of a package",
-                                                    ),
-                                                )))
+                                                // the input is kept so only the mistake is retyped
+                                                Err(e) => DisplayMode::Input(Messages::InfoError(
+                                                    (user_input.to_string(), e),
+                                                )),
                                             }
                                         }
                                         Messages::Error(_) => DisplayMode::Input(Messages::Error(
@@ -524,113 +1534,196 @@ pub fn run(project: Option<Project>, display_fields: DisplayFields) -> Result<()
                                 _ => (),
                             }
                         }
+                        DisplayMode::VersionInput(input) => match key.code {
+                            KeyCode::Char(key_char) => input.push(key_char),
+                            KeyCode::Backspace => {
+                                input.pop();
+                            }
+                            KeyCode::Esc => mode = DisplayMode::Normal,
+                            KeyCode::Enter => {
+                                let prj = project
+                                    .as_mut()
+                                    .expect("a version can only be chosen for a loaded project");
+                                let chosen = prj.package().map(|p| {
+                                    matching_versions(&p.ordered_versions(), input)
+                                        .into_iter()
+                                        .next()
+                                });
+                                mode = match chosen {
+                                    Ok(Some(version)) => {
+                                        switch_version(
+                                            &mut project,
+                                            &mut last_good_project,
+                                            project_loads,
+                                            &version,
+                                        );
+                                        url_cursor = 0;
+                                        DisplayMode::Normal
+                                    }
+                                    Ok(None) => DisplayMode::Info(Messages::Error(format!(
+                                        "no version of {} matches {input:?}",
+                                        prj.package_selector()
+                                    ))),
+                                    Err(err) => DisplayMode::Info(Messages::Error(err.to_string())),
+                                };
+                            }
+                            _ => (),
+                        },
+                        DisplayMode::VersionList(picker) => match key.code {
+                            KeyCode::Up => picker.select_previous(),
+                            KeyCode::Down => picker.select_next(),
+                            KeyCode::Esc => mode = DisplayMode::Normal,
+                            KeyCode::Enter => {
+                                if let Some(version) = picker.selected().cloned() {
+                                    switch_version(
+                                        &mut project,
+                                        &mut last_good_project,
+                                        project_loads,
+                                        &version,
+                                    );
+                                    url_cursor = 0;
+                                }
+                                mode = DisplayMode::Normal;
+                            }
+                            _ => (),
+                        },
+                        DisplayMode::SearchInput => {
+                            let typed = search.get_or_insert_with(Search::default);
+                            match key.code {
+                                KeyCode::Char(key_char) => {
+                                    typed.query.push(key_char);
+                                    typed.current = 0;
+                                }
+                                KeyCode::Backspace => {
+                                    typed.query.pop();
+                                    typed.current = 0;
+                                }
+                                KeyCode::Down => typed.next(),
+                                KeyCode::Up => typed.previous(),
+                                KeyCode::Enter => {
+                                    if typed.query.is_empty() {
+                                        search = None;
+                                    }
+                                    mode = DisplayMode::Normal;
+                                }
+                                KeyCode::Esc => {
+                                    search = None;
+                                    mode = DisplayMode::Normal;
+                                }
+                                _ => (),
+                            }
+                        }
+                        // nothing can change until the project arrives, but quitting is not blocked
+                        DisplayMode::Loading(..) => {
+                            if let KeyCode::Char('q') = key.code {
+                                break;
+                            }
+                        }
                         DisplayMode::Normal => match key.code {
                             KeyCode::Char('q') => {
                                 break;
                             }
                             KeyCode::Char('?') => {
-                                mode = DisplayMode::Help;
+                                mode = DisplayMode::Help(0);
                             }
                             KeyCode::Char(' ') => {
                                 mode = DisplayMode::Input(Messages::Info(String::new()));
                             }
-                            KeyCode::Char('n') => {
-                                display_fields.name = true;
+                            KeyCode::Char('j') => {
+                                mode = DisplayMode::VersionInput(String::new());
                             }
-                            KeyCode::Char('N') => {
-                                display_fields.name = false;
+                            KeyCode::Char('/') => {
+                                search = Some(Search::default());
+                                mode = DisplayMode::SearchInput;
                             }
-                            KeyCode::Char('v') => {
-                                display_fields.versions = true;
-                            }
-                            KeyCode::Char('V') => {
-                                display_fields.versions = false;
-                            }
-                            KeyCode::Char('t') => {
-                                display_fields.time = true;
-                            }
-                            KeyCode::Char('T') => {
-                                display_fields.time = false;
-                            }
-                            KeyCode::Char('s') => {
-                                display_fields.summary = true;
-                            }
-                            KeyCode::Char('S') => {
-                                display_fields.summary = false;
-                            }
-                            KeyCode::Char('l') => {
-                                display_fields.license = true;
-                            }
-                            KeyCode::Char('L') => {
-                                display_fields.license = false;
-                            }
-                            KeyCode::Char('u') => {
-                                display_fields.urls = true;
-                            }
-                            KeyCode::Char('U') => {
-                                display_fields.urls = false;
-                            }
-                            KeyCode::Char('k') => {
-                                display_fields.keywords = true;
-                            }
-                            KeyCode::Char('K') => {
-                                display_fields.keywords = false;
-                            }
-                            KeyCode::Char('c') => {
-                                display_fields.classifiers = true;
-                            }
-                            KeyCode::Char('C') => {
-                                display_fields.classifiers = false;
-                            }
-                            KeyCode::Char('a') => {
-                                if display_fields.artifacts < 4 {
-                                    display_fields.artifacts += 1;
+                            KeyCode::Char('i') => show_status = !show_status,
+                            KeyCode::Down => {
+                                if let Some(search) = &mut search {
+                                    search.next();
                                 }
                             }
-                            KeyCode::Char('A') => {
-                                if display_fields.artifacts > 0 {
-                                    display_fields.artifacts -= 1;
+                            KeyCode::Up => {
+                                if let Some(search) = &mut search {
+                                    search.previous();
                                 }
                             }
-                            KeyCode::Char('d') => {
-                                display_fields.dependencies = true;
+                            KeyCode::Esc => search = None,
+                            KeyCode::Char('b') => {
+                                let prj = project
+                                    .as_mut()
+                                    .expect("normal mode should alway have a project loaded");
+                                mode = match VersionPicker::new(prj) {
+                                    Ok(picker) => DisplayMode::VersionList(picker),
+                                    Err(err) => DisplayMode::Info(Messages::Error(err.to_string())),
+                                };
                             }
-                            KeyCode::Char('D') => {
-                                display_fields.dependencies = false;
-                            }
-                            KeyCode::Char('r') => {
-                                if display_fields.readme < 2 {
-                                    display_fields.readme += 1;
-                                }
+                            KeyCode::Char('o') if display_fields.urls => {
+                                let prj = project
+                                    .as_mut()
+                                    .expect("normal mode should alway have a project loaded");
+                                mode = match prj.version().map(list_urls) {
+                                    Ok(urls) if !urls.is_empty() => {
+                                        let (label, url) = &urls[url_cursor % urls.len()];
+                                        url_cursor += 1;
+                                        match open_url(url) {
+                                            Ok(()) => DisplayMode::Info(Messages::Info(format!(
+                                                "opening {label}: {url}"
+                                            ))),
+                                            Err(err) => DisplayMode::Info(Messages::Error(
+                                                format!("could not open {url}: {err}"),
+                                            )),
+                                        }
+                                    }
+                                    Ok(_) => DisplayMode::Info(Messages::Error(String::from(
+                                        "this project has no URLs",
+                                    ))),
+                                    Err(err) => DisplayMode::Info(Messages::Error(err.to_string())),
+                                };
                             }
-                            KeyCode::Char('R') => {
-                                if display_fields.readme > 0 {
-                                    display_fields.readme -= 1;
+                            KeyCode::Char('p' | 'P')
+                                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                            {
+                                let command = encode_cli(
+                                    project
+                                        .as_mut()
+                                        .expect("normal mode should alway have a project loaded"),
+                                    &display_fields,
+                                );
+                                if key.modifiers.contains(KeyModifiers::SHIFT) {
+                                    on_exit = Some(command);
+                                    break;
                                 }
+                                mode = DisplayMode::Info(Messages::Info(command));
                             }
-                            KeyCode::Char('p') => {
-                                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                    mode = DisplayMode::Info(Messages::Info(encode_cli(
-                                        &mut project.as_mut().expect(
-                                            "normal mode should alway have a project loaded",
-                                        ),
-                                        &display_fields,
-                                    )));
-                                    if key.modifiers.contains(KeyModifiers::SHIFT) {
+                            KeyCode::Char('e' | 'E')
+                                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                            {
+                                let prj = project
+                                    .as_mut()
+                                    .expect("normal mode should alway have a project loaded");
+                                mode = match export_json(prj, &display_fields) {
+                                    Ok((_, report))
+                                        if key.modifiers.contains(KeyModifiers::SHIFT) =>
+                                    {
+                                        on_exit = Some(report);
                                         break;
                                     }
-                                } else {
-                                    display_fields.packages = true;
-                                }
+                                    Ok((path, report)) => match save_export(&path, &report) {
+                                        Ok(saved) => DisplayMode::Info(Messages::Info(saved)),
+                                        Err(err) => DisplayMode::Info(Messages::Error(err)),
+                                    },
+                                    Err(err) => DisplayMode::Info(Messages::Error(err.to_string())),
+                                };
                             }
-                            KeyCode::Char('P') => {
-                                display_fields.packages = false;
+                            KeyCode::Char(c @ ('v' | 'V')) => {
+                                switch_versions_view(
+                                    &mut display_fields,
+                                    &mut details_fields,
+                                    c == 'v',
+                                );
                             }
-                            KeyCode::Char('e') => {
-                                display_fields.executables = true;
-                            }
-                            KeyCode::Char('E') => {
-                                display_fields.executables = false;
+                            KeyCode::Char(c) => {
+                                toggle_field(&mut display_fields, c);
                             }
                             _ => (),
                         },
@@ -641,5 +1734,8 @@ pub fn run(project: Option<Project>, display_fields: DisplayFields) -> Result<()
     }
     stdout().execute(LeaveAlternateScreen)?;
     disable_raw_mode()?;
+    if let Some(output) = on_exit {
+        println!("{output}");
+    }
     Ok(())
 }
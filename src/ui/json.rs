@@ -0,0 +1,859 @@
+use crate::package_inspect;
+use crate::ui::*;
+use crate::warehouse::DistributionUrl;
+use crate::{DisplayFields, Field, Project};
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::Write;
+
+/// The project details selected by DisplayFields, in a serializable form
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub yanked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copyright: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub people: Option<Vec<PersonReport>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downloads: Option<DownloadsReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urls: Option<Vec<UrlReport>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classifiers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unrecognized_classifiers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<ArtifactReport>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<DependenciesReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packages: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_kinds: Option<Vec<PackageKindReport>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executables: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry_points: Option<Vec<EntryPointGroupReport>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+}
+
+/// An author or maintainer, with a name, an email, or both
+#[derive(Debug, Serialize)]
+pub struct PersonReport {
+    pub roles: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// How many times the project was downloaded recently
+#[derive(Debug, Serialize)]
+pub struct DownloadsReport {
+    pub last_day: u64,
+    pub last_week: u64,
+    pub last_month: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UrlReport {
+    pub label: String,
+    pub url: String,
+}
+
+/// One clause of a version specifier, such as {"operator": "!=", "version": "3.9.*"}
+#[derive(Debug, Serialize)]
+pub struct SpecifierReport {
+    pub operator: String,
+    pub version: String,
+}
+
+/// A distribution artifact; more fields are populated at higher artifact detail levels
+#[derive(Debug, Serialize)]
+pub struct ArtifactReport {
+    pub packagetype: String,
+    pub filename: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// A top-level import name and whether it is a package, module, or namespace package
+#[derive(Debug, Serialize)]
+pub struct PackageKindReport {
+    pub name: String,
+    pub kind: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntryPointGroupReport {
+    pub group: String,
+    pub entry_points: Vec<EntryPointReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntryPointReport {
+    pub name: String,
+    pub object: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependenciesReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires_python: Option<String>,
+    /// Each clause of requires_python, when it can be understood
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub python_specifiers: Option<Vec<SpecifierReport>>,
+    /// Dependencies that are always required
+    pub requires_dist: Vec<String>,
+    /// The dependencies of each displayed extra
+    pub extras: Vec<ExtraReport>,
+    /// Extras that are available but not displayed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub other_extras: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExtraReport {
+    pub name: String,
+    pub requires_dist: Vec<String>,
+}
+
+/// All versions of a project, newest first
+#[derive(Debug, Serialize)]
+pub struct VersionsReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub versions: Vec<String>,
+    /// The listed versions that are yanked
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub yanked: Vec<String>,
+    /// The listed versions that are known to be vulnerable
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub vulnerable: Vec<String>,
+    /// The listed versions again, in the same order, each with its details
+    pub releases: Vec<ReleaseReport>,
+}
+
+/// A single version of a project, as listed by --versions
+#[derive(Debug, Serialize)]
+pub struct ReleaseReport {
+    pub version: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub yanked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
+    /// Whether the version is known to be vulnerable
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub vulnerable: bool,
+    /// When the first file of the version was uploaded, only with --time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_time: Option<String>,
+}
+
+/// A time as RFC 3339 in UTC, which machine formats always use whatever times are displayed as
+fn report_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// The time artifact was uploaded, or as the index gave it if it cannot be understood
+fn report_upload_time(artifact: &DistributionUrl) -> String {
+    artifact
+        .uploaded()
+        .map(|time| report_time(&time))
+        .unwrap_or_else(|| artifact.upload_time.clone())
+}
+
+/// Report on artifact with the fields that the details level, as given by --artifacts, shows
+pub(crate) fn report_artifact(artifact: &DistributionUrl, details: u8) -> ArtifactReport {
+    ArtifactReport {
+        packagetype: artifact.packagetype.clone(),
+        filename: artifact.filename.clone(),
+        tag: artifact
+            .filename()
+            .ok()
+            .map(|f| f.compatibility_tag.to_string()),
+        url: (details >= 3).then(|| artifact.url.clone()),
+        upload_time: (details >= 4).then(|| report_upload_time(artifact)),
+        sha256: artifact.digests.sha256.clone().filter(|_| details >= 5),
+    }
+}
+
+fn report_package_version_details(
+    project: &mut Project,
+    display_fields: &DisplayFields,
+) -> Result<Report> {
+    let mut report = Report::default();
+
+    if display_fields.name {
+        let version = project.version()?;
+        report.name = Some(version.name.clone());
+        report.version = Some(version.version.clone());
+        report.yanked = version.yanked;
+        report.yanked_reason = version.yanked_reason.clone();
+        if project.distribution_selector().is_some() {
+            let distribution = project.distribution()?;
+            report.distribution = Some(
+                distribution
+                    .filename()
+                    .map(|f| f.compatibility_tag.to_string())
+                    .unwrap_or_else(|_| distribution.packagetype.clone()),
+            );
+        }
+    }
+
+    if display_fields.time {
+        report.time = if project.distribution_selector().is_some() {
+            Some(report_upload_time(project.distribution()?))
+        } else {
+            earliest_upload_time(project.version()?.urls.iter()).map(|time| report_time(&time))
+        };
+    }
+
+    if display_fields.license {
+        let version = project.version()?;
+        report.license = version.license.clone();
+        report.copyright = version.author_email.as_ref().map(|a| a.replace('"', ""));
+    }
+
+    if display_fields.people {
+        report.people = Some(
+            project
+                .version()?
+                .people()
+                .into_iter()
+                .map(|person| PersonReport {
+                    roles: person
+                        .roles
+                        .iter()
+                        .map(|r| r.to_string().to_lowercase())
+                        .collect(),
+                    name: person.name,
+                    email: person.email,
+                })
+                .collect(),
+        );
+    }
+
+    if display_fields.summary {
+        report.summary = project.version()?.summary.clone();
+    }
+
+    if display_fields.downloads {
+        report.downloads = project.downloads().map(|downloads| DownloadsReport {
+            last_day: downloads.last_day,
+            last_week: downloads.last_week,
+            last_month: downloads.last_month,
+        });
+    }
+
+    if display_fields.urls {
+        report.urls = Some(
+            list_urls(project.version()?)
+                .into_iter()
+                .map(|(label, url)| UrlReport { label, url })
+                .collect(),
+        );
+    }
+
+    if display_fields.keywords {
+        report.keywords = Some(project.version()?.keywords());
+    }
+
+    if display_fields.classifiers {
+        let version = project.version()?;
+        report.classifiers = Some(
+            version
+                .classifiers()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        );
+        // classifiers too new for trove-classifiers are kept apart, as they were given
+        let unrecognized = version.unrecognized_classifiers();
+        if !unrecognized.is_empty() {
+            report.unrecognized_classifiers =
+                Some(unrecognized.into_iter().map(String::from).collect());
+        }
+    }
+
+    if display_fields.artifacts >= 1 {
+        let artifacts = if project.distribution_selector().is_some() {
+            vec![report_artifact(
+                project.distribution()?,
+                display_fields.artifacts,
+            )]
+        } else {
+            project
+                .version()?
+                .urls
+                .iter()
+                .map(|u| report_artifact(u, display_fields.artifacts))
+                .collect()
+        };
+        report.artifacts = Some(artifacts);
+    }
+
+    if display_fields.dependencies {
+        let version = project.version()?;
+        let groups = group_dependencies(version, display_fields.extras.as_deref());
+        report.dependencies = Some(DependenciesReport {
+            requires_python: version.requires_python.clone(),
+            python_specifiers: version.python_specifiers().map(|specifiers| {
+                specifiers
+                    .0
+                    .iter()
+                    .map(|s| SpecifierReport {
+                        operator: s.operator.to_string(),
+                        version: s.version_pattern(),
+                    })
+                    .collect()
+            }),
+            requires_dist: groups.base,
+            extras: groups
+                .extras
+                .into_iter()
+                .map(|(name, requires_dist)| ExtraReport {
+                    name,
+                    requires_dist,
+                })
+                .collect(),
+            other_extras: groups.hidden,
+        });
+    }
+
+    if display_fields.packages >= 1 {
+        let mut packages = project
+            .import_package()?
+            .provides_import_kinds()
+            .into_iter()
+            .collect::<Vec<_>>();
+        packages.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        report.packages = Some(packages.iter().map(|(p, _)| p.to_string()).collect());
+        if display_fields.packages > 1 {
+            report.package_kinds = Some(
+                packages
+                    .into_iter()
+                    .map(|(name, kind)| PackageKindReport {
+                        name,
+                        kind: kind.to_string(),
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    if display_fields.executables {
+        let package = project.import_package()?;
+        report.executables = Some(package.executables());
+    }
+
+    if display_fields.entry_points {
+        report.entry_points = Some(
+            project
+                .import_package()?
+                .entry_point_groups()
+                .into_iter()
+                .map(|(group, entry_points)| EntryPointGroupReport {
+                    group,
+                    entry_points: entry_points
+                        .into_iter()
+                        .map(|(name, object)| EntryPointReport { name, object })
+                        .collect(),
+                })
+                .collect(),
+        );
+    }
+
+    if display_fields.readme >= 1 {
+        report.readme = project.version()?.description.clone();
+    }
+
+    Ok(report)
+}
+
+fn report_package_versions(
+    project: &mut Project,
+    display_fields: &DisplayFields,
+) -> Result<VersionsReport> {
+    let package = project.package()?;
+    let versions = list_versions(package, display_fields);
+    let releases = package.ordered_releases();
+    let yanked_reason = |version: &Version| {
+        let (_, urls) = releases[releases.binary_search_by(|(v, _)| v.cmp(version)).ok()?];
+        urls.iter()
+            .find_map(|u| u.yanked_reason.clone().filter(|r| !r.is_empty()))
+    };
+    Ok(VersionsReport {
+        name: display_fields.name.then(|| package.name.clone()),
+        versions: versions.iter().map(|(v, _, _)| v.normalize()).collect(),
+        yanked: versions
+            .iter()
+            .filter(|(_, _, yanked)| *yanked)
+            .map(|(v, _, _)| v.normalize())
+            .collect(),
+        vulnerable: versions
+            .iter()
+            .filter(|(v, _, _)| package.is_vulnerable(v))
+            .map(|(v, _, _)| v.normalize())
+            .collect(),
+        releases: versions
+            .iter()
+            .map(|(v, time, yanked)| ReleaseReport {
+                version: v.normalize(),
+                yanked: *yanked,
+                yanked_reason: yanked.then(|| yanked_reason(v)).flatten(),
+                vulnerable: package.is_vulnerable(v),
+                upload_time: time
+                    .filter(|_| display_fields.time)
+                    .map(|time| report_time(&time)),
+            })
+            .collect(),
+    })
+}
+
+/// The keys of a Report that hold a section's details
+fn field_keys(field: Field) -> &'static [&'static str] {
+    match field {
+        Field::Name => &["name", "version", "yanked", "yanked_reason", "distribution"],
+        Field::Time => &["time"],
+        Field::License => &["license", "copyright"],
+        Field::People => &["people"],
+        Field::Summary => &["summary"],
+        Field::Downloads => &["downloads"],
+        Field::Urls => &["urls"],
+        Field::Keywords => &["keywords"],
+        Field::Classifiers => &["classifiers", "unrecognized_classifiers"],
+        Field::Artifacts => &["artifacts"],
+        Field::Dependencies => &["dependencies"],
+        Field::Packages => &["packages", "package_kinds"],
+        Field::Executables => &["executables"],
+        Field::EntryPoints => &["entry_points"],
+        Field::Readme => &["readme"],
+    }
+}
+
+/// Collect the project details selected by display_fields into a serializable value
+pub fn report(project: &mut Project, display_fields: &DisplayFields) -> Result<Value> {
+    if display_fields.versions {
+        return Ok(serde_json::to_value(report_package_versions(
+            project,
+            display_fields,
+        )?)?);
+    }
+    let mut report =
+        serde_json::to_value(report_package_version_details(project, display_fields)?)?;
+    if let (Some(order), Value::Object(details)) = (&display_fields.order, &mut report) {
+        let mut ordered = serde_json::Map::new();
+        for key in order.iter().flat_map(|f| field_keys(*f)) {
+            if let Some(value) = details.remove(*key) {
+                ordered.insert(key.to_string(), value);
+            }
+        }
+        ordered.append(details);
+        *details = ordered;
+    }
+    Ok(report)
+}
+
+/// Write one report per project as JSON Lines
+///
+/// A project that fails is written as its name and the error, and does not stop the rest
+pub fn display_lines(
+    projects: Vec<Project>,
+    display_fields: DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    for mut project in projects {
+        let report = report(&mut project, &display_fields).unwrap_or_else(|err| {
+            serde_json::json!({
+                "name": project.package_selector(),
+                "error": format!("{err:#}"),
+            })
+        });
+        writeln!(out, "{}", serde_json::to_string(&report)?)?;
+    }
+    Ok(())
+}
+
+pub fn display(
+    mut project: Project,
+    display_fields: DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report = report(&mut project, &display_fields)?;
+    writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+    Ok(())
+}
+
+/// A file an inspected wheel installs, by where it is installed
+#[derive(Debug, Serialize)]
+pub struct InstalledFileReport {
+    pub location: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<usize>,
+}
+
+/// Every file an inspected wheel installs
+#[derive(Debug, Serialize)]
+pub struct FilesReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub files: Vec<InstalledFileReport>,
+}
+
+pub fn report_files(
+    package: &package_inspect::Package,
+    display_fields: &DisplayFields,
+) -> FilesReport {
+    FilesReport {
+        name: display_fields.name.then(|| package.name().to_string()),
+        version: display_fields.name.then(|| package.version().to_string()),
+        files: package
+            .installed_files()
+            .into_iter()
+            .flat_map(|(scheme, files)| {
+                files
+                    .into_iter()
+                    .map(move |(path, size)| InstalledFileReport {
+                        location: scheme.to_string(),
+                        path,
+                        size,
+                    })
+            })
+            .collect(),
+    }
+}
+
+pub fn display_files(
+    package: &package_inspect::Package,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report = report_files(package, display_fields);
+    writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+    Ok(())
+}
+
+/// How large an inspected wheel is to download, when known, and once installed
+#[derive(Debug, Serialize)]
+pub struct SizeReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_size: Option<usize>,
+    pub installed_size: usize,
+    pub installed_files: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub largest_files: Vec<InstalledFileReport>,
+}
+
+/// Report the size of package, listing up to largest of its files biggest first
+pub fn report_size(
+    package: &package_inspect::Package,
+    download_size: Option<usize>,
+    largest: usize,
+    display_fields: &DisplayFields,
+) -> SizeReport {
+    let installed_files = package
+        .installed_files()
+        .iter()
+        .map(|(_, files)| files.len())
+        .sum();
+    SizeReport {
+        name: display_fields.name.then(|| package.name().to_string()),
+        version: display_fields.name.then(|| package.version().to_string()),
+        download_size,
+        installed_size: package.installed_size(),
+        installed_files,
+        largest_files: largest_files(package, largest)
+            .into_iter()
+            .map(|(size, scheme, path)| InstalledFileReport {
+                location: scheme.to_string(),
+                path,
+                size: Some(size),
+            })
+            .collect(),
+    }
+}
+
+pub fn display_size_report(
+    package: &package_inspect::Package,
+    download_size: Option<usize>,
+    largest: usize,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report = report_size(package, download_size, largest, display_fields);
+    writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+    Ok(())
+}
+
+fn string_list() -> Value {
+    json!({"type": "array", "items": {"type": "string"}})
+}
+
+/// A JSON Schema describing the output of the json and yaml formats
+///
+/// Each property mirrors a field of [`Report`] or [`VersionsReport`]; a property is only required
+/// when that field is always serialized.
+pub fn schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "kayak report",
+        "oneOf": [
+            {"$ref": "#/$defs/report"},
+            {"$ref": "#/$defs/versions"},
+            {"$ref": "#/$defs/error"},
+            {"$ref": "#/$defs/files"},
+            {"$ref": "#/$defs/size_report"},
+        ],
+        "$defs": {
+            "report": {
+                "description": "The details of one project version",
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "version": {"type": "string"},
+                    "yanked": {"const": true},
+                    "yanked_reason": {"type": "string"},
+                    "distribution": {
+                        "description": "The selected distribution's compatibility tag, or sdist",
+                        "type": "string",
+                    },
+                    "time": {"type": "string"},
+                    "license": {"type": "string"},
+                    "copyright": {"type": "string"},
+                    "people": {"type": "array", "items": {"$ref": "#/$defs/person"}},
+                    "summary": {"type": "string"},
+                    "downloads": {"$ref": "#/$defs/downloads"},
+                    "urls": {"type": "array", "items": {"$ref": "#/$defs/url"}},
+                    "keywords": string_list(),
+                    "classifiers": string_list(),
+                    "unrecognized_classifiers": string_list(),
+                    "artifacts": {"type": "array", "items": {"$ref": "#/$defs/artifact"}},
+                    "dependencies": {"$ref": "#/$defs/dependencies"},
+                    "packages": string_list(),
+                    "package_kinds": {"type": "array", "items": {"$ref": "#/$defs/package_kind"}},
+                    "executables": string_list(),
+                    "entry_points": {
+                        "type": "array",
+                        "items": {"$ref": "#/$defs/entry_point_group"},
+                    },
+                    "readme": {"type": "string"},
+                },
+                "additionalProperties": false,
+            },
+            "person": {
+                "type": "object",
+                "properties": {
+                    "roles": string_list(),
+                    "name": {"type": "string"},
+                    "email": {"type": "string"},
+                },
+                "required": ["roles"],
+                "additionalProperties": false,
+            },
+            "downloads": {
+                "description": "How many times the project was downloaded recently",
+                "type": "object",
+                "properties": {
+                    "last_day": {"type": "integer"},
+                    "last_week": {"type": "integer"},
+                    "last_month": {"type": "integer"},
+                },
+                "required": ["last_day", "last_week", "last_month"],
+                "additionalProperties": false,
+            },
+            "url": {
+                "type": "object",
+                "properties": {
+                    "label": {"type": "string"},
+                    "url": {"type": "string"},
+                },
+                "required": ["label", "url"],
+                "additionalProperties": false,
+            },
+            "artifact": {
+                "description": "A distribution artifact; optional fields appear at higher detail levels",
+                "type": "object",
+                "properties": {
+                    "packagetype": {"type": "string"},
+                    "filename": {"type": "string"},
+                    "tag": {"type": "string"},
+                    "url": {"type": "string"},
+                    "upload_time": {"type": "string"},
+                    "sha256": {"type": "string"},
+                },
+                "required": ["packagetype", "filename"],
+                "additionalProperties": false,
+            },
+            "package_kind": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "kind": {"type": "string"},
+                },
+                "required": ["name", "kind"],
+                "additionalProperties": false,
+            },
+            "entry_point_group": {
+                "type": "object",
+                "properties": {
+                    "group": {"type": "string"},
+                    "entry_points": {"type": "array", "items": {"$ref": "#/$defs/entry_point"}},
+                },
+                "required": ["group", "entry_points"],
+                "additionalProperties": false,
+            },
+            "entry_point": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "object": {"type": "string"},
+                },
+                "required": ["name", "object"],
+                "additionalProperties": false,
+            },
+            "dependencies": {
+                "type": "object",
+                "properties": {
+                    "requires_python": {"type": "string"},
+                    "python_specifiers": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "operator": {"type": "string"},
+                                "version": {"type": "string"},
+                            },
+                            "required": ["operator", "version"],
+                            "additionalProperties": false,
+                        },
+                    },
+                    "requires_dist": string_list(),
+                    "extras": {"type": "array", "items": {"$ref": "#/$defs/extra"}},
+                    "other_extras": string_list(),
+                },
+                "required": ["requires_dist", "extras"],
+                "additionalProperties": false,
+            },
+            "extra": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "requires_dist": string_list(),
+                },
+                "required": ["name", "requires_dist"],
+                "additionalProperties": false,
+            },
+            "versions": {
+                "description": "All versions of a project, newest first",
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "versions": string_list(),
+                    "yanked": string_list(),
+                    "vulnerable": string_list(),
+                    "releases": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "version": {"type": "string"},
+                                "yanked": {"const": true},
+                                "yanked_reason": {"type": "string"},
+                                "vulnerable": {"const": true},
+                                "upload_time": {"type": "string"},
+                            },
+                            "required": ["version"],
+                            "additionalProperties": false,
+                        },
+                    },
+                },
+                "required": ["versions", "releases"],
+                "additionalProperties": false,
+            },
+            "error": {
+                "description": "A project that could not be reported on, only written by --projects-file",
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "error": {"type": "string"},
+                },
+                "required": ["name", "error"],
+                "additionalProperties": false,
+            },
+            "files": {
+                "description": "Every file a wheel installs, only written by --files",
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "version": {"type": "string"},
+                    "files": {
+                        "type": "array",
+                        "items": {"$ref": "#/$defs/installed_file"},
+                    },
+                },
+                "required": ["files"],
+                "additionalProperties": false,
+            },
+            "size_report": {
+                "description": "How large a wheel is, only written by --size-report",
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "version": {"type": "string"},
+                    "download_size": {"type": "integer"},
+                    "installed_size": {"type": "integer"},
+                    "installed_files": {"type": "integer"},
+                    "largest_files": {
+                        "type": "array",
+                        "items": {"$ref": "#/$defs/installed_file"},
+                    },
+                },
+                "required": ["installed_size", "installed_files"],
+                "additionalProperties": false,
+            },
+            "installed_file": {
+                "type": "object",
+                "properties": {
+                    "location": {"enum": ["site-packages", "scripts", "headers", "data"]},
+                    "path": {"type": "string"},
+                    "size": {"type": "integer"},
+                },
+                "required": ["location", "path"],
+                "additionalProperties": false,
+            },
+        },
+    })
+}
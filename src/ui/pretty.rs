@@ -1,76 +1,164 @@
+use crate::distribution::same_package_name;
 use crate::ui::*;
-use crate::warehouse::{DistributionUrl, PackageVersion};
-use crate::{DisplayFields, Project};
+use crate::warehouse::DistributionUrl;
+use crate::{DisplayFields, Field, Project};
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use ratatui::backend::TestBackend;
 use ratatui::layout::*;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use ratatui::TerminalOptions;
 use ratatui::Viewport;
-use std::io::stdout;
+use std::io::{self, IsTerminal, Write};
 use std::iter;
 
+/// The space left between the columns of a list
+const COLUMN_GAP: usize = 2;
+
+/// Arrange items top to bottom into as many equal columns as fit within a box width wide
+fn into_columns(items: Vec<Line<'_>>, width: u16) -> Vec<Line<'_>> {
+    let inner = usize::from(width.saturating_sub(2)); // minus box
+    let widest = items.iter().map(Line::width).max().unwrap_or(0);
+    let columns = ((inner + COLUMN_GAP) / (widest + COLUMN_GAP)).clamp(1, items.len().max(1));
+    if columns == 1 {
+        return items;
+    }
+    let rows = items.len().div_ceil(columns);
+    (0..rows)
+        .map(|r| {
+            let mut spans = Vec::new();
+            for (c, item) in items.iter().skip(r).step_by(rows).enumerate() {
+                if c > 0 {
+                    let previous = &items[r + (c - 1) * rows];
+                    spans.push(Span::raw(
+                        " ".repeat(widest - previous.width() + COLUMN_GAP),
+                    ));
+                }
+                spans.extend(item.spans.iter().cloned());
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
 fn render_name_versions<'a>(
     display_fields: &DisplayFields,
     project: &mut Project,
 ) -> Result<Option<(Constraint, Paragraph<'a>)>> {
     let package = project.package()?;
-    let mut versions = package
-        .ordered_versions()
-        .iter()
-        .map(|v| v.normalize())
-        .collect::<Vec<_>>();
-    versions.reverse();
+    let versions = list_versions(package, display_fields);
+    let mut lines = if versions.is_empty() {
+        let empty = if package.has_releases() {
+            "no versions"
+        } else {
+            "no releases"
+        };
+        vec![Line::from(Span::styled(empty, Style::new().italic()))]
+    } else if display_fields.time {
+        versions
+            .iter()
+            .map(|(v, time, yanked)| {
+                let mut line = vec![Span::raw(v.normalize())];
+                if *yanked {
+                    line.push(Span::styled(" [YANKED]", Style::new().red()));
+                }
+                if package.is_vulnerable(v) {
+                    line.push(Span::styled(" [VULNERABLE]", Style::new().red().bold()));
+                }
+                if let Some(time) = time {
+                    line.push("  ".into());
+                    line.push(Span::raw(format_time(time, display_fields)));
+                }
+                Line::from(line)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        let mut line = Vec::new();
+        for (i, (v, _, yanked)) in versions.iter().enumerate() {
+            if i > 0 {
+                line.push(Span::raw(", "));
+            }
+            line.push(Span::raw(v.normalize()));
+            if *yanked {
+                line.push(Span::styled(" [YANKED]", Style::new().red()));
+            }
+            if package.is_vulnerable(v) {
+                line.push(Span::styled(" [VULNERABLE]", Style::new().red().bold()));
+            }
+        }
+        vec![Line::from(line)]
+    };
 
     if display_fields.name {
-        Ok(Some((
-            Constraint::Min(2),
-            Paragraph::new(vec![
-                Line::from(Span::styled(
-                    package.name.to_string(),
-                    Style::new().bold().reversed(),
-                ))
-                .centered(),
-                Line::from(versions.join(", ")),
-            ])
-            .wrap(Wrap { trim: false }),
-        )))
-    } else {
-        Ok(Some((
-            Constraint::Min(1),
-            Paragraph::new(Line::from(versions.join(", "))).wrap(Wrap { trim: false }),
-        )))
+        lines.insert(
+            0,
+            Line::from(Span::styled(
+                package.name.to_string(),
+                Style::new().bold().reversed(),
+            ))
+            .centered(),
+        );
     }
+    Ok(Some((
+        Constraint::Min(lines.len().try_into().unwrap_or(u16::MAX)),
+        Paragraph::new(lines).wrap(Wrap { trim: false }),
+    )))
 }
 
 fn render_name_version<'a>(
     display_fields: &DisplayFields,
     project: &mut Project,
+    width: u16,
 ) -> Result<Option<(Constraint, Paragraph<'a>)>> {
     if !display_fields.name {
         return Ok(None);
     }
+    let selector = project.package_selector();
     let version = project.version()?;
+    // the same check as Project::resolved_name, which cannot be asked while version is borrowed
+    let resolved_from = (!same_package_name(&version.name, &selector)).then_some(selector);
     let name = Line::from(Span::styled(
         version.name.to_string(),
         Style::new().bold().reversed(),
     ));
-    let ver = if let Some(_reason) = &version.yanked_reason {
-        Line::from(Span::styled(
-            format!("{} [YANKED]", version.version),
-            Style::new().bold().white().on_red(),
-        ))
+    let mut lines = if version.yanked {
+        vec![
+            name,
+            Line::from(Span::styled(
+                format!("{} [YANKED]", version.version),
+                Style::new().bold().white().on_red(),
+            )),
+        ]
     } else {
-        Line::from(Span::styled(
-            version.version.to_string(),
-            Style::new().bold().reversed(),
-        ))
+        vec![
+            name,
+            Line::from(Span::styled(
+                version.version.to_string(),
+                Style::new().bold().reversed(),
+            )),
+        ]
     };
+    // the reason is wrapped, so estimate how many lines it will take
+    let mut height = 2;
+    if let Some(selector) = resolved_from {
+        lines.push(Line::from(Span::styled(
+            format!("resolved from `{selector}`"),
+            Style::new().italic().dim(),
+        )));
+        height += 1;
+    }
+    if let Some(reason) = version.yanked_reason.as_ref().filter(|r| !r.is_empty()) {
+        let reason = Line::from(Span::styled(
+            reason.to_string(),
+            Style::new().red().italic(),
+        ));
+        height += reason.width().div_ceil(usize::from(width.max(1)));
+        lines.push(reason);
+    }
 
     Ok(Some((
-        Constraint::Length(2),
-        Paragraph::new(vec![name, ver]).centered(),
+        Constraint::Length(height.try_into().unwrap_or(u16::MAX)),
+        Paragraph::new(lines).centered().wrap(Wrap { trim: true }),
     )))
 }
 
@@ -82,7 +170,15 @@ fn render_distribution<'a>(
         Ok(None)
     } else {
         let dist = if let Ok(d) = project.distribution()?.filename() {
-            d.compatibility_tag.to_string()
+            if display_fields.describe_tags {
+                format!(
+                    "{} ({})",
+                    d.compatibility_tag,
+                    d.compatibility_tag.describe()
+                )
+            } else {
+                d.compatibility_tag.to_string()
+            }
         } else {
             "sdist".to_string()
         };
@@ -107,7 +203,7 @@ fn render_time<'a>(
         Ok(Some((
             Constraint::Length(1),
             Paragraph::new(Line::from(Span::styled(
-                project.distribution()?.upload_time.clone(),
+                format_upload_time(project.distribution()?, display_fields),
                 Style::new().bold().reversed(),
             )))
             .centered(),
@@ -115,17 +211,11 @@ fn render_time<'a>(
     } else {
         // cannot use project.distribution() as this should report the earliest upload time, not
         // the time of the best-fit distribution
-        if let Some(time) = project
-            .version()?
-            .urls
-            .iter()
-            .filter_map(|u| u.upload_time_iso_8601.parse::<DateTime<Utc>>().ok())
-            .min()
-        {
+        if let Some(time) = earliest_upload_time(project.version()?.urls.iter()) {
             Ok(Some((
                 Constraint::Length(1),
                 Paragraph::new(Line::from(Span::styled(
-                    time.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                    format_time(&time, display_fields),
                     Style::new().bold().reversed(),
                 )))
                 .centered(),
@@ -172,6 +262,35 @@ fn render_license_copyright<'a>(
     }
 }
 
+fn render_people<'a>(
+    display_fields: &DisplayFields,
+    project: &mut Project,
+) -> Result<Option<(Constraint, Paragraph<'a>)>> {
+    if !display_fields.people {
+        return Ok(None);
+    }
+    let people = project.version()?.people();
+    if people.is_empty() {
+        return Ok(None);
+    }
+    let size = people.len() + 2; // plus box
+    Ok(Some((
+        Constraint::Length(size.try_into().unwrap_or(u16::MAX)),
+        Paragraph::new(
+            people
+                .iter()
+                .map(|person| {
+                    Line::from(vec![
+                        Span::styled(format!("{}: ", person.roles()), Style::new().bold()),
+                        person.to_string().into(),
+                    ])
+                })
+                .collect::<Vec<_>>(),
+        )
+        .block(Block::default().title("People").borders(Borders::ALL)),
+    )))
+}
+
 fn render_summary<'a>(
     display_fields: &DisplayFields,
     project: &mut Project,
@@ -190,6 +309,25 @@ fn render_summary<'a>(
     }
 }
 
+fn render_downloads<'a>(
+    display_fields: &DisplayFields,
+    project: &mut Project,
+) -> Result<Option<(Constraint, Paragraph<'a>)>> {
+    if !display_fields.downloads {
+        return Ok(None);
+    }
+    Ok(project.downloads().map(|downloads| {
+        (
+            Constraint::Length(3),
+            Paragraph::new(Line::from(format_downloads(downloads))).block(
+                Block::default()
+                    .title("Recent Downloads")
+                    .borders(Borders::ALL),
+            ),
+        )
+    }))
+}
+
 fn render_urls<'a>(
     display_fields: &DisplayFields,
     project: &mut Project,
@@ -197,21 +335,17 @@ fn render_urls<'a>(
     if !display_fields.urls {
         return Ok(None);
     }
-    let version: &PackageVersion = project.version()?;
-    let size = version.project_urls.len() + 3; // plus project URL plus box
+    let urls = list_urls(project.version()?);
+    let size = urls.len() + 2; // plus box
     Ok(Some((
-        Constraint::Max(size.try_into().unwrap()),
+        Constraint::Max(size.try_into().unwrap_or(u16::MAX)),
         Paragraph::new(
-            iter::once((&"Package Index".to_string(), &version.project_url))
-                .chain(version.project_urls.iter())
-                .map(|url| {
+            urls.into_iter()
+                .map(|(label, url)| {
                     Line::from(vec![
-                        iconify_url(url).into(),
+                        iconify_url(&label, display_fields.emoji).into(),
                         "  ".into(),
-                        Span::styled(
-                            url.1.to_string(),
-                            Style::new().blue().add_modifier(Modifier::UNDERLINED),
-                        ),
+                        Span::styled(url, Style::new().blue().add_modifier(Modifier::UNDERLINED)),
                     ])
                 })
                 .collect::<Vec<_>>(),
@@ -242,22 +376,34 @@ fn render_keywords<'a>(
 fn render_classifiers<'a>(
     display_fields: &DisplayFields,
     project: &mut Project,
+    width: u16,
 ) -> Result<Option<(Constraint, Paragraph<'a>)>> {
     if !display_fields.classifiers {
         return Ok(None);
     }
-    let classifiers = project.version()?.classifiers();
-    if !classifiers.is_empty() {
+    let version = project.version()?;
+    let classifiers = version.classifiers();
+    // classifiers too new for trove-classifiers are still shown, as they were given
+    let unrecognized = version.unrecognized_classifiers();
+    if !classifiers.is_empty() || !unrecognized.is_empty() {
+        let classifiers = into_columns(
+            classifiers
+                .iter()
+                .map(|c| Line::from(c.to_string()))
+                .chain(unrecognized.into_iter().map(|c| {
+                    Line::styled(
+                        format!("{c} (unrecognized)"),
+                        Style::new().add_modifier(Modifier::DIM),
+                    )
+                }))
+                .collect(),
+            width,
+        );
         let size = classifiers.len() + 2;
         Ok(Some((
-            Constraint::Max(size.try_into().unwrap()),
-            Paragraph::new(
-                classifiers
-                    .iter()
-                    .map(|c| Line::from(c.to_string()))
-                    .collect::<Vec<_>>(),
-            )
-            .block(Block::default().title("Classifiers").borders(Borders::ALL)),
+            Constraint::Max(size.try_into().unwrap_or(u16::MAX)),
+            Paragraph::new(classifiers)
+                .block(Block::default().title("Classifiers").borders(Borders::ALL)),
         )))
     } else {
         Ok(None)
@@ -293,37 +439,52 @@ fn render_artifacts<'a>(
         }
     } else {
         let lines = artifacts
-            .filter_map(|artifact| {
-                let tag = if let Ok(dist) = artifact.filename() {
-                    Span::raw(dist.compatibility_tag.to_string())
-                } else if artifact.packagetype == "sdist" {
-                    Span::raw(String::from("sdist"))
-                } else {
-                    return None;
+            .map(|artifact| {
+                let tag = match artifact.filename() {
+                    Ok(dist) if artifact.packagetype == "bdist_wheel" => {
+                        Span::raw(dist.compatibility_tag.to_string())
+                    }
+                    _ if artifact.packagetype == "sdist" => {
+                        Span::raw(sdist_label(&artifact.filename))
+                    }
+                    _ => Span::raw(format!("{} {}", artifact.packagetype, artifact.filename)),
                 };
 
-                if display_fields.artifacts > 3 {
-                    Some(Line::from(vec![
+                if display_fields.artifacts > 4 {
+                    Line::from(vec![
+                        tag,
+                        " ".into(),
+                        format_upload_time(artifact, display_fields).into(),
+                        " ".into(),
+                        Span::styled(
+                            artifact.url.clone(),
+                            Style::new().blue().add_modifier(Modifier::UNDERLINED),
+                        ),
+                        " ".into(),
+                        Span::raw(format_sha256(artifact)),
+                    ])
+                } else if display_fields.artifacts == 4 {
+                    Line::from(vec![
                         tag,
                         " ".into(),
-                        artifact.upload_time.clone().into(),
+                        format_upload_time(artifact, display_fields).into(),
                         " ".into(),
                         Span::styled(
                             artifact.url.clone(),
                             Style::new().blue().add_modifier(Modifier::UNDERLINED),
                         ),
-                    ]))
+                    ])
                 } else if display_fields.artifacts == 3 {
-                    Some(Line::from(vec![
+                    Line::from(vec![
                         tag,
                         " ".into(),
                         Span::styled(
                             artifact.url.clone(),
                             Style::new().blue().add_modifier(Modifier::UNDERLINED),
                         ),
-                    ]))
+                    ])
                 } else {
-                    Some(Line::from(tag))
+                    Line::from(tag)
                 }
             })
             .collect::<Vec<_>>();
@@ -332,7 +493,7 @@ fn render_artifacts<'a>(
                 // TODO: *2 and trim:false allows long url to wrap to the next line, but leaves
                 // excess space when not needed
                 //Constraint::Max((lines.len() * 2 + 2).try_into().unwrap()),
-                Constraint::Max((lines.len() + 2).try_into().unwrap()),
+                Constraint::Max((lines.len() + 2).try_into().unwrap_or(u16::MAX)),
                 Paragraph::new(lines)
                     .block(Block::default().title("Downloads").borders(Borders::ALL)), //.wrap(Wrap { trim: false }),
             ));
@@ -345,28 +506,46 @@ fn render_artifacts<'a>(
 fn render_dependencies<'a>(
     display_fields: &DisplayFields,
     project: &mut Project,
+    width: u16,
 ) -> Result<Option<(Constraint, Paragraph<'a>)>> {
     if !display_fields.dependencies {
         return Ok(None);
     }
-    let dependencies = project
-        .version()?
-        .requires_python
-        .clone()
-        .into_iter()
-        .map(|p| format!("python{p}"))
-        .chain(
-            project
-                .version()?
-                .requires_dist
-                .iter()
-                .map(|d| d.to_string()),
-        )
-        .map(Line::from)
-        .collect::<Vec<_>>();
+    let version = project.version()?;
+    let groups = group_dependencies(version, display_fields.extras.as_deref());
+    // each extra's dependencies are arranged into columns under their own header
+    let mut dependencies = into_columns(
+        version
+            .requires_python_canonical()
+            .into_iter()
+            .map(|p| Line::from(format!("python{p}")))
+            .chain(groups.base.into_iter().map(Line::from))
+            .collect(),
+        width,
+    );
+    for (extra, requirements) in groups.extras {
+        dependencies.push(Line::from(Span::styled(
+            format!("[{extra}]"),
+            Style::new().bold(),
+        )));
+        dependencies.extend(into_columns(
+            requirements
+                .into_iter()
+                .map(|d| Line::from(format!("  {d}")))
+                .collect(),
+            width,
+        ));
+    }
+    // this is usually much wider than any dependency, so it is kept out of the columns
+    if !groups.hidden.is_empty() {
+        dependencies.push(Line::from(Span::styled(
+            format!("other extras: {}", groups.hidden.join(", ")),
+            Style::new().italic(),
+        )));
+    }
     if !dependencies.is_empty() {
         Ok(Some((
-            Constraint::Max(dependencies.len().try_into().unwrap()),
+            Constraint::Max((dependencies.len() + 2).try_into().unwrap_or(u16::MAX)), // plus box
             Paragraph::new(dependencies)
                 .block(Block::default().title("Dependencies").borders(Borders::ALL))
                 .wrap(Wrap { trim: false }),
@@ -380,17 +559,26 @@ fn render_packages<'a>(
     display_fields: &DisplayFields,
     project: &mut Project,
 ) -> Result<Option<(Constraint, Paragraph<'a>)>> {
-    if !display_fields.packages {
+    if display_fields.packages == 0 {
         return Ok(None);
     }
     let mut packages = project
         .import_package()?
-        .provides_packages()
-        .iter()
-        .map(|p| p.to_string())
+        .provides_import_kinds()
+        .into_iter()
         .collect::<Vec<_>>();
     if !packages.is_empty() {
-        packages.sort_unstable();
+        packages.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        let packages = packages
+            .into_iter()
+            .map(|(p, kind)| {
+                if display_fields.packages > 1 {
+                    format!("{p} ({kind})")
+                } else {
+                    p
+                }
+            })
+            .collect::<Vec<_>>();
         Ok(Some((
             Constraint::Length(3),
             Paragraph::new(Line::from(packages.join(", ")))
@@ -415,10 +603,9 @@ fn render_executables<'a>(
     }
     let package = project.import_package()?;
     let executables = package
-        .provides_executables()
-        .iter()
-        .chain(package.console_scripts().iter())
-        .map(|p| p.to_string())
+        .executable_kinds()
+        .into_iter()
+        .map(|(p, kind)| format!("{p} ({kind})"))
         .collect::<Vec<_>>();
     if !executables.is_empty() {
         Ok(Some((
@@ -436,6 +623,41 @@ fn render_executables<'a>(
     }
 }
 
+fn render_entry_points<'a>(
+    display_fields: &DisplayFields,
+    project: &mut Project,
+) -> Result<Option<(Constraint, Paragraph<'a>)>> {
+    if !display_fields.entry_points {
+        return Ok(None);
+    }
+    let entry_points = project
+        .import_package()?
+        .entry_point_groups()
+        .into_iter()
+        .flat_map(|(group, entry_points)| {
+            iter::once(Line::from(Span::styled(
+                format!("[{group}]"),
+                Style::new().bold(),
+            )))
+            .chain(
+                entry_points
+                    .into_iter()
+                    .map(|(name, object)| Line::from(format!("  {name} = {object}"))),
+            )
+        })
+        .collect::<Vec<_>>();
+    if !entry_points.is_empty() {
+        Ok(Some((
+            Constraint::Max((entry_points.len() + 2).try_into().unwrap_or(u16::MAX)), // plus box
+            Paragraph::new(entry_points)
+                .block(Block::default().title("Entry Points").borders(Borders::ALL))
+                .wrap(Wrap { trim: false }),
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
 fn render_readme<'a>(
     // TODO: cannot render md within ratatui as escape codes don't work
     display_fields: &DisplayFields,
@@ -444,10 +666,20 @@ fn render_readme<'a>(
     if display_fields.readme == 0 {
         return Ok(None);
     }
-    if let Some(readme) = &project.version()?.description {
+    let version = project.version()?;
+    if let Some(readme) = &version.description {
+        // markdown cannot be styled here, but rST adornments are far noisier than markdown's
+        let readme = match version.description_content_type() {
+            Some(Ok(content_type))
+                if display_fields.readme > 1 && content_type.essence_str() == "text/x-rst" =>
+            {
+                rst_to_markdown(readme)
+            }
+            _ => readme.to_string(),
+        };
         return Ok(Some((
             Constraint::Fill(1),
-            Paragraph::new(readme.to_string()).wrap(Wrap { trim: false }),
+            Paragraph::new(readme).wrap(Wrap { trim: false }),
         )));
     }
     Ok(None)
@@ -465,6 +697,10 @@ fn render_error(frame: &mut Frame, area: Rect, message: String) {
     );
 }
 
+/// Render one field of the project details, along with how much space it takes up
+type RenderField<'a> =
+    dyn Fn(&DisplayFields, &mut Project) -> Result<Option<(Constraint, Paragraph<'a>)>>;
+
 pub fn render(
     frame: &mut Frame,
     area: Rect,
@@ -484,29 +720,40 @@ pub fn render(
             Err(error) => return Err(error.to_string()),
         };
     } else {
-        for render_field in [
-            render_name_version,
-            render_distribution,
-            render_time,
-            render_license_copyright,
-            render_summary,
-            render_urls,
-            render_keywords,
-            render_classifiers,
-            render_artifacts,
-            render_dependencies,
-            render_packages,
-            render_executables,
-            render_readme,
-        ] {
-            match render_field(display_fields, project) {
-                Ok(Some((constraint, component))) => {
-                    constraints.push(constraint);
-                    components.push(component);
-                }
-                Ok(None) => (),
-                Err(error) => return Err(error.to_string()),
+        let width = area.width;
+        let name_version =
+            move |d: &DisplayFields, p: &mut Project| render_name_version(d, p, width);
+        let classifiers = move |d: &DisplayFields, p: &mut Project| render_classifiers(d, p, width);
+        let dependencies =
+            move |d: &DisplayFields, p: &mut Project| render_dependencies(d, p, width);
+        for field in display_fields.ordered() {
+            let render_fields: &[&RenderField] = match field {
+                Field::Name => &[&name_version, &render_distribution],
+                Field::Time => &[&render_time],
+                Field::License => &[&render_license_copyright],
+                Field::People => &[&render_people],
+                Field::Summary => &[&render_summary],
+                Field::Downloads => &[&render_downloads],
+                Field::Urls => &[&render_urls],
+                Field::Keywords => &[&render_keywords],
+                Field::Classifiers => &[&classifiers],
+                Field::Artifacts => &[&render_artifacts],
+                Field::Dependencies => &[&dependencies],
+                Field::Packages => &[&render_packages],
+                Field::Executables => &[&render_executables],
+                Field::EntryPoints => &[&render_entry_points],
+                Field::Readme => &[&render_readme],
             };
+            for render_field in render_fields {
+                match render_field(display_fields, project) {
+                    Ok(Some((constraint, component))) => {
+                        constraints.push(constraint);
+                        components.push(component);
+                    }
+                    Ok(None) => (),
+                    Err(error) => return Err(error.to_string()),
+                };
+            }
         }
     }
 
@@ -520,22 +767,137 @@ pub fn render(
     Ok(())
 }
 
-pub fn display(mut project: Project, display_fields: DisplayFields) -> Result<()> {
-    let backend = CrosstermBackend::new(stdout());
+/// How many rows an off-screen page starts with, doubled for as long as the details fill it
+const OFFSCREEN_HEIGHT: u16 = 256;
+
+/// Draw off-screen on a page width columns wide and tall enough that its last row is left blank
+///
+/// Details cut off at the bottom of the page always reach its last row, so the page grows until
+/// they do not. A ratatui buffer holds at most u16::MAX cells though, so details longer than that
+/// are still cut off, which is returned alongside the page.
+fn draw_offscreen(width: u16, mut draw: impl FnMut(&mut Frame)) -> io::Result<(Buffer, bool)> {
+    let tallest = u16::MAX / width.max(1);
+    let mut height = OFFSCREEN_HEIGHT.min(tallest);
+    loop {
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        terminal.draw(|frame| draw(frame))?;
+        let buffer = terminal.backend().buffer();
+        let last = buffer.area.bottom().saturating_sub(1);
+        let filled = (0..width).any(|x| buffer[(x, last)].symbol() != " ");
+        if !filled || height == tallest {
+            if filled {
+                crate::warnings::warn(format!(
+                    "the details were cut off after {height} rows, the most a page {width} \
+                     columns wide can hold"
+                ));
+            }
+            return Ok((buffer.clone(), filled));
+        }
+        height = height.saturating_mul(2).min(tallest);
+    }
+}
+
+/// Render project off-screen, width columns wide
+///
+/// The rows left blank below the details are dropped, so the buffer is only as tall as the
+/// details are.
+pub fn render_buffer(
+    project: &mut Project,
+    display_fields: &DisplayFields,
+    width: u16,
+) -> Result<Buffer, String> {
+    let mut rendered = Ok(());
+    let (mut buffer, _) = draw_offscreen(width, |frame| {
+        let area = frame.area();
+        rendered = render(frame, area, project, display_fields);
+    })
+    .map_err(|err| err.to_string())?;
+    rendered?;
+    let height = buffer.area.height;
+    let used = (0..height)
+        .rev()
+        .find(|&y| (0..width).any(|x| buffer[(x, y)].symbol() != " "))
+        .map_or(0, |y| y + 1);
+    buffer.resize(Rect::new(0, 0, width, used));
+    Ok(buffer)
+}
+
+fn draw(frame: &mut Frame, project: &mut Project, display_fields: &DisplayFields) {
+    let mut area = frame.area();
+    if let Some(width) = display_fields.width {
+        area.width = area.width.min(width);
+    }
+    match render(frame, area, project, display_fields) {
+        Ok(()) => (),
+        Err(err) => {
+            let area = Rect::new(4, 0, area.right().saturating_sub(8), 4);
+            render_error(frame, area, err)
+        }
+    }
+}
+
+/// The width of a page written when rendering without styles for something other than a terminal
+const PLAIN_WIDTH: u16 = 80;
+
+/// Render off-screen and write only the text of each line, dropping all styling
+fn display_plain(
+    mut project: Project,
+    display_fields: DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let width = if let Some(width) = display_fields.width {
+        width
+    } else if io::stdout().is_terminal() {
+        crossterm::terminal::size().map_or(PLAIN_WIDTH, |(w, _)| w)
+    } else {
+        // a pipe or a CI log gets the same page wherever it is run from
+        PLAIN_WIDTH
+    };
+    let (buffer, cut_off) =
+        draw_offscreen(width, |frame| draw(frame, &mut project, &display_fields))?;
+    let mut lines = Vec::new();
+    for y in 0..buffer.area.height {
+        let mut line = String::new();
+        let mut skip = 0;
+        for x in 0..buffer.area.width {
+            if skip > 0 {
+                // covered by the previous wide character
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer[(x, y)].symbol();
+            skip = Span::raw(symbol).width().saturating_sub(1);
+            line.push_str(symbol);
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    if cut_off {
+        lines.push("[cut off here; --format text shows every detail]".to_string());
+    }
+    if !lines.is_empty() {
+        writeln!(out, "{}", lines.join("\n"))?;
+    }
+    Ok(())
+}
+
+pub fn display(
+    mut project: Project,
+    display_fields: DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    if !display_fields.color {
+        return display_plain(project, display_fields, out);
+    }
+    let backend = CrosstermBackend::new(&mut *out);
     let options = TerminalOptions {
         viewport: Viewport::Inline(backend.size()?.height),
     };
     let mut terminal = Terminal::with_options(backend, options)?;
-    terminal.draw(|frame| {
-        let area = frame.area();
-        match render(frame, area, &mut project, &display_fields) {
-            Ok(()) => (),
-            Err(err) => {
-                let area = Rect::new(4, 0, frame.area().right().saturating_sub(8), 4);
-                render_error(frame, area, err)
-            }
-        }
-    })?;
-    println!();
+    terminal.draw(|frame| draw(frame, &mut project, &display_fields))?;
+    drop(terminal);
+    writeln!(out)?;
     Ok(())
 }
@@ -1,30 +1,46 @@
-use crate::package_inspect;
+use crate::distribution::requirement_line;
+use crate::package_inspect::{InstallScheme, Package};
 use crate::ui::*;
 use crate::warehouse::{DistributionUrl, PackageVersion};
-use crate::{DisplayFields, Project};
+use crate::{DisplayFields, Field, Project};
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use std::io::Write;
 use std::iter;
+use std::process::{Command, Stdio};
 use termimad::*;
 
 fn format_name_version(version: &PackageVersion) -> String {
-    if let Some(_reason) = &version.yanked_reason {
+    if let Some(reason) = version.yanked_reason.as_ref().filter(|r| !r.is_empty()) {
+        format!(
+            "{}@{} [YANKED]\n  yanked: {reason}",
+            &version.name, &version.version
+        )
+    } else if version.yanked {
         format!("{}@{} [YANKED]", &version.name, &version.version)
     } else {
         format!("{}@{}", &version.name, &version.version)
     }
 }
 
-fn format_dist_time(version: &PackageVersion, distribution: Option<&DistributionUrl>) -> String {
+fn format_dist_time(
+    version: &PackageVersion,
+    distribution: Option<&DistributionUrl>,
+    display_fields: &DisplayFields,
+) -> String {
     if let Some(dist) = distribution {
-        format!("{}@{}", format_dist(dist, 0), dist.upload_time)
-    } else if let Some(time) = version
-        .urls
-        .iter()
-        .filter_map(|u| u.upload_time_iso_8601.parse::<DateTime<Utc>>().ok())
-        .min()
-    {
-        format!("  {}", time.format("%Y-%m-%dT%H:%M:%S"))
+        let description = match dist.filename() {
+            Ok(wheel) if display_fields.describe_tags => {
+                format!(" ({})", wheel.compatibility_tag.describe())
+            }
+            _ => "".to_string(),
+        };
+        format!(
+            "{}@{}{description}",
+            format_dist(dist, 0, display_fields),
+            format_upload_time(dist, display_fields)
+        )
+    } else if let Some(time) = earliest_upload_time(version.urls.iter()) {
+        format!("  {}", format_time(&time, display_fields))
     } else {
         "".to_string()
     }
@@ -48,12 +64,23 @@ fn format_license_copyright(version: &PackageVersion) -> String {
     }
 }
 
-fn format_urls(version: &PackageVersion) -> Vec<String> {
+fn format_people(version: &PackageVersion) -> Vec<String> {
+    let people = version.people();
+    if !people.is_empty() {
+        iter::once("People".to_string())
+            .chain(people.iter().map(|p| format!("  {}: {p}", p.roles())))
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+fn format_urls(version: &PackageVersion, emoji: bool) -> Vec<String> {
     iter::once("Links".to_string())
         .chain(
-            iter::once((&"Package Index".to_string(), &version.project_url))
-                .chain(version.project_urls.iter())
-                .map(|url| format!("  {}  {}", iconify_url(url), url.1)),
+            list_urls(version)
+                .iter()
+                .map(|(label, url)| format!("  {}  {}", iconify_url(label, emoji), url)),
         )
         .collect()
 }
@@ -68,52 +95,54 @@ fn format_keywords(version: &PackageVersion) -> Vec<String> {
 }
 
 fn format_classifiers(version: &PackageVersion) -> Vec<String> {
-    if !&version.classifiers.is_empty() {
+    let classifiers = version.classifiers();
+    // classifiers too new for trove-classifiers are still shown, as they were given
+    let unrecognized = version.unrecognized_classifiers();
+    if !classifiers.is_empty() || !unrecognized.is_empty() {
         iter::once("Classifiers".to_string())
-            .chain(version.classifiers.iter().map(|c| format!("  {c}")))
+            .chain(classifiers.iter().map(|c| format!("  {c}")))
+            .chain(unrecognized.iter().map(|c| format!("  {c} (unrecognized)")))
             .collect()
     } else {
         vec![]
     }
 }
 
-fn format_dist(dist: &DistributionUrl, details: u8) -> String {
-    if dist.packagetype == "sdist" {
-        if details > 3 {
-            format!("  sdist {} {}", dist.upload_time, dist.url)
-        } else if details == 3 {
-            format!("  sdist {}", dist.url)
-        } else {
-            "  sdist".to_string()
-        }
-    } else if dist.packagetype == "bdist_wheel" {
-        if details > 3 {
-            format!(
-                "  {} {} {}",
-                dist.filename().unwrap().compatibility_tag,
-                dist.upload_time,
-                dist.url
-            )
-        } else if details == 3 {
-            format!(
-                "  {} {}",
-                dist.filename().unwrap().compatibility_tag,
-                dist.url
-            )
-        } else {
-            format!("  {}", dist.filename().unwrap().compatibility_tag)
-        }
+fn format_dist(dist: &DistributionUrl, details: u8, display_fields: &DisplayFields) -> String {
+    let tag = match dist.filename() {
+        Ok(wheel) if dist.packagetype == "bdist_wheel" => wheel.compatibility_tag.to_string(),
+        _ if dist.packagetype == "sdist" => sdist_label(&dist.filename),
+        // legacy artifacts have no compatibility tag, but their type and filename say as much
+        _ => format!("{} {}", dist.packagetype, dist.filename),
+    };
+    if details > 4 {
+        format!(
+            "  {tag} {} {} {}",
+            format_upload_time(dist, display_fields),
+            dist.url,
+            format_sha256(dist)
+        )
+    } else if details == 4 {
+        format!(
+            "  {tag} {} {}",
+            format_upload_time(dist, display_fields),
+            dist.url
+        )
+    } else if details == 3 {
+        format!("  {tag} {}", dist.url)
     } else {
-        "".to_string()
+        format!("  {tag}")
     }
 }
 
-fn format_distributions(distributions: &[DistributionUrl], details: u8) -> Vec<String> {
-    let sdist = distributions.iter().any(|u| u.packagetype == "sdist");
-    let wheel = distributions.iter().any(|u| u.packagetype == "bdist_wheel");
-    if !(sdist || wheel) {
+fn format_distributions(
+    distributions: &[DistributionUrl],
+    display_fields: &DisplayFields,
+) -> Vec<String> {
+    if distributions.is_empty() {
         return vec![];
     };
+    let details = display_fields.artifacts;
 
     let header = "Distribution Types".to_string();
     if details == 1 {
@@ -123,20 +152,32 @@ fn format_distributions(distributions: &[DistributionUrl], details: u8) -> Vec<S
         ]
     } else {
         iter::once(header)
-            .chain(distributions.iter().map(|u| format_dist(u, details)))
+            .chain(
+                distributions
+                    .iter()
+                    .map(|u| format_dist(u, details, display_fields)),
+            )
             .collect()
     }
 }
 
-fn format_dependencies(version: &PackageVersion) -> Vec<String> {
+fn format_dependencies(version: &PackageVersion, extras: Option<&[String]>) -> Vec<String> {
+    let groups = group_dependencies(version, extras);
     let dependencies = iter::once("Dependencies".to_string())
         .chain(
             version
-                .requires_python
-                .clone()
+                .requires_python_canonical()
                 .into_iter()
                 .map(|p| format!("  python{p}"))
-                .chain(version.requires_dist.iter().map(|d| format!("  {d}"))),
+                .chain(groups.base.iter().map(|d| format!("  {d}"))),
+        )
+        .chain(groups.extras.iter().flat_map(|(extra, requirements)| {
+            iter::once(format!("  [{extra}]"))
+                .chain(requirements.iter().map(|d| format!("    {d}")))
+        }))
+        .chain(
+            (!groups.hidden.is_empty())
+                .then(|| format!("  other extras: {}", groups.hidden.join(", "))),
         )
         .collect::<Vec<_>>();
     if dependencies.len() == 1 {
@@ -146,46 +187,64 @@ fn format_dependencies(version: &PackageVersion) -> Vec<String> {
     }
 }
 
-fn format_readme(version: &PackageVersion, style: bool) -> String {
+/// The readme of version, styled for the terminal when style is true
+///
+/// A styled readme is wrapped to width columns, or else to the width of the terminal.
+fn format_readme(version: &PackageVersion, style: bool, width: Option<u16>) -> String {
+    let description = version.description.clone().unwrap_or_default();
+    let styled = |markdown: &str| match width {
+        Some(width) => MadSkin::default()
+            .text(markdown, Some(width.into()))
+            .to_string(),
+        None => MadSkin::default().term_text(markdown).to_string(),
+    };
     if style {
         if let Some(Ok(content_type)) = version.description_content_type() {
-            if content_type.essence_str() == "text/markdown" {
-                return MadSkin::default()
-                    .term_text(&version.description.clone().unwrap_or_default())
-                    .to_string();
+            match content_type.essence_str() {
+                "text/markdown" => {
+                    return styled(&description);
+                }
+                "text/x-rst" => {
+                    return styled(&rst_to_markdown(&description));
+                }
+                // plain text has no markup to style
+                _ => (),
             };
         };
     };
-    version.description.clone().unwrap_or_default().to_string()
+    description
 }
 
-fn format_packages(distribution: &DistributionUrl) -> Vec<String> {
-    if distribution.packagetype == "sdist" {
-        // don't know how to extract packages from an sdist
-        return vec![];
-    };
-    if let Ok(inspect) = package_inspect::fetch(&distribution.url) {
+fn format_packages(package: Option<&Package>, details: u8) -> Vec<String> {
+    // packages cannot be extracted from an sdist, or a distribution that failed inspection
+    if let Some(inspect) = package {
+        let mut packages = inspect
+            .provides_import_kinds()
+            .into_iter()
+            .collect::<Vec<_>>();
+        packages.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
         iter::once("Importable Packages".to_string())
-            .chain(inspect.provides_packages().iter().map(|p| format!("  {p}")))
+            .chain(packages.iter().map(|(p, kind)| {
+                if details > 1 {
+                    format!("  {p} ({kind})")
+                } else {
+                    format!("  {p}")
+                }
+            }))
             .collect()
     } else {
         vec![]
     }
 }
 
-fn format_executables(distribution: &DistributionUrl) -> Vec<String> {
-    if distribution.packagetype == "sdist" {
-        // don't know how to extract packages from an sdist
-        return vec![];
-    };
-    if let Ok(inspect) = package_inspect::fetch(&distribution.url) {
+fn format_executables(package: Option<&Package>) -> Vec<String> {
+    if let Some(inspect) = package {
         iter::once("Executable Commands".to_string())
             .chain(
                 inspect
-                    .provides_executables()
-                    .iter()
-                    .chain(inspect.console_scripts().iter())
-                    .map(|p| format!("  {p}")),
+                    .executable_kinds()
+                    .into_iter()
+                    .map(|(p, kind)| format!("  {p} ({kind})")),
             )
             .collect()
     } else {
@@ -193,99 +252,421 @@ fn format_executables(distribution: &DistributionUrl) -> Vec<String> {
     }
 }
 
+fn format_entry_points(package: Option<&Package>) -> Vec<String> {
+    if let Some(inspect) = package {
+        let groups = inspect.entry_point_groups();
+        if groups.is_empty() {
+            return vec![];
+        }
+        iter::once("Entry Points".to_string())
+            .chain(groups.into_iter().flat_map(|(group, entry_points)| {
+                iter::once(format!("  [{group}]")).chain(
+                    entry_points
+                        .into_iter()
+                        .map(|(name, object)| format!("    {name} = {object}")),
+                )
+            }))
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+/// Format the details of version selected by display_fields, without retrieving anything else
+///
+/// dist is the selected distribution, if one was selected, package is what inspecting it found,
+/// if it could be inspected, and downloads are the project's recent downloads, if they are known.
+pub fn format_version_details(
+    version: &PackageVersion,
+    dist: Option<&DistributionUrl>,
+    package: Option<&Package>,
+    downloads: Option<&Downloads>,
+    display_fields: &DisplayFields,
+) -> String {
+    let mut display = Vec::new();
+
+    for field in display_fields.ordered() {
+        match field {
+            Field::Name if display_fields.name => display.push(format_name_version(version)),
+            Field::Time if display_fields.time || dist.is_some() => {
+                display.push(format_dist_time(version, dist, display_fields))
+            }
+            Field::License if display_fields.license => {
+                display.push(format_license_copyright(version))
+            }
+            Field::People if display_fields.people => display.extend(format_people(version)),
+            Field::Summary if display_fields.summary => display.push(format_summary(version)),
+            Field::Downloads if display_fields.downloads => {
+                if let Some(downloads) = downloads {
+                    display.push("Recent Downloads".to_string());
+                    display.push(format!("  {}", format_downloads(downloads)));
+                }
+            }
+            Field::Urls if display_fields.urls => {
+                display.extend(format_urls(version, display_fields.emoji))
+            }
+            Field::Keywords if display_fields.keywords => display.extend(format_keywords(version)),
+            Field::Classifiers if display_fields.classifiers => {
+                display.extend(format_classifiers(version))
+            }
+            Field::Artifacts if display_fields.artifacts >= 1 => {
+                let distributions = match dist {
+                    Some(dist) => std::slice::from_ref(dist),
+                    None => &version.urls,
+                };
+                display.extend(format_distributions(distributions, display_fields));
+            }
+            Field::Dependencies if display_fields.dependencies => display.extend(
+                format_dependencies(version, display_fields.extras.as_deref()),
+            ),
+            Field::Packages if display_fields.packages >= 1 => {
+                display.extend(format_packages(package, display_fields.packages))
+            }
+            Field::Executables if display_fields.executables => {
+                display.extend(format_executables(package))
+            }
+            Field::EntryPoints if display_fields.entry_points => {
+                display.extend(format_entry_points(package))
+            }
+            Field::Readme if display_fields.readme >= 1 => {
+                let render_readme = display_fields.readme >= 2 && display_fields.color;
+                display.push(format_readme(version, render_readme, display_fields.width));
+            }
+            _ => (),
+        }
+    }
+
+    display.join("\n")
+}
+
 fn format_package_version_details(
     mut project: Project,
     display_fields: DisplayFields,
 ) -> Result<String> {
-    let mut display = Vec::new();
-
-    if display_fields.name {
-        display.push(format_name_version(project.version()?));
+    let version = project.version()?.clone();
+    let dist = match project.distribution_selector() {
+        Some(_) => Some(project.distribution()?.clone()),
+        None => None,
     };
-
-    if display_fields.time || project.distribution_selector().is_some() {
-        let dist = (project.distribution_selector().is_some()).then_some(project.distribution()?.clone());
-        display.push(format_dist_time(project.version()?, dist.as_ref()));
+    let inspects =
+        display_fields.packages >= 1 || display_fields.executables || display_fields.entry_points;
+    let downloads = if display_fields.downloads {
+        project.downloads().cloned()
+    } else {
+        None
     };
-
-    if display_fields.license {
-        display.push(format_license_copyright(project.version()?));
+    let package = if inspects {
+        project.import_package().ok()
+    } else {
+        None
     };
+    Ok(format_version_details(
+        &version,
+        dist.as_ref(),
+        package,
+        downloads.as_ref(),
+        &display_fields,
+    ))
+}
 
-    if display_fields.summary {
-        display.push(format_summary(project.version()?));
-    };
+fn format_listed_version(version: &Version, yanked: bool, vulnerable: bool) -> String {
+    let mut listed = version.normalize();
+    if yanked {
+        listed += " [YANKED]";
+    }
+    if vulnerable {
+        listed += " [VULNERABLE]";
+    }
+    listed
+}
 
-    if display_fields.urls {
-        display.extend(format_urls(project.version()?));
+fn format_package_versions(mut project: Project, display_fields: DisplayFields) -> Result<String> {
+    let package = project.package()?;
+    let name = if display_fields.name {
+        format!("{}\n", &package.name)
+    } else {
+        "".to_string()
     };
+    let versions = list_versions(package, &display_fields);
+    if !package.has_releases() {
+        Ok(format!("{name}no releases"))
+    } else if versions.is_empty() {
+        Ok(format!("{name}no versions"))
+    } else if display_fields.time {
+        let timeline = versions
+            .iter()
+            .map(|(v, time, yanked)| {
+                let v = format_listed_version(v, *yanked, package.is_vulnerable(v));
+                if let Some(time) = time {
+                    format!("{v}  {}", format_time(time, &display_fields))
+                } else {
+                    v
+                }
+            })
+            .collect::<Vec<_>>();
+        Ok(format!("{name}{}", timeline.join("\n")))
+    } else {
+        let versions = versions
+            .iter()
+            .map(|(v, _, yanked)| format_listed_version(v, *yanked, package.is_vulnerable(v)))
+            .collect::<Vec<_>>();
+        Ok(format!("{name}{}", versions.join(", ")))
+    }
+}
 
-    if display_fields.keywords {
-        display.extend(format_keywords(project.version()?));
-    };
+fn format_diff_value(header: &str, old: Option<&String>, new: Option<&String>) -> Vec<String> {
+    match (old, new) {
+        (None, None) => vec![],
+        (Some(o), Some(n)) if o == n => vec![header.to_string(), format!("  {n}")],
+        _ => iter::once(header.to_string())
+            .chain(old.map(|o| format!("- {o}")))
+            .chain(new.map(|n| format!("+ {n}")))
+            .collect(),
+    }
+}
 
-    if display_fields.classifiers {
-        display.extend(format_classifiers(project.version()?));
-    };
+fn format_diff_list(header: &str, old: &[String], new: &[String]) -> Vec<String> {
+    if old.is_empty() && new.is_empty() {
+        return vec![];
+    }
+    iter::once(header.to_string())
+        .chain(old.iter().map(|o| {
+            if new.contains(o) {
+                format!("  {o}")
+            } else {
+                format!("- {o}")
+            }
+        }))
+        .chain(
+            new.iter()
+                .filter(|n| !old.contains(n))
+                .map(|n| format!("+ {n}")),
+        )
+        .collect()
+}
 
-    if display_fields.artifacts >= 1 {
-        if project.distribution_selector().is_some() {
-            display.extend(format_distributions(
-                &vec![project.distribution()?.clone()],
-                display_fields.artifacts,
-            ));
-        } else {
-            display.extend(format_distributions(
-                &project.version()?.urls,
-                display_fields.artifacts,
-            ));
-        };
-    };
+fn format_package_version_diff(mut project: Project, mut other: Project) -> Result<String> {
+    let old = project.version()?;
+    let new = other.version()?;
+    let mut display = vec![format!(
+        "{} -> {}",
+        format_name_version(old),
+        format_name_version(new)
+    )];
+    display.extend(format_diff_value(
+        "Summary",
+        old.summary.as_ref(),
+        new.summary.as_ref(),
+    ));
+    display.extend(format_diff_value(
+        "License",
+        old.license.as_ref(),
+        new.license.as_ref(),
+    ));
+    display.extend(format_diff_list(
+        "Classifiers",
+        &old.classifiers,
+        &new.classifiers,
+    ));
+    display.extend(format_diff_value(
+        "Requires Python",
+        old.requires_python.as_ref(),
+        new.requires_python.as_ref(),
+    ));
+    display.extend(format_diff_list(
+        "Dependencies",
+        &old.requires_dist,
+        &new.requires_dist,
+    ));
+    Ok(display.join("\n"))
+}
 
-    if display_fields.dependencies {
-        display.extend(format_dependencies(project.version()?));
-    };
+/// The version on a single line, as "name version — summary (license)"
+///
+/// Only the first line of a license is kept, as some projects give the whole license text.
+fn format_line(version: &PackageVersion) -> String {
+    let mut line = format!("{} {}", version.name, version.version);
+    if version.yanked {
+        line += " [YANKED]";
+    }
+    if let Some(summary) = &version.summary {
+        let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !summary.is_empty() {
+            line += &format!(" — {summary}");
+        }
+    }
+    if let Some(license) = version
+        .license
+        .as_deref()
+        .and_then(|l| l.lines().map(str::trim).find(|l| !l.is_empty()))
+    {
+        line += &format!(" ({license})");
+    }
+    line
+}
 
-    if display_fields.packages {
-        display.extend(format_packages(project.distribution()?));
+/// Display the version selected by project on a single line
+pub fn display_line(mut project: Project, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "{}", format_line(project.version()?))?;
+    Ok(())
+}
+
+/// Display each project on a single line, continuing past any that cannot be looked up
+pub fn display_lines(projects: Vec<Project>, out: &mut dyn Write) -> Result<()> {
+    for mut project in projects {
+        match project.version() {
+            Ok(version) => writeln!(out, "{}", format_line(version))?,
+            Err(err) => writeln!(out, "{} error: {err:#}", project.package_selector())?,
+        }
     }
+    Ok(())
+}
 
-    if display_fields.executables {
-        display.extend(format_executables(project.distribution()?));
+/// Display the dependencies of the version selected by project as requirement lines
+pub fn display_requirements(mut project: Project, out: &mut dyn Write) -> Result<()> {
+    for requirement in &project.version()?.requires_dist {
+        writeln!(out, "{}", requirement_line(requirement))?;
     }
+    Ok(())
+}
 
-    if display_fields.readme >= 1 {
-        let render_readme = display_fields.readme >= 2;
-        display.push(format_readme(project.version()?, render_readme));
-    };
+/// Display what was found by inspecting a single wheel, outside of any project on an index
+///
+/// When none of packages, executables, or entry points are selected, packages and executables are
+/// displayed.
+pub fn display_inspected(
+    package: &Package,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let any_selected =
+        display_fields.packages >= 1 || display_fields.executables || display_fields.entry_points;
+    let mut display = Vec::new();
+    if display_fields.name {
+        display.push(format!("{}@{}", package.name(), package.version()));
+    }
+    if display_fields.packages >= 1 || !any_selected {
+        display.extend(format_packages(Some(package), display_fields.packages));
+    }
+    if display_fields.executables || !any_selected {
+        display.extend(format_executables(Some(package)));
+    }
+    if display_fields.entry_points {
+        display.extend(format_entry_points(Some(package)));
+    }
+    if !display.is_empty() {
+        writeln!(out, "{}", display.join("\n"))?;
+    }
+    Ok(())
+}
 
-    Ok(display.join("\n"))
+/// Display every file an inspected wheel installs, grouped by where it is installed
+pub fn display_files(
+    package: &Package,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let mut display = Vec::new();
+    if display_fields.name {
+        display.push(format!("{}@{}", package.name(), package.version()));
+    }
+    display.push("Installed Files".to_string());
+    for (scheme, files) in package.installed_files() {
+        display.push(format!("  {scheme}"));
+        display.extend(files.into_iter().map(|(path, size)| match size {
+            Some(size) => format!("    {path}  {size} bytes"),
+            None => format!("    {path}"),
+        }));
+    }
+    writeln!(out, "{}", display.join("\n"))?;
+    Ok(())
 }
 
-fn format_package_versions(mut project: Project, display_fields: DisplayFields) -> Result<String> {
-    let package = project.package()?;
-    let name = if display_fields.name {
-        format!("{}\n", &package.name)
-    } else {
-        "".to_string()
-    };
-    let mut versions: Vec<String> = package
-        .ordered_versions()
+/// Display how large an inspected wheel is to download, when known, and once installed
+///
+/// The largest files, up to largest of them, are listed biggest first.
+pub fn display_size_report(
+    package: &Package,
+    download_size: Option<usize>,
+    largest: usize,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let mut display = Vec::new();
+    if display_fields.name {
+        display.push(format!("{}@{}", package.name(), package.version()));
+    }
+    if let Some(size) = download_size {
+        display.push("Download Size".to_string());
+        display.push(format!("  {}", format_size(size)));
+    }
+    let files = package
+        .installed_files()
         .iter()
-        .map(|v| v.normalize())
-        .collect();
-    versions.reverse();
-    Ok(format!("{name}{}", versions.join(", ")))
+        .map(|(_, files)| files.len())
+        .sum::<usize>();
+    display.push("Installed Size".to_string());
+    let plural = if files == 1 { "" } else { "s" };
+    display.push(format!(
+        "  {} in {files} file{plural}",
+        format_size(package.installed_size()),
+    ));
+    if largest > 0 {
+        display.push("Largest Files".to_string());
+        display.extend(
+            largest_files(package, largest)
+                .into_iter()
+                .map(|(size, scheme, path)| match scheme {
+                    InstallScheme::SitePackages => format!("  {}  {path}", format_size(size)),
+                    _ => format!("  {}  {path} ({scheme})", format_size(size)),
+                }),
+        );
+    }
+    writeln!(out, "{}", display.join("\n"))?;
+    Ok(())
+}
+
+/// Display the metadata differences between the versions selected by project and other
+pub fn display_diff(project: Project, other: Project, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "{}", format_package_version_diff(project, other)?)?;
+    Ok(())
 }
 
-pub fn display(project: Project, display_fields: DisplayFields) -> Result<()> {
-    if display_fields.versions {
-        println!("{}", format_package_versions(project, display_fields)?);
+pub fn display(project: Project, display_fields: DisplayFields, out: &mut dyn Write) -> Result<()> {
+    let display = if display_fields.versions {
+        format_package_versions(project, display_fields)?
     } else {
-        println!(
-            "{}",
-            format_package_version_details(project, display_fields)?
-        );
+        format_package_version_details(project, display_fields)?
+    };
+    // with every detail turned off, not even a blank line is written
+    if !display.is_empty() {
+        writeln!(out, "{display}")?;
+    }
+    Ok(())
+}
+
+/// Write output through pager if it is too long to fit in height lines, like git does
+///
+/// pager is a command line split on whitespace; an empty pager, or one that cannot be started,
+/// writes the output directly to out instead.
+pub fn page(output: &str, pager: &str, height: u16, out: &mut dyn Write) -> Result<()> {
+    let mut words = pager.split_whitespace();
+    if let (Some(program), true) = (words.next(), output.lines().count() >= height.into()) {
+        let mut command = Command::new(program);
+        command.args(words).stdin(Stdio::piped());
+        // like git, have less quit on short input and pass styling through
+        if std::env::var_os("LESS").is_none() {
+            command.env("LESS", "FRX");
+        }
+        if let Ok(mut child) = command.spawn() {
+            if let Some(mut stdin) = child.stdin.take() {
+                // the pager closing early, when the reader quits, is not an error
+                let _ = stdin.write_all(output.as_bytes());
+            }
+            child.wait()?;
+            return Ok(());
+        }
     }
+    write!(out, "{output}")?;
     Ok(())
 }
@@ -0,0 +1,129 @@
+use crate::package_inspect;
+use crate::ui::json::{report, report_files, report_size};
+use crate::{DisplayFields, Project};
+use anyhow::Result;
+use serde_json::Value;
+use std::io::Write;
+
+/// Whether s can be written as a plain YAML scalar without being read back as something else
+fn is_plain(s: &str) -> bool {
+    let reserved = [
+        "", "~", "null", "true", "false", "yes", "no", "on", "off", "y", "n",
+    ];
+    !reserved.contains(&s.to_ascii_lowercase().as_str())
+        && s.parse::<f64>().is_err()
+        && !s.starts_with(|c: char| c.is_whitespace() || "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        && !s.ends_with(|c: char| c.is_whitespace() || c == ':')
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && !s.contains(|c: char| c.is_control())
+}
+
+fn format_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) if is_plain(s) => s.to_string(),
+        // a JSON string is also a valid double-quoted YAML scalar
+        _ => value.to_string(),
+    }
+}
+
+fn format_key(key: &str) -> String {
+    format_scalar(&Value::String(key.to_string()))
+}
+
+fn write_yaml(value: &Value, indent: usize, out: &mut Vec<String>) {
+    let prefix = " ".repeat(indent);
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::Object(m) if !m.is_empty() => {
+                        out.push(format!("{prefix}{}:", format_key(key)));
+                        write_yaml(value, indent + 2, out);
+                    }
+                    Value::Array(a) if !a.is_empty() => {
+                        out.push(format!("{prefix}{}:", format_key(key)));
+                        write_yaml(value, indent, out);
+                    }
+                    _ => out.push(format!(
+                        "{prefix}{}: {}",
+                        format_key(key),
+                        format_value(value)
+                    )),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                match item {
+                    Value::Object(m) if !m.is_empty() => {
+                        // the first entry of a mapping shares the line with its sequence marker
+                        let mut entries = Vec::new();
+                        write_yaml(item, indent + 2, &mut entries);
+                        for (e, entry) in entries.into_iter().enumerate() {
+                            if e == 0 {
+                                out.push(format!("{prefix}- {}", entry.trim_start()));
+                            } else {
+                                out.push(entry);
+                            }
+                        }
+                    }
+                    Value::Array(a) if !a.is_empty() => {
+                        out.push(format!("{prefix}-"));
+                        write_yaml(item, indent + 2, out);
+                    }
+                    _ => out.push(format!("{prefix}- {}", format_value(item))),
+                }
+            }
+        }
+        _ => out.push(format!("{prefix}{}", format_value(value))),
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        _ => format_scalar(value),
+    }
+}
+
+/// Serialize value as a YAML document
+pub fn to_yaml(value: &Value) -> String {
+    let mut lines = Vec::new();
+    write_yaml(value, 0, &mut lines);
+    lines.join("\n")
+}
+
+pub fn display(
+    mut project: Project,
+    display_fields: DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report = report(&mut project, &display_fields)?;
+    writeln!(out, "{}", to_yaml(&report))?;
+    Ok(())
+}
+
+pub fn display_files(
+    package: &package_inspect::Package,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report = serde_json::to_value(report_files(package, display_fields))?;
+    writeln!(out, "{}", to_yaml(&report))?;
+    Ok(())
+}
+
+pub fn display_size_report(
+    package: &package_inspect::Package,
+    download_size: Option<usize>,
+    largest: usize,
+    display_fields: &DisplayFields,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report =
+        serde_json::to_value(report_size(package, download_size, largest, display_fields))?;
+    writeln!(out, "{}", to_yaml(&report))?;
+    Ok(())
+}
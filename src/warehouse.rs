@@ -4,11 +4,17 @@
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::error::Error as ErrorTrait;
 use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex, OnceLock};
 
 use anyhow::Result as aResult;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use pep440::Version;
-use serde::de::IgnoredAny;
+use ring::digest;
 use serde::{Deserialize, Deserializer};
 use trove_classifiers::Classifier;
 use ureq;
@@ -19,12 +25,34 @@ use super::distribution;
 pub const MAJOR_API_VERSION: u8 = 1;
 pub const MINOR_API_VERSION: u8 = 0;
 pub const PYPI_URI: &str = "https://pypi.org";
+/// Where a project's recent downloads are counted, with {project} standing for its name
+pub const DOWNLOADS_URI: &str = "https://pypistats.org/api/packages/{project}/recent";
 
 #[derive(Debug)]
 pub enum Error {
     NotFound,
     InvalidName,
     InvalidVersion,
+    DigestMismatch,
+    /// The project exists but none of its versions are valid and not yanked
+    NoValidVersion,
+    /// The project exists but has never had a release, as with a reserved name
+    NoReleases,
+    /// The index uses a newer major API version than this client understands
+    UnsupportedIndex,
+    /// A distribution was needed, but only project metadata may be retrieved
+    MetadataOnly,
+    /// A request was redirected to this location while redirects are forbidden
+    Redirected(String),
+    /// A request to this url was needed, but requests are forbidden
+    Offline(String),
+    /// A distribution's filename would be saved somewhere other than the directory it was meant
+    /// for
+    UnsafeFilename(String),
+    /// A distribution would be saved over this file, which already exists
+    AlreadyExists(String),
+    /// The index publishes no sha256 digest for this distribution, so it cannot be verified
+    NoDigest(String),
 }
 
 impl fmt::Display for Error {
@@ -33,13 +61,202 @@ impl fmt::Display for Error {
             Error::NotFound => write!(f, "NotFound"),
             Error::InvalidName => write!(f, "InvalidName"),
             Error::InvalidVersion => write!(f, "InvalidVersion"),
+            Error::DigestMismatch => write!(f, "DigestMismatch"),
+            Error::NoValidVersion => write!(
+                f,
+                "NoValidVersion: the project exists, but has no valid versions that are not yanked"
+            ),
+            Error::NoReleases => {
+                write!(f, "NoReleases: the project exists, but has no releases")
+            }
+            Error::UnsupportedIndex => write!(
+                f,
+                "UnsupportedIndex: the index uses an API version this client does not understand"
+            ),
+            Error::MetadataOnly => write!(
+                f,
+                "MetadataOnly: distributions are not inspected when only metadata is retrieved"
+            ),
+            Error::Redirected(location) => write!(
+                f,
+                "Redirected: the request was redirected to {location}, but redirects are forbidden"
+            ),
+            Error::Offline(url) => write!(f, "Offline: cannot fetch {url} while offline"),
+            Error::UnsafeFilename(filename) => write!(
+                f,
+                "UnsafeFilename: {filename:?} is not a plain filename, so it will not be saved"
+            ),
+            Error::AlreadyExists(path) => write!(
+                f,
+                "AlreadyExists: {path} already exists, move it away to save the distribution there"
+            ),
+            Error::NoDigest(filename) => write!(
+                f,
+                "NoDigest: the index publishes no sha256 digest for {filename}, so it cannot be \
+                 verified"
+            ),
         }
     }
 }
 
 impl ErrorTrait for Error {}
 
-/// The response from a Package Index root URL
+/// Whether requests follow redirects, which is true until [`forbid_redirects`] is called
+static FOLLOW_REDIRECTS: AtomicBool = AtomicBool::new(true);
+
+/// Whether every request fails instead of being sent, which is false until [`go_offline`] is
+/// called
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// The proxy given with [`use_proxy`], which every request is sent through instead of the proxy
+/// named by the environment
+static PROXY: OnceLock<String> = OnceLock::new();
+
+/// The proxy an agent sends requests through, and whether it follows redirects
+type AgentKind = (Option<String>, bool);
+
+/// The agents made so far, by the proxy they use and whether they follow redirects
+///
+/// Agents are kept so connections are reused, as most requests are sent to the same index.
+static AGENTS: LazyLock<Mutex<HashMap<AgentKind, ureq::Agent>>> = LazyLock::new(Default::default);
+
+/// Fail every later request that is redirected, rather than following it
+///
+/// A redirect to another host could otherwise carry the index's credentials along with it.
+pub fn forbid_redirects() {
+    FOLLOW_REDIRECTS.store(false, Ordering::Relaxed);
+}
+
+/// Fail every later request before it is sent, so that nothing is fetched over the network
+pub fn go_offline() {
+    OFFLINE.store(true, Ordering::Relaxed);
+}
+
+/// Whether [`go_offline`] has been called
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Send every later request through proxy, instead of any proxy named by the environment
+///
+/// Hosts listed in NO_PROXY are still reached directly.
+pub fn use_proxy(proxy: &str) -> aResult<()> {
+    ureq::Proxy::new(proxy)?;
+    PROXY.get_or_init(|| proxy.to_string());
+    Ok(())
+}
+
+/// Whether host is listed in no_proxy, the comma separated hosts that are never proxied
+///
+/// As with curl, a listed domain also matches its subdomains, with or without a leading dot, and
+/// "*" matches every host. A port given with a listed host is ignored.
+pub fn bypasses_proxy(host: &str, no_proxy: &str) -> bool {
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_ascii_lowercase();
+    no_proxy.split(',').map(str::trim).any(|listed| {
+        let listed = match listed.rsplit_once(':') {
+            // an IPv6 address is made of colons, so only a bracketed one can have a port
+            Some((name, port))
+                if port.chars().all(|c| c.is_ascii_digit())
+                    && (!name.contains(':') || name.ends_with(']')) =>
+            {
+                name
+            }
+            _ => listed,
+        };
+        let listed = listed
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .trim_start_matches('.')
+            .to_ascii_lowercase();
+        listed == "*"
+            || (!listed.is_empty() && (host == listed || host.ends_with(&format!(".{listed}"))))
+    })
+}
+
+/// The proxy a request to url is sent through: the one given with [`use_proxy`], else the one
+/// the environment names for the url's scheme, unless NO_PROXY lists the url's host
+fn proxy_for(url: &str) -> Option<String> {
+    let var = |name: &str| {
+        std::env::var(name.to_ascii_lowercase())
+            .or_else(|_| std::env::var(name))
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+    };
+    let url = Url::parse(url).ok()?;
+    if let (Some(host), Some(no_proxy)) = (url.host_str(), var("NO_PROXY")) {
+        if bypasses_proxy(host, &no_proxy) {
+            return None;
+        }
+    }
+    PROXY.get().cloned().or_else(|| match url.scheme() {
+        "https" => var("HTTPS_PROXY"),
+        "http" => var("HTTP_PROXY"),
+        _ => None,
+    })
+}
+
+/// The agent to send a request to url with, which follows redirects only when follow is true
+fn agent(url: &str, follow: bool) -> aResult<ureq::Agent> {
+    let proxy = proxy_for(url);
+    let mut agents = AGENTS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(agent) = agents.get(&(proxy.clone(), follow)) {
+        return Ok(agent.clone());
+    }
+    let mut builder = ureq::AgentBuilder::new();
+    if !follow {
+        // hand back a redirect as the response, instead of following it
+        builder = builder.redirects(0);
+    }
+    if let Some(proxy) = &proxy {
+        builder = builder.proxy(ureq::Proxy::new(proxy)?);
+    }
+    let agent = builder.build();
+    agents.insert((proxy, follow), agent.clone());
+    Ok(agent)
+}
+
+/// Send a GET request to url, following any redirects unless they are forbidden
+///
+/// A request that ends somewhere other than url is warned about, as an index that redirects
+/// elsewhere is often a misconfigured mirror.
+pub fn get(url: &str) -> aResult<ureq::Response> {
+    get_accepting(url, "*/*")
+}
+
+/// Send a GET request to url like [`get`], asking for a response of the accept media type
+fn get_accepting(url: &str, accept: &str) -> aResult<ureq::Response> {
+    if is_offline() {
+        Err(Error::Offline(redact_index(url)))?;
+    }
+    let agent = agent(url, FOLLOW_REDIRECTS.load(Ordering::Relaxed))?;
+    let response = agent.get(url).set("Accept", accept).call()?;
+    if (300..400).contains(&response.status()) {
+        // the location may be relative to where the request was sent
+        let location = response.header("location").unwrap_or_default();
+        let location = Url::parse(url)
+            .and_then(|u| u.join(location))
+            .map(String::from)
+            .unwrap_or(location.to_string());
+        Err(Error::Redirected(redact_index(&location)))?;
+    }
+    let requested = Url::parse(url).map(String::from).unwrap_or(url.to_string());
+    if response.get_url() != requested {
+        crate::warnings::warn(format!(
+            "{} was redirected to {}",
+            redact_index(url),
+            redact_index(response.get_url())
+        ));
+    }
+    Ok(response)
+}
+
+/// The project list at the root of a Package Index's simple api
+///
+/// Index roots themselves are usually an HTML page, or nothing at all, so the simple api is the
+/// only place every index describes itself.
 #[derive(Debug)]
 struct IndexRoot {
     api_version: String,
@@ -48,12 +265,12 @@ struct IndexRoot {
 
 impl IndexRoot {
     fn fetch(index: &str) -> aResult<Self> {
-        let index = Url::parse(index)?;
+        let mut index = Url::parse(index)?;
         if index.cannot_be_a_base() {
             return Err(Error::NotFound)?;
         }
-        let response: IndexRoot = ureq::get(index.as_str()).call()?.into_json()?;
-        Ok(response)
+        index.set_path("simple/");
+        fetch_json_accepting(&index, SIMPLE_JSON)
     }
 }
 
@@ -71,6 +288,23 @@ pub enum SupportLevel {
     Unsupported,
 }
 
+/// The index URL with any credentials removed, safe to display or share
+///
+/// Both the userinfo and the query string are dropped, as either can carry a token
+pub fn redact_index(index: &str) -> String {
+    match Url::parse(index) {
+        Ok(mut url) => {
+            // these only fail for URLs that cannot have credentials in the first place
+            let _ = url.set_username("");
+            let _ = url.set_password(None);
+            url.set_query(None);
+            url.to_string()
+        }
+        // do not risk echoing a secret from a URL that could not be understood
+        Err(_) => "<index>".to_string(),
+    }
+}
+
 pub fn index_is_supported(index: &str) -> aResult<SupportLevel> {
     let api_version = fetch_index_version(index)?;
     let mut version_parts = api_version.split('.');
@@ -104,7 +338,129 @@ pub fn index_is_supported(index: &str) -> aResult<SupportLevel> {
     }
 }
 
-/// Retrieve the names of all projects hosted on this index
+/// Retrieve and deserialize a JSON api response, reporting a missing resource as NotFound
+fn fetch_json<T: serde::de::DeserializeOwned>(url: &Url) -> aResult<T> {
+    fetch_json_accepting(url, "*/*")
+}
+
+/// Retrieve and deserialize a response of the accept media type, like [`fetch_json`]
+fn fetch_json_accepting<T: serde::de::DeserializeOwned>(url: &Url, accept: &str) -> aResult<T> {
+    match get_accepting(url.as_str(), accept) {
+        Ok(response) => Ok(response.into_json()?),
+        Err(err) if matches!(err.downcast_ref(), Some(ureq::Error::Status(404, _))) => {
+            Err(Error::NotFound)?
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Where the JSON api serves the metadata of a project, or of one of its versions
+fn metadata_url(index: &str, package: &str, version: Option<&str>) -> aResult<Url> {
+    let mut index = Url::parse(index)?;
+    if index.cannot_be_a_base() {
+        Err(Error::NotFound)?;
+    }
+    let package = distribution::normalize_package_name(package)?;
+    match version {
+        Some(version) => {
+            let version = Version::parse(version)
+                .ok_or(Error::InvalidVersion)?
+                .normalize();
+            index.set_path(&format!("pypi/{package}/{version}/json"));
+        }
+        None => index.set_path(&format!("pypi/{package}/json")),
+    }
+    Ok(index)
+}
+
+/// Where the simple api serves the files of a project
+fn simple_url(index: &str, package: &str) -> aResult<Url> {
+    let mut index = Url::parse(index)?;
+    if index.cannot_be_a_base() {
+        Err(Error::NotFound)?;
+    }
+    let package = distribution::normalize_package_name(package)?;
+    index.set_path(&format!("simple/{package}/"));
+    Ok(index)
+}
+
+/// Retrieve the JSON api response for a project, or one of its versions, exactly as it was sent
+///
+/// This includes everything that [`Package`] and [`PackageVersion`] do not keep, such as
+/// last_serial or a project's vulnerabilities.
+pub fn fetch_raw(index: &str, package: &str, version: Option<&str>) -> aResult<serde_json::Value> {
+    fetch_json(&metadata_url(index, package, version)?)
+}
+
+/// Retrieve the metadata at the exact url of a JSON api response, rather than at a path under an
+/// index
+///
+/// A project's response is read as the project, and also as its latest version when it lists
+/// that version's artifacts as PyPI does; a version's response is read as only that version.
+pub fn fetch_url(url: &str) -> aResult<(Option<Package>, Option<PackageVersion>)> {
+    let url = Url::parse(url)?;
+    if url.cannot_be_a_base() {
+        Err(Error::NotFound)?;
+    }
+    let response: serde_json::Value = fetch_json(&url)?;
+    read_response(&response, &redact_index(url.as_str()))
+}
+
+/// Read the metadata from a saved JSON api response, the same way as [`fetch_url`]
+pub fn read_file(path: &Path) -> aResult<(Option<Package>, Option<PackageVersion>)> {
+    let response: serde_json::Value = serde_json::from_reader(fs::File::open(path)?)?;
+    read_response(&response, &path.display().to_string())
+}
+
+fn read_response(
+    response: &serde_json::Value,
+    source: &str,
+) -> aResult<(Option<Package>, Option<PackageVersion>)> {
+    let package = match response.get("releases") {
+        Some(_) => Some(Package::deserialize(response)?),
+        None => None,
+    };
+    let version = match response.get("urls") {
+        Some(_) => Some(PackageVersion::deserialize(response)?),
+        None => None,
+    };
+    if package.is_none() && version.is_none() {
+        return Err(anyhow::anyhow!(
+            "{source} is not the metadata of a project or a version"
+        ));
+    }
+    Ok((package, version))
+}
+
+/// The classifiers that trove-classifiers recognizes, warning about those that were not
+fn validate_classifiers(classifiers: &[String]) -> Vec<Classifier> {
+    let valid = classifiers
+        .iter()
+        .filter_map(|c| Classifier::from_str(c).ok())
+        .collect::<Vec<_>>();
+    let ignored = unrecognized_classifiers(classifiers);
+    if !ignored.is_empty() {
+        let plural = if ignored.len() > 1 { "s" } else { "" };
+        crate::warnings::warn(format!(
+            "ignored {} classifier{plural} not recognized by trove-classifiers version {}: {}",
+            ignored.len(),
+            trove_classifiers::PYPA_VERSION,
+            ignored.join(", ")
+        ));
+    }
+    valid
+}
+
+/// The classifiers that trove-classifiers does not recognize, as they were given
+fn unrecognized_classifiers(classifiers: &[String]) -> Vec<&str> {
+    classifiers
+        .iter()
+        .filter(|c| Classifier::from_str(c).is_err())
+        .map(String::as_str)
+        .collect()
+}
+
+/// Retrieve the names of all projects hosted on this index, as listed by its simple api
 /// Names may or may not be normalized
 pub fn fetch_projects(index: &str) -> aResult<HashSet<String>> {
     let metadata = IndexRoot::fetch(index)?;
@@ -124,6 +480,7 @@ impl<'de> Deserialize<'de> for IndexRoot {
 
         #[derive(Deserialize)]
         struct Meta {
+            #[serde(rename = "api-version")]
             api_version: String,
         }
 
@@ -140,6 +497,44 @@ impl<'de> Deserialize<'de> for IndexRoot {
     }
 }
 
+/// Parse a Description-Content-Type header, such as "text/markdown; charset=UTF-8"
+///
+/// An empty header is the same as a missing one. Whitespace around parameters is tolerated, as
+/// some build backends write "text/x-rst; charset = UTF-8"
+pub fn parse_content_type(content_type: Option<&str>) -> Option<aResult<mime::Mime>> {
+    let content_type = content_type.map(str::trim).filter(|c| !c.is_empty())?;
+    let normalized = content_type
+        .split(';')
+        .map(|part| part.split('=').map(str::trim).collect::<Vec<_>>().join("="))
+        .collect::<Vec<_>>()
+        .join("; ");
+    Some(normalized.parse().map_err(Into::into))
+}
+
+/// How many times a project was downloaded recently, as counted by a pypistats-style source
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Downloads {
+    pub last_day: u64,
+    pub last_week: u64,
+    pub last_month: u64,
+}
+
+impl Downloads {
+    /// Fetch the recent downloads of package from source, a url with {project} in place of its
+    /// normalized name
+    pub fn fetch(source: &str, package: &str) -> aResult<Self> {
+        #[derive(Deserialize)]
+        struct Top {
+            data: Downloads,
+        }
+
+        let package = distribution::normalize_package_name(package)?;
+        let url = Url::parse(&source.replace("{project}", &package))?;
+        let top: Top = fetch_json(&url)?;
+        Ok(top.data)
+    }
+}
+
 /// A Python package as returned by the JSON api
 /// /pypi/{project}/json
 #[derive(Debug)]
@@ -161,27 +556,106 @@ pub struct Package {
     pub platform: Option<String>,
     pub project_url: String,
     pub project_urls: HashMap<String, String>,
+    pub releases: HashMap<String, Vec<DistributionUrl>>,
     pub requires_dist: Vec<String>,
     pub requires_python: Option<String>,
     pub summary: Option<String>,
+    /// The version the index considers latest, which the rest of the details describe
+    pub latest_version: String,
     pub versions: Vec<String>,
+    /// The known vulnerabilities of the latest version
+    pub vulnerabilities: Vec<PackageVulnerability>,
     pub yanked: bool,
     pub yanked_reason: Option<String>,
 }
 
 impl Package {
+    pub fn description_content_type(&self) -> Option<aResult<mime::Mime>> {
+        parse_content_type(self.description_content_type.as_deref())
+    }
+
     /// Retrieve package metadata from the package index
     pub fn fetch(index: &str, package: &str) -> aResult<Self> {
-        let mut index = Url::parse(index)?;
-        if index.cannot_be_a_base() {
-            return Err(Error::NotFound)?;
-        }
-        let package = distribution::normalize_package_name(package)?;
-        index.set_path(&format!("pypi/{package}/json"));
-        let response: Package = ureq::get(index.as_str()).call()?.into_json()?;
+        let response: Package = fetch_json(&metadata_url(index, package, None)?)?;
         Ok(response)
     }
 
+    /// Retrieve the files of package from the simple api of the package index
+    ///
+    /// The simple api only lists files, so none of the other metadata is filled in. Each file is
+    /// released under the version given in its filename.
+    pub fn fetch_simple(index: &str, package: &str) -> aResult<Self> {
+        let url = simple_url(index, package)?;
+        let response: SimpleProject = fetch_json_accepting(&url, SIMPLE_JSON)?;
+        let major = response.meta.api_version.split('.').next();
+        if major.and_then(|m| m.parse::<u8>().ok()) != Some(MAJOR_API_VERSION) {
+            Err(Error::UnsupportedIndex)?;
+        }
+        let name = response.name;
+        // versions without any files are only known from the list PEP-700 added
+        let mut releases = response
+            .versions
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| Version::parse(v))
+            .map(|v| (v.normalize(), Vec::new()))
+            .collect::<HashMap<_, _>>();
+        for file in response.files {
+            let Some(version) = filename_version(&name, &file.filename) else {
+                crate::warnings::warn(format!(
+                    "ignored {}, whose version could not be read from its name",
+                    file.filename
+                ));
+                continue;
+            };
+            releases
+                .entry(version)
+                .or_default()
+                .push(DistributionUrl::from_simple(file, &url));
+        }
+        let versions = releases.keys().cloned().collect::<Vec<_>>();
+        let latest_version = versions
+            .iter()
+            .filter_map(|v| Version::parse(v))
+            .max()
+            .map(|v| v.normalize())
+            .unwrap_or_default();
+        let page = redact_index(url.as_str());
+        Ok(Package {
+            author: None,
+            author_email: None,
+            classifiers: Vec::new(),
+            description: None,
+            description_content_type: None,
+            docs_url: None,
+            download_url: None,
+            home_page: None,
+            keywords: None,
+            license: None,
+            maintainer: None,
+            maintainer_email: None,
+            name,
+            package_url: page.clone(),
+            platform: None,
+            project_url: page,
+            project_urls: HashMap::new(),
+            releases,
+            requires_dist: Vec::new(),
+            requires_python: None,
+            summary: None,
+            latest_version,
+            versions,
+            vulnerabilities: Vec::new(),
+            yanked: false,
+            yanked_reason: None,
+        })
+    }
+
+    /// Whether Package has any releases at all, valid or not
+    pub fn has_releases(&self) -> bool {
+        !self.versions.is_empty()
+    }
+
     /// Return validated versions of Package in comparison order
     ///
     /// Note that the order is not necessarily the same order as creation time
@@ -195,6 +669,43 @@ impl Package {
         ordered_versions.into_sorted_vec()
     }
 
+    /// Return the validated versions of Package that may be automatically selected, in
+    /// comparison order
+    ///
+    /// Pre-releases are excluded unless requested, or unless Package has no final releases at all.
+    pub fn candidate_versions(&self, prereleases: bool) -> Vec<Version> {
+        let ordered_versions = self.ordered_versions();
+        if prereleases || ordered_versions.iter().all(distribution::is_prerelease) {
+            ordered_versions
+        } else {
+            ordered_versions
+                .into_iter()
+                .filter(|v| !distribution::is_prerelease(v))
+                .collect()
+        }
+    }
+
+    /// Return validated versions of Package in comparison order, along with the distributions
+    /// uploaded for each version
+    pub fn ordered_releases(&self) -> Vec<(Version, &[DistributionUrl])> {
+        let mut ordered_releases = self
+            .releases
+            .iter()
+            .filter_map(|(v, urls)| Some((Version::parse(v)?, urls.as_slice())))
+            .collect::<Vec<_>>();
+        ordered_releases.sort_by(|a, b| a.0.cmp(&b.0));
+        ordered_releases
+    }
+
+    /// Whether version is known to be vulnerable, by any advisory that has not been withdrawn
+    ///
+    /// The project's response only reports the vulnerabilities of its latest version, so no other
+    /// version is ever known to be vulnerable; the response for that version has to be fetched.
+    pub fn is_vulnerable(&self, version: &Version) -> bool {
+        Version::parse(&self.latest_version).is_some_and(|latest| &latest == version)
+            && self.vulnerabilities.iter().any(|v| v.withdrawn.is_none())
+    }
+
     /// Return validated classifiers of Package
     ///
     /// This function may return less items than the classifiers field but
@@ -204,10 +715,14 @@ impl Package {
     /// from a different package index that does not validate classifiers or uses
     /// some other set of classifiers.
     pub fn classifiers(&self) -> Vec<Classifier> {
-        self.classifiers
-            .iter()
-            .filter_map(|c| Classifier::from_str(c).ok())
-            .collect()
+        validate_classifiers(&self.classifiers)
+    }
+
+    /// Return the classifiers of Package that the bundled trove-classifiers does not recognize
+    ///
+    /// These are the classifiers dropped by classifiers, most likely added to pypi.org since.
+    pub fn unrecognized_classifiers(&self) -> Vec<&str> {
+        unrecognized_classifiers(&self.classifiers)
     }
 
     /// Return all keywords set on a Package
@@ -233,9 +748,11 @@ impl<'de> Deserialize<'de> for Package {
         struct Top {
             info: Info,
             //last_serial: usize,
-            releases: HashMap<String, IgnoredAny>,
+            releases: HashMap<String, Vec<DistributionUrl>>,
             //urls: Vec<DistributionUrl>,
-            //vulnerabilities: Vec<IgnoredAny>,
+            // other indexes may not report vulnerabilities at all
+            #[serde(default)]
+            vulnerabilities: Vec<PackageVulnerability>,
         }
 
         #[derive(Deserialize)]
@@ -265,12 +782,19 @@ impl<'de> Deserialize<'de> for Package {
             requires_dist: Option<Vec<String>>,
             requires_python: Option<String>,
             summary: Option<String>,
-            //version: String,
+            version: String,
             yanked: bool,
             yanked_reason: Option<String>,
         }
 
         let top = Top::deserialize(deserializer)?;
+        // versions is yet-to-be implemented directly in the json API
+        // https://github.com/pypi/warehouse/pull/12079
+        let versions = top
+            .releases
+            .keys()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>();
         Ok(Package {
             author: top.info.author,
             author_email: top.info.author_email,
@@ -289,25 +813,86 @@ impl<'de> Deserialize<'de> for Package {
             platform: top.info.platform,
             project_url: top.info.project_url,
             project_urls: top.info.project_urls.unwrap_or_default(),
+            releases: top.releases,
             requires_dist: top.info.requires_dist.unwrap_or_default(),
             requires_python: top.info.requires_python,
             summary: top.info.summary,
-            // versions is yet-to-be implemented directly in the json API
-            // https://github.com/pypi/warehouse/pull/12079
-            versions: top
-                .releases
-                .keys()
-                .map(|v| v.to_string())
-                .collect::<Vec<String>>(),
+            latest_version: top.info.version,
+            versions,
+            vulnerabilities: top.vulnerabilities,
             yanked: top.info.yanked,
             yanked_reason: top.info.yanked_reason,
         })
     }
 }
 
+/// What someone credited in a project's metadata does for it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    Author,
+    Maintainer,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::Author => write!(f, "Author"),
+            Role::Maintainer => write!(f, "Maintainer"),
+        }
+    }
+}
+
+/// Someone credited in a project's metadata, by name, email, or both
+#[derive(Clone, Debug, PartialEq)]
+pub struct Person {
+    pub roles: Vec<Role>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl Person {
+    /// None if neither a name nor an email is given; blank values are not given
+    fn new(role: Role, name: Option<&str>, email: Option<&str>) -> Option<Self> {
+        let name = name.map(str::trim).filter(|n| !n.is_empty());
+        // some build backends quote names within the email field
+        let email = email
+            .map(|e| e.replace('"', "").trim().to_string())
+            .filter(|e| !e.is_empty());
+        if name.is_none() && email.is_none() {
+            return None;
+        }
+        Some(Person {
+            roles: vec![role],
+            name: name.map(str::to_string),
+            email,
+        })
+    }
+
+    /// The roles of Person, joined like "Author & Maintainer"
+    pub fn roles(&self) -> String {
+        self.roles
+            .iter()
+            .map(Role::to_string)
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+}
+
+impl fmt::Display for Person {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.name, &self.email) {
+            // an email field written from pyproject.toml already holds the names
+            (Some(name), Some(email)) if !email.contains('<') => write!(f, "{name} <{email}>"),
+            (_, Some(email)) => write!(f, "{email}"),
+            (Some(name), None) => write!(f, "{name}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
 /// A Python package version as returned by the JSON api
 /// /pypi/{project}/{version}/json
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PackageVersion {
     pub author: Option<String>,
     pub author_email: Option<String>,
@@ -340,19 +925,54 @@ pub struct PackageVersion {
 impl PackageVersion {
     /// Retrieve package version metadata from the package index
     pub fn fetch(index: &str, package: &str, version: &str) -> aResult<Self> {
-        let mut index = Url::parse(index)?;
-        if index.cannot_be_a_base() {
-            return Err(Error::NotFound)?;
-        }
-        let package = distribution::normalize_package_name(package)?;
-        let version = Version::parse(version)
-            .ok_or(Error::InvalidVersion)?
-            .normalize();
-        index.set_path(&format!("pypi/{package}/{version}/json"));
-        let response: PackageVersion = ureq::get(index.as_str()).call()?.into_json()?;
+        let response: PackageVersion = fetch_json(&metadata_url(index, package, Some(version))?)?;
         Ok(response)
     }
 
+    /// The version of package, with only what package knows of it, if package has that version
+    ///
+    /// This is all that is known of a version from the simple api. The version is yanked when all
+    /// of its files are.
+    pub fn from_release(package: &Package, version: &str) -> Option<Self> {
+        let version = Version::parse(version)?;
+        let (version, urls) = package
+            .ordered_releases()
+            .into_iter()
+            .find(|(v, _)| *v == version)?;
+        let yanked = !urls.is_empty() && urls.iter().all(|u| u.yanked);
+        Some(PackageVersion {
+            author: None,
+            author_email: None,
+            classifiers: Vec::new(),
+            description: None,
+            description_content_type: None,
+            docs_url: None,
+            download_url: None,
+            home_page: None,
+            keywords: None,
+            license: None,
+            maintainer: None,
+            maintainer_email: None,
+            name: package.name.clone(),
+            package_url: package.package_url.clone(),
+            platform: None,
+            project_url: package.project_url.clone(),
+            project_urls: HashMap::new(),
+            release_url: None,
+            requires_dist: Vec::new(),
+            requires_python: urls.iter().find_map(|u| u.requires_python.clone()),
+            summary: None,
+            urls: urls.to_vec(),
+            version: version.normalize(),
+            vulnerabilities: Vec::new(),
+            yanked,
+            yanked_reason: urls
+                .iter()
+                .find_map(|u| u.yanked_reason.clone())
+                .filter(|_| yanked),
+        })
+    }
+
     /// Return the validated classifiers set on PackageVersion
     ///
     /// This function may return less results than the classifiers field but
@@ -362,10 +982,14 @@ impl PackageVersion {
     /// from a different package index that does not validate classifiers or uses
     /// some other set of classifiers.
     pub fn classifiers(&self) -> Vec<Classifier> {
-        self.classifiers
-            .iter()
-            .filter_map(|c| Classifier::from_str(c).ok())
-            .collect()
+        validate_classifiers(&self.classifiers)
+    }
+
+    /// Return the classifiers of PackageVersion that the bundled trove-classifiers does not recognize
+    ///
+    /// These are the classifiers dropped by classifiers, most likely added to pypi.org since.
+    pub fn unrecognized_classifiers(&self) -> Vec<&str> {
+        unrecognized_classifiers(&self.classifiers)
     }
 
     /// Return all keywords set on a PackageVersion
@@ -381,14 +1005,50 @@ impl PackageVersion {
         }
     }
 
+    /// The Requires-Python of PackageVersion, parsed into its clauses
+    ///
+    /// None if no Requires-Python was given, or it cannot be understood.
+    pub fn python_specifiers(&self) -> Option<distribution::SpecifierSet> {
+        distribution::SpecifierSet::parse(self.requires_python.as_ref()?)
+    }
+
+    /// The Requires-Python of PackageVersion in canonical form, or as given if it cannot be parsed
+    pub fn requires_python_canonical(&self) -> Option<String> {
+        self.python_specifiers()
+            .map(|s| s.to_string())
+            .or_else(|| self.requires_python.clone())
+    }
+
+    /// Everyone credited as an author or maintainer of PackageVersion
+    ///
+    /// Either a name or an email may be missing, but never both. A maintainer who is the same as
+    /// the author is listed once, with both roles.
+    pub fn people(&self) -> Vec<Person> {
+        let mut people: Vec<Person> = Vec::new();
+        for (role, name, email) in [
+            (Role::Author, &self.author, &self.author_email),
+            (Role::Maintainer, &self.maintainer, &self.maintainer_email),
+        ] {
+            let Some(person) = Person::new(role, name.as_deref(), email.as_deref()) else {
+                continue;
+            };
+            match people
+                .iter_mut()
+                .find(|p| p.name == person.name && p.email == person.email)
+            {
+                Some(same) => same.roles.push(role),
+                None => people.push(person),
+            }
+        }
+        people
+    }
+
     pub fn version(&self) -> aResult<Version> {
         Version::parse(&self.version).ok_or(Error::InvalidVersion.into())
     }
 
     pub fn description_content_type(&self) -> Option<aResult<mime::Mime>> {
-        self.description_content_type
-            .as_ref()
-            .map(|content_type| content_type.parse().map_err(Into::into))
+        parse_content_type(self.description_content_type.as_deref())
     }
 }
 
@@ -488,6 +1148,93 @@ impl DistributionUrl {
     pub fn filename(&self) -> aResult<distribution::WheelName> {
         distribution::WheelName::from_filename(&self.filename)
     }
+
+    /// The time the distribution was uploaded, in UTC
+    ///
+    /// upload_time is the same time without its fraction of a second or zone, which is always UTC,
+    /// so it is only read when upload_time_iso_8601 cannot be.
+    pub fn uploaded(&self) -> Option<DateTime<Utc>> {
+        self.upload_time_iso_8601
+            .parse::<DateTime<Utc>>()
+            .ok()
+            .or_else(|| {
+                NaiveDateTime::parse_from_str(&self.upload_time, "%Y-%m-%dT%H:%M:%S")
+                    .ok()
+                    .map(|time| time.and_utc())
+            })
+    }
+
+    /// Download the distribution file and confirm it matches the published sha256 digest
+    pub fn verify(&self) -> aResult<()> {
+        let Some(published) = &self.digests.sha256 else {
+            return Err(Error::NoDigest(self.filename.clone()))?;
+        };
+        let mut download = get(&self.url)?.into_reader();
+        let sha256 = copy_and_digest(&mut download, &mut io::sink())?;
+        if sha256 != *published {
+            return Err(Error::DigestMismatch)?;
+        }
+        Ok(())
+    }
+
+    /// Save the distribution file into dir, confirming it matches the published sha256 digest
+    ///
+    /// The file is named after the distribution's filename, which must not already exist in dir.
+    /// It is removed again if the download fails or the digest does not match. A distribution
+    /// without a published digest is saved unverified.
+    pub fn download(&self, dir: &Path) -> aResult<PathBuf> {
+        // the filename comes from the index, and must not be able to name a path outside of dir
+        let mut components = Path::new(&self.filename).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        ) {
+            return Err(Error::UnsafeFilename(self.filename.clone()))?;
+        }
+        let path = dir.join(&self.filename);
+        let mut download = get(&self.url)?.into_reader();
+        let mut file = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                return Err(Error::AlreadyExists(path.display().to_string()))?;
+            }
+            Err(err) => return Err(err)?,
+        };
+        let saved = match copy_and_digest(&mut download, &mut file) {
+            Ok(sha256) if self.digests.sha256.as_ref().is_none_or(|p| *p == sha256) => Ok(()),
+            Ok(_) => Err(Error::DigestMismatch.into()),
+            Err(err) => Err(err.into()),
+        };
+        if saved.is_err() {
+            drop(file);
+            let _ = fs::remove_file(&path);
+        }
+        saved.map(|()| path)
+    }
+}
+
+/// Copy all of reader into writer, returning the hex-encoded sha256 digest of what was copied
+fn copy_and_digest<R: Read, W: Write>(reader: &mut R, writer: &mut W) -> io::Result<String> {
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buf = [0; 8192];
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        context.update(&buf[..read]);
+        writer.write_all(&buf[..read])?;
+    }
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
 }
 
 impl<'de> Deserialize<'de> for DistributionUrl {
@@ -532,14 +1279,124 @@ impl<'de> Deserialize<'de> for DistributionUrl {
     }
 }
 
+/// The media type of the PEP-691 JSON simple api
+const SIMPLE_JSON: &str = "application/vnd.pypi.simple.v1+json";
+
+/// A project's files as returned by the simple api
+/// /simple/{project}/
+#[derive(Deserialize)]
+struct SimpleProject {
+    meta: SimpleMeta,
+    name: String,
+    files: Vec<SimpleFile>,
+    // added by PEP-700
+    versions: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct SimpleMeta {
+    #[serde(rename = "api-version")]
+    api_version: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SimpleFile {
+    filename: String,
+    url: String,
+    hashes: HashMap<String, String>,
+    requires_python: Option<String>,
+    yanked: Option<SimpleYanked>,
+    // added by PEP-700
+    size: Option<usize>,
+    upload_time: Option<String>,
+}
+
+/// A file is yanked by giving either true or the reason it was yanked
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SimpleYanked {
+    Yanked(bool),
+    Reason(String),
+}
+
+/// The version of project that a distribution's filename says it is, normalized
+///
+/// An sdist or egg is named "{name}-{version}", which is ambiguous when the name has a "-" in it,
+/// so the name is found by comparing it normalized against project.
+fn filename_version(project: &str, filename: &str) -> Option<String> {
+    if let Ok(wheel) = distribution::WheelName::from_filename(filename) {
+        return Some(wheel.version.normalize());
+    }
+    let stem = [
+        ".tar.gz", ".tar.bz2", ".tar.xz", ".tgz", ".zip", ".tar", ".egg",
+    ]
+    .iter()
+    .find_map(|ext| filename.strip_suffix(ext))?;
+    let project = distribution::normalize_package_name(project).ok()?;
+    stem.match_indices('-')
+        .find(|(at, _)| {
+            distribution::normalize_package_name(&stem[..*at]).ok() == Some(project.clone())
+        })
+        // an egg is also named for the Python it was built for
+        .and_then(|(at, _)| stem[at + 1..].split('-').next())
+        .and_then(Version::parse)
+        .map(|v| v.normalize())
+}
+
+impl DistributionUrl {
+    /// The distribution a file of the simple api describes, where the file's url is relative to
+    /// page
+    fn from_simple(file: SimpleFile, page: &Url) -> Self {
+        let hash = |algorithm: &str| file.hashes.get(algorithm).cloned().unwrap_or_default();
+        // simple api files may publish any hashes, or none at all
+        let (packagetype, python_version) =
+            match distribution::WheelName::from_filename(&file.filename) {
+                Ok(wheel) => {
+                    let tag = wheel.compatibility_tag.to_string();
+                    let python = tag.split('-').next().unwrap_or_default().to_string();
+                    ("bdist_wheel", python)
+                }
+                Err(_) if file.filename.ends_with(".egg") => ("bdist_egg", String::new()),
+                Err(_) => ("sdist", "source".to_string()),
+            };
+        let (yanked, yanked_reason) = match file.yanked {
+            Some(SimpleYanked::Reason(reason)) => (true, Some(reason)),
+            Some(SimpleYanked::Yanked(yanked)) => (yanked, None),
+            None => (false, None),
+        };
+        let upload_time = file.upload_time.unwrap_or_default();
+        DistributionUrl {
+            digests: DistributionDigest {
+                blake2b_256: hash("blake2b_256"),
+                md5: hash("md5"),
+                sha256: file.hashes.get("sha256").cloned(),
+            },
+            md5_digest: hash("md5"),
+            url: page.join(&file.url).map(String::from).unwrap_or(file.url),
+            filename: file.filename,
+            packagetype: packagetype.to_string(),
+            python_version,
+            requires_python: file.requires_python,
+            size: file.size.unwrap_or_default(),
+            // the json api leaves the fraction of a second and the timezone out of upload_time
+            upload_time: upload_time.chars().take(19).collect(),
+            upload_time_iso_8601: upload_time,
+            yanked,
+            yanked_reason,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct DistributionDigest {
     pub blake2b_256: String,
     pub md5: String,
-    pub sha256: String,
+    /// Only missing from simple api files that do not publish it
+    pub sha256: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct PackageVulnerability {
     pub id: String,
     pub source: String,
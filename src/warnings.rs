@@ -0,0 +1,33 @@
+//! Non-fatal conditions that explain why the displayed details differ from what the index has
+//!
+//! Warnings are only collected once [`enable`] is called, and each distinct warning is kept once,
+//! however many times it is raised. They are collected rather than written as they happen so that
+//! they are not drawn over by a spinner or an interactive display.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Start collecting warnings
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Record a warning, if warnings are enabled and it has not already been recorded
+pub fn warn(warning: impl Into<String>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let warning = warning.into();
+    let mut warnings = WARNINGS.lock().unwrap();
+    if !warnings.contains(&warning) {
+        warnings.push(warning);
+    }
+}
+
+/// Every warning recorded since the last take, in the order they were first raised
+pub fn take() -> Vec<String> {
+    std::mem::take(&mut WARNINGS.lock().unwrap())
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::{kayak, MockIndex};
+use serde_json::json;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+fn cache_home(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("kayak-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Run kayak, keeping its cache in cache_home
+fn cached(cache_home: &Path, index: &str, args: &[&str]) -> Output {
+    kayak(index)
+        .args(["--format", "text"])
+        .args(args)
+        .env("XDG_CACHE_HOME", cache_home)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn project_list_is_fetched_once_across_sessions() {
+    let index = MockIndex::with_projects(&["example"]);
+    let home = cache_home("cached-projects");
+    for _ in 0..2 {
+        let output = cached(&home, &index.url, &["exampel"]);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("did you mean `example`?"));
+    }
+    // every session checks the index is supported, but only the first fetches the project list
+    assert_eq!(index.hits("/simple/"), 3);
+    assert_eq!(
+        fs::read_dir(home.join("kayak").join("projects"))
+            .unwrap()
+            .count(),
+        1
+    );
+    fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn refresh_index_fetches_the_project_list_again() {
+    let index = MockIndex::with_projects(&["example"]);
+    let home = cache_home("refreshed-projects");
+    let output = cached(&home, &index.url, &["exampel"]);
+    assert!(!output.status.success());
+    assert_eq!(index.hits("/simple/"), 2);
+
+    let output = cached(&home, &index.url, &["--refresh-index"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("cached 1 projects on "));
+    assert_eq!(index.hits("/simple/"), 4);
+    fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn offline_sessions_never_reach_the_index() {
+    let index = MockIndex::with_projects(&["example"]);
+    index.serve_metadata("example", "1.0.0", json!({}));
+    let home = cache_home("offline");
+    let output = cached(&home, &index.url, &["--offline", "example", "1.0.0"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(&format!(
+            "Offline: cannot fetch {}/pypi/example/1.0.0/json while offline",
+            index.url.trim_end_matches('/')
+        )),
+        "{output:?}"
+    );
+
+    let fixture = format!(
+        "{}/tests/fixtures/example-rc.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let output = cached(&home, &index.url, &["--offline", "--from-file", &fixture]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        index.hits("/simple/") + index.hits("/pypi/example/1.0.0/json"),
+        0
+    );
+    let _ = fs::remove_dir_all(home);
+}
+
+#[test]
+fn simple_only_indexes_list_their_projects() {
+    let index = MockIndex::with_projects(&["example"]);
+    index.serve(
+        "/",
+        b"<!DOCTYPE html><html><body>An index</body></html>".to_vec(),
+    );
+    index.serve(
+        "/simple/example/",
+        json!({
+            "meta": {"api-version": "1.1"},
+            "name": "example",
+            "files": [{
+                "filename": "example-1.0.0.tar.gz",
+                "url": "../../files/example-1.0.0.tar.gz",
+                "hashes": {},
+            }],
+        })
+        .to_string()
+        .into_bytes(),
+    );
+    let home = cache_home("simple-only");
+    let output = cached(&home, &index.url, &["--api", "simple", "example"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stderr.is_empty(), "{output:?}");
+
+    let output = cached(&home, &index.url, &["--api", "simple", "exampel"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("did you mean `example`?"));
+    assert_eq!(index.hits("/"), 0);
+    let _ = fs::remove_dir_all(home);
+}
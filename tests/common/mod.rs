@@ -0,0 +1,329 @@
+//! A package index served from memory, so projects can be looked up without a network
+
+#![allow(dead_code)]
+
+use kayak::{DisplayFields, SortBy};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::net::TcpListener;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+pub struct MockIndex {
+    pub url: String,
+    routes: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    redirects: Arc<Mutex<HashMap<String, String>>>,
+    hits: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl MockIndex {
+    /// Start serving an index on a local port; requests for unknown paths are answered with 404
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let routes: Arc<Mutex<HashMap<String, Vec<u8>>>> = Default::default();
+        let redirects: Arc<Mutex<HashMap<String, String>>> = Default::default();
+        let hits: Arc<Mutex<HashMap<String, usize>>> = Default::default();
+        let (served_routes, served_redirects, served_hits) = (
+            Arc::clone(&routes),
+            Arc::clone(&redirects),
+            Arc::clone(&hits),
+        );
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                // drain the headers, requests never have a body
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+                let path = request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or("/")
+                    .to_string();
+                *served_hits.lock().unwrap().entry(path.clone()).or_default() += 1;
+                let body = served_routes.lock().unwrap().get(&path).cloned();
+                let location = served_redirects.lock().unwrap().get(&path).cloned();
+                let (status, body, location) = match (body, location) {
+                    (_, Some(location)) => ("302 Found", Vec::new(), location),
+                    (Some(body), None) => ("200 OK", body, String::new()),
+                    (None, None) => ("404 Not Found", Vec::new(), String::new()),
+                };
+                let location = if location.is_empty() {
+                    location
+                } else {
+                    format!("Location: {location}\r\n")
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\n{location}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(&body);
+            }
+        });
+        MockIndex {
+            url,
+            routes,
+            redirects,
+            hits,
+        }
+    }
+
+    /// Start serving an index whose Simple API root lists projects, so it is known to be supported
+    pub fn with_projects(projects: &[&str]) -> Self {
+        let index = MockIndex::start();
+        let projects = projects
+            .iter()
+            .map(|name| json!({"name": name}))
+            .collect::<Vec<_>>();
+        index.serve(
+            "/simple/",
+            json!({"meta": {"api-version": "1.0"}, "projects": projects})
+                .to_string()
+                .into_bytes(),
+        );
+        index
+    }
+
+    pub fn serve(&self, path: &str, body: Vec<u8>) {
+        self.routes.lock().unwrap().insert(path.to_string(), body);
+    }
+
+    /// Answer requests for path with a redirect to location
+    pub fn redirect(&self, path: &str, location: &str) {
+        self.redirects
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), location.to_string());
+    }
+
+    /// The number of times path was requested
+    pub fn hits(&self, path: &str) -> usize {
+        self.hits.lock().unwrap().get(path).copied().unwrap_or(0)
+    }
+
+    /// Serve a project with one version that has one artifact, returning the artifact's path
+    pub fn serve_project(
+        &self,
+        name: &str,
+        version: &str,
+        filename: &str,
+        artifact: Vec<u8>,
+    ) -> String {
+        let artifact_path = format!("/files/{filename}");
+        let url = distribution_url(&format!("{}{artifact_path}", self.url), filename);
+        let project = json!({
+            "info": info(name, version),
+            "releases": {version: [url.clone()]},
+        });
+        let project_version = json!({
+            "info": info(name, version),
+            "urls": [url],
+            "vulnerabilities": [],
+        });
+        self.serve(
+            &format!("/pypi/{name}/json"),
+            project.to_string().into_bytes(),
+        );
+        self.serve(
+            &format!("/pypi/{name}/{version}/json"),
+            project_version.to_string().into_bytes(),
+        );
+        self.serve(&artifact_path, artifact);
+        artifact_path
+    }
+
+    /// Serve a project with one version that has no artifacts, overriding its default metadata
+    pub fn serve_metadata(&self, name: &str, version: &str, metadata: Value) {
+        let mut info = info(name, version);
+        for (key, value) in metadata.as_object().unwrap() {
+            info[key] = value.clone();
+        }
+        let project = json!({
+            "info": info,
+            "releases": {version: []},
+        });
+        let project_version = json!({
+            "info": info,
+            "urls": [],
+            "vulnerabilities": [],
+        });
+        self.serve(
+            &format!("/pypi/{name}/json"),
+            project.to_string().into_bytes(),
+        );
+        self.serve(
+            &format!("/pypi/{name}/{version}/json"),
+            project_version.to_string().into_bytes(),
+        );
+    }
+
+    /// Serve a project version with one artifact for each of filenames, which are never served
+    pub fn serve_artifacts(&self, name: &str, version: &str, filenames: &[&str]) {
+        self.serve(
+            &format!("/pypi/{name}/{version}/json"),
+            self.version_json(name, version, filenames)
+                .to_string()
+                .into_bytes(),
+        );
+    }
+
+    /// The JSON api response for a project version with one artifact for each of filenames
+    pub fn version_json(&self, name: &str, version: &str, filenames: &[&str]) -> Value {
+        let urls = filenames
+            .iter()
+            .map(|f| distribution_url(&format!("{}/files/{f}", self.url), f))
+            .collect::<Vec<_>>();
+        json!({
+            "info": info(name, version),
+            "urls": urls,
+            "vulnerabilities": [],
+        })
+    }
+}
+
+fn info(name: &str, version: &str) -> Value {
+    json!({
+        "author": null,
+        "author_email": null,
+        "classifiers": [],
+        "description": null,
+        "description_content_type": null,
+        "docs_url": null,
+        "download_url": null,
+        "home_page": null,
+        "keywords": null,
+        "license": null,
+        "maintainer": null,
+        "maintainer_email": null,
+        "name": name,
+        "package_url": format!("https://pypi.org/project/{name}/"),
+        "platform": null,
+        "project_url": format!("https://pypi.org/project/{name}/"),
+        "project_urls": null,
+        "release_url": format!("https://pypi.org/project/{name}/{version}/"),
+        "requires_dist": null,
+        "requires_python": null,
+        "summary": null,
+        "version": version,
+        "yanked": false,
+        "yanked_reason": null,
+    })
+}
+
+fn distribution_url(url: &str, filename: &str) -> Value {
+    let packagetype = if filename.ends_with(".whl") {
+        "bdist_wheel"
+    } else if filename.ends_with(".egg") {
+        "bdist_egg"
+    } else {
+        "sdist"
+    };
+    json!({
+        "digests": {
+            "blake2b_256": "0000000000000000000000000000000000000000000000000000000000000000",
+            "md5": "00000000000000000000000000000000",
+            "sha256": "0000000000000000000000000000000000000000000000000000000000000000",
+        },
+        "filename": filename,
+        "md5_digest": "00000000000000000000000000000000",
+        "packagetype": packagetype,
+        "python_version": "py3",
+        "requires_python": null,
+        "size": 1024,
+        "upload_time": "2024-01-01T00:00:00",
+        "upload_time_iso_8601": "2024-01-01T00:00:00.000000Z",
+        "url": url,
+        "yanked": false,
+        "yanked_reason": null,
+    })
+}
+
+/// Build a wheel archive holding files, with a RECORD listing every one of them
+pub fn wheel(dist_info: &str, files: &[(&str, &str)]) -> Vec<u8> {
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let mut record = String::new();
+    for (name, content) in files {
+        archive.start_file(*name, options).unwrap();
+        archive.write_all(content.as_bytes()).unwrap();
+        record += &format!("{name},sha256=0,{}\n", content.len());
+    }
+    let metadata = format!("{dist_info}/METADATA");
+    archive.start_file(metadata.as_str(), options).unwrap();
+    archive
+        .write_all(b"Metadata-Version: 2.1\nName: example\nVersion: 1.0.0\n")
+        .unwrap();
+    let record_name = format!("{dist_info}/RECORD");
+    record += &format!("{metadata},sha256=0,0\n{record_name},,\n");
+    archive.start_file(record_name.as_str(), options).unwrap();
+    archive.write_all(record.as_bytes()).unwrap();
+    archive.finish().unwrap().into_inner()
+}
+
+/// Rewrite a wheel as a streaming zip writer would, with each file's sizes left out of its local
+/// header as though they were written to a trailing data descriptor
+pub fn streamed(mut wheel: Vec<u8>) -> Vec<u8> {
+    let u16_at = |w: &[u8], at: usize| u16::from_le_bytes([w[at], w[at + 1]]) as usize;
+    let mut at = 0;
+    while wheel[at..].starts_with(&[0x50, 0x4b, 0x03, 0x04]) {
+        let compressed_size =
+            u32::from_le_bytes(wheel[at + 18..at + 22].try_into().unwrap()) as usize;
+        let next = at + 30 + u16_at(&wheel, at + 26) + u16_at(&wheel, at + 28) + compressed_size;
+        wheel[at + 6] |= 1 << 3;
+        wheel[at + 14..at + 26].fill(0);
+        at = next;
+    }
+    wheel
+}
+
+/// The kayak binary, ignoring any configuration file and looking projects up on index
+pub fn kayak(index: &str) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_kayak"));
+    command.args(["--no-config", "--index", index]);
+    command
+}
+
+/// Display fields with every detail turned off
+pub fn display_fields() -> DisplayFields {
+    DisplayFields {
+        name: false,
+        versions: false,
+        sort_by: SortBy::Version,
+        limit: None,
+        reverse: false,
+        stable_only: false,
+        time: false,
+        local_time: false,
+        date_format: None,
+        relative_time: false,
+        summary: false,
+        license: false,
+        downloads: false,
+        people: false,
+        urls: false,
+        keywords: false,
+        classifiers: false,
+        artifacts: 0,
+        describe_tags: false,
+        dependencies: false,
+        extras: None,
+        readme: 0,
+        packages: 0,
+        executables: false,
+        entry_points: false,
+        color: false,
+        emoji: true,
+        width: None,
+        order: None,
+    }
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::MockIndex;
+use kayak::config::Config;
+use serde_json::json;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn write_config(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("kayak-{}-{name}.ini", std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn config_reads_flags_and_counts() {
+    let path = write_config(
+        "values",
+        "# always display licenses and urls\nverbose = 1\nlicense = true\nurls=false\nentry_points = true\n",
+    );
+    let config = Config::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(config.get_count("verbose").unwrap(), Some(1));
+    assert_eq!(config.get_bool("license").unwrap(), Some(true));
+    assert_eq!(config.get_bool("urls").unwrap(), Some(false));
+    assert_eq!(config.get_bool("entry-points").unwrap(), Some(true));
+    assert_eq!(config.get_bool("summary").unwrap(), None);
+    assert!(config
+        .check_options(&["verbose", "license", "urls", "entry-points"])
+        .is_ok());
+}
+
+#[test]
+fn config_rejects_unknown_options_and_bad_values() {
+    let path = write_config("invalid", "lisense = true\nverbose = lots\n");
+    let config = Config::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(config.check_options(&["license", "verbose"]).is_err());
+    assert!(config.get_count("verbose").is_err());
+}
+
+#[test]
+fn command_line_overrides_conflicting_config_options() {
+    let index = MockIndex::with_projects(&[]);
+    index.serve_metadata("example", "1.0.0", json!({}));
+    let mut version = index.version_json("example", "1.0.0", &["example-1.0.0.tar.gz"]);
+    version["info"]["license"] = json!("MIT");
+    index.serve("/pypi/example/1.0.0/json", version.to_string().into_bytes());
+    let home = env::temp_dir().join(format!("kayak-{}-config-home", std::process::id()));
+    fs::create_dir_all(home.join("kayak")).unwrap();
+    let kayak = |config: &str, args: &[&str]| -> Output {
+        fs::write(home.join("kayak").join("config.ini"), config).unwrap();
+        // not common::kayak, which ignores the configuration file
+        Command::new(env!("CARGO_BIN_EXE_kayak"))
+            .args(["--format", "text", "--index", &index.url])
+            .args(args)
+            .args(["example", "1.0.0"])
+            .env("XDG_CONFIG_HOME", &home)
+            .output()
+            .unwrap()
+    };
+
+    let output = kayak("relative-time = true\n", &["--time", "--date-format", "%Y"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("2024") && !stdout.contains(" ago"),
+        "{stdout}"
+    );
+
+    let output = kayak("quiet = 1\n", &["-v"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8(output.stdout).unwrap().contains("MIT"));
+    let output = kayak("verbose = 1\n", &["-q"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!String::from_utf8(output.stdout).unwrap().contains("MIT"));
+
+    let output = kayak("relative-time = true\nlocal-time = true\n", &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("config options local-time and relative-time cannot be used together"));
+    fs::remove_dir_all(home).unwrap();
+}
@@ -0,0 +1,332 @@
+use kayak::distribution;
+
+#[test]
+fn requirement_line_unwraps_parenthesized_specifier() {
+    assert_eq!(
+        distribution::requirement_line("idna (<4,>=2.5)"),
+        "idna<4,>=2.5"
+    );
+}
+
+#[test]
+fn requirement_line_keeps_extras_and_markers() {
+    assert_eq!(
+        distribution::requirement_line("PySocks[tls] (!=1.5.7,>=1.5.6) ; extra == 'socks'"),
+        "PySocks[tls]!=1.5.7,>=1.5.6 ; extra == 'socks'"
+    );
+}
+
+#[test]
+fn requirement_line_leaves_modern_specifiers() {
+    assert_eq!(
+        distribution::requirement_line("urllib3<3,>=1.21.1"),
+        "urllib3<3,>=1.21.1"
+    );
+    assert_eq!(
+        distribution::requirement_line("certifi>=2017.4.17; python_version >= \"3.7\""),
+        "certifi>=2017.4.17; python_version >= \"3.7\""
+    );
+}
+
+#[test]
+fn split_extras_leaves_unconditional_requirements() {
+    assert_eq!(
+        distribution::split_extras("certifi>=2017.4.17; python_version >= \"3.7\""),
+        (
+            vec![],
+            "certifi>=2017.4.17; python_version >= \"3.7\"".to_string()
+        )
+    );
+    assert_eq!(
+        distribution::split_extras("idna<4,>=2.5"),
+        (vec![], "idna<4,>=2.5".to_string())
+    );
+}
+
+#[test]
+fn split_extras_removes_extra_marker() {
+    assert_eq!(
+        distribution::split_extras("PySocks!=1.5.7,>=1.5.6; extra == 'socks'"),
+        (
+            vec!["socks".to_string()],
+            "PySocks!=1.5.7,>=1.5.6".to_string()
+        )
+    );
+    assert_eq!(
+        distribution::split_extras("chardet<6,>=3.0.2; extra == \"use_chardet_on_py3\""),
+        (
+            vec!["use-chardet-on-py3".to_string()],
+            "chardet<6,>=3.0.2".to_string()
+        )
+    );
+}
+
+#[test]
+fn split_extras_keeps_other_markers() {
+    assert_eq!(
+        distribution::split_extras("tomli; python_version < \"3.11\" and extra == \"toml\""),
+        (
+            vec!["toml".to_string()],
+            "tomli; python_version < \"3.11\"".to_string()
+        )
+    );
+    assert_eq!(
+        distribution::split_extras("pytest; (extra == \"test\") and sys_platform != \"win32\""),
+        (
+            vec!["test".to_string()],
+            "pytest; sys_platform != \"win32\"".to_string()
+        )
+    );
+}
+
+#[test]
+fn split_extras_finds_every_extra() {
+    assert_eq!(
+        distribution::split_extras("coverage; extra == \"a\" or extra == \"b\""),
+        (
+            vec!["a".to_string(), "b".to_string()],
+            "coverage".to_string()
+        )
+    );
+    assert_eq!(
+        distribution::split_extras(
+            "tomli; (extra == 'a' or extra == 'B') and (python_version < '3.11' or os_name == 'nt')"
+        ),
+        (
+            vec!["a".to_string(), "b".to_string()],
+            "tomli; python_version < '3.11' or os_name == 'nt'".to_string()
+        )
+    );
+    // required without the extra too, so it cannot be filed under it
+    let either = "tomli; extra == \"a\" or python_version < \"3.11\"";
+    assert_eq!(
+        distribution::split_extras(either),
+        (vec![], either.to_string())
+    );
+    let broken = "tomli; extra == \"a\" or";
+    assert_eq!(
+        distribution::split_extras(broken),
+        (vec![], broken.to_string())
+    );
+}
+
+#[test]
+fn describe_compatibility_tag() {
+    let describe = |tag| {
+        distribution::CompatibilityTag::from_tag(tag)
+            .unwrap()
+            .describe()
+    };
+    assert_eq!(
+        describe("cp311-cp311-manylinux_2_17_x86_64"),
+        "CPython 3.11, manylinux glibc 2.17, x86_64"
+    );
+    assert_eq!(
+        describe("cp311-cp311-manylinux_2_17_x86_64.manylinux2014_x86_64"),
+        "CPython 3.11, manylinux glibc 2.17, x86_64"
+    );
+    assert_eq!(
+        describe("cp38-abi3-macosx_10_9_universal2"),
+        "CPython 3.8, stable ABI, macOS 10.9, universal2"
+    );
+    assert_eq!(
+        describe("cp312-cp312-musllinux_1_1_aarch64"),
+        "CPython 3.12, musllinux musl 1.1, aarch64"
+    );
+    assert_eq!(
+        describe("cp39-cp39-win_amd64"),
+        "CPython 3.9, Windows, amd64"
+    );
+    assert_eq!(
+        describe("py2.py3-none-any"),
+        "Python 2 or Python 3, any platform"
+    );
+}
+
+#[test]
+fn host_supports_compatible_tags() {
+    let host = distribution::Host {
+        python: Some((3, 11)),
+        platform: distribution::Platform::from_tag("manylinux_2_31_aarch64"),
+    };
+    let supports = |tag| host.supports(&distribution::CompatibilityTag::from_tag(tag).unwrap());
+    assert!(supports("py3-none-any"));
+    assert!(supports("py2.py3-none-any"));
+    assert!(supports("cp311-cp311-manylinux_2_28_aarch64"));
+    assert!(supports("cp38-abi3-manylinux2014_aarch64"));
+    assert!(supports("cp311-cp311-linux_aarch64"));
+    assert!(!supports("cp311-cp311-manylinux_2_34_aarch64"));
+    assert!(!supports("cp311-cp311-manylinux_2_17_x86_64"));
+    assert!(!supports("cp311-cp311-musllinux_1_1_aarch64"));
+    assert!(!supports("cp312-cp312-manylinux_2_17_aarch64"));
+    assert!(!supports("cp312-abi3-manylinux_2_17_aarch64"));
+    assert!(!supports("py2-none-any"));
+
+    let mac = distribution::Host {
+        python: None,
+        platform: distribution::Platform::from_tag("macosx_14_0_arm64"),
+    };
+    let supports = |tag| mac.supports(&distribution::CompatibilityTag::from_tag(tag).unwrap());
+    assert!(supports("cp38-abi3-macosx_10_9_universal2"));
+    assert!(supports("pp310-pypy310_pp73-macosx_11_0_arm64"));
+    assert!(!supports("cp311-cp311-macosx_10_9_x86_64"));
+    assert!(!supports("cp311-cp311-macosx_15_0_arm64"));
+}
+
+#[test]
+fn detection_only_fills_unknown_host_parts() {
+    let host = distribution::Host {
+        python: Some((3, 8)),
+        platform: distribution::Platform::from_tag("musllinux_1_2_x86_64"),
+    };
+    assert_eq!(host.clone().or_detected(), host);
+}
+
+#[test]
+fn platform_tags_are_parsed() {
+    let platform = |tag| distribution::Platform::from_tag(tag).unwrap();
+    assert_eq!(platform("manylinux1_i686").version, Some((2, 5)));
+    assert_eq!(platform("manylinux_2_28_x86_64").arch, "x86_64");
+    assert_eq!(platform("win32").arch, "x86");
+    assert_eq!(platform("linux_armv7l").family, "linux");
+    assert!(distribution::Platform::from_tag("manylinux_x86_64").is_none());
+    assert!(distribution::Platform::from_tag("any").is_none());
+}
+
+#[test]
+fn build_tags_are_parsed_leniently() {
+    let tag = |t| distribution::BuildTag::parse(t).unwrap();
+    assert_eq!((tag("1").number, tag("1").string.as_str()), (1, ""));
+    assert_eq!(
+        (tag("1rc1").number, tag("1rc1").string.as_str()),
+        (1, "rc1")
+    );
+    // a build tag should start with a number, but one that does not still parses
+    assert_eq!((tag("rc1").number, tag("rc1").string.as_str()), (0, "rc1"));
+    assert_eq!(tag("rc1").to_string(), "rc1");
+    assert_eq!(tag("1rc1").to_string(), "1rc1");
+    assert_eq!(tag("0rc1").to_string(), "0rc1");
+    assert_eq!(tag("007").to_string(), "007");
+    assert!(tag("007") == tag("7"));
+
+    assert!(tag("2") > tag("1rc1"));
+    assert!(tag("1rc1") > tag("1"));
+    assert!(tag("1") > tag("rc1"));
+
+    assert!(distribution::BuildTag::parse("").is_none());
+    assert!(distribution::BuildTag::parse("1+local").is_none());
+    assert!(distribution::BuildTag::parse("99999999999999999999999").is_none());
+}
+
+#[test]
+fn wheels_with_unusual_build_tags_are_parsed() {
+    let wheel =
+        distribution::WheelName::from_filename("example-1.0-dev2-py3-none-any.whl").unwrap();
+    assert_eq!(wheel.build_tag.unwrap().string, "dev2");
+    assert!(
+        distribution::WheelName::from_filename("example-1.0-py3-none-any.whl")
+            .unwrap()
+            .build_tag
+            .is_none()
+    );
+}
+
+#[test]
+fn requires_python_admits_python_versions() {
+    let admits = |spec, python| distribution::admits_python(spec, python);
+    assert_eq!(admits(">=3.9", (3, 7)), Some(false));
+    assert_eq!(admits(">=3.9", (3, 12)), Some(true));
+    assert_eq!(admits(">=3.8, <4", (3, 11)), Some(true));
+    assert_eq!(admits(">=2.7, !=3.0.*, !=3.1.*", (3, 1)), Some(false));
+    assert_eq!(admits(">=2.7, !=3.0.*, !=3.1.*", (3, 2)), Some(true));
+    assert_eq!(admits("~=3.6", (3, 10)), Some(true));
+    assert_eq!(admits("~=3.6", (4, 0)), Some(false));
+    assert_eq!(admits("==3.7.*", (3, 7)), Some(true));
+    // some patch release of 3.7 is admitted
+    assert_eq!(admits(">=3.7.1", (3, 7)), Some(true));
+    assert_eq!(admits(">3.7", (3, 7)), Some(true));
+    assert_eq!(admits("<3.7", (3, 7)), Some(false));
+    assert_eq!(admits("", (3, 7)), Some(true));
+    assert_eq!(admits("3.7", (3, 7)), None);
+    assert_eq!(admits(">=three", (3, 7)), None);
+}
+
+#[test]
+fn requires_python_is_parsed_into_canonical_clauses() {
+    let canonical = |spec| distribution::SpecifierSet::parse(spec).map(|s| s.to_string());
+    assert_eq!(canonical(">=3.8"), Some(">=3.8".to_string()));
+    assert_eq!(canonical(" >= 3.7 , <4 "), Some(">=3.7,<4".to_string()));
+    assert_eq!(canonical("!=3.9.*"), Some("!=3.9.*".to_string()));
+    assert_eq!(
+        canonical("~=3.06,>3.6.0.dev1"),
+        Some("~=3.6,>3.6.0.dev1".to_string())
+    );
+    assert_eq!(canonical(""), Some("".to_string()));
+    assert_eq!(canonical("=>3.7"), None);
+
+    let specifiers = distribution::SpecifierSet::parse(">=2.7,!=3.0.*").unwrap();
+    assert_eq!(specifiers.0.len(), 2);
+    assert_eq!(specifiers.0[1].operator, distribution::Operator::NotEqual);
+    assert_eq!(specifiers.0[1].version_pattern(), "3.0.*");
+    assert!(specifiers.admits_python((3, 1)));
+    assert!(!specifiers.admits_python((3, 0)));
+}
+
+#[test]
+fn lenient_compatibility_tags() {
+    let lenient = |tag| distribution::CompatibilityTag::from_tag_lenient(tag);
+    let exact = |tag| distribution::CompatibilityTag::from_tag(tag).unwrap();
+    assert!(lenient("CP38-ABI3-MacOSX-10-9-universal2")
+        .unwrap()
+        .matches(&exact("cp38-abi3-macosx_10_9_universal2")));
+    assert!(lenient("py3.py2-none-any")
+        .unwrap()
+        .matches(&exact("py2.py3-none-any")));
+    assert!(lenient("cp311-cp311").is_none());
+    assert!(!exact("cp311-cp311-win_amd64").matches(&exact("cp311-cp311-win32")));
+}
+
+#[test]
+fn valid_package_names_are_normalized() {
+    for (name, normalized) in [
+        ("Foo.Bar_baz", "foo-bar-baz"),
+        ("a", "a"),
+        ("9", "9"),
+        ("PyYAML", "pyyaml"),
+        ("zope.interface", "zope-interface"),
+        ("a__-._b", "a-b"),
+    ] {
+        assert_eq!(
+            distribution::normalize_package_name(name).unwrap(),
+            normalized,
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn invalid_package_names_say_why() {
+    for (name, reason) in [
+        ("", "the name is empty"),
+        ("-foo", "must start with a letter or digit, not '-'"),
+        ("_foo", "must start with a letter or digit, not '_'"),
+        ("foo-", "must end with a letter or digit, not '-'"),
+        ("foo.", "must end with a letter or digit, not '.'"),
+        (".", "must contain a letter or digit"),
+        ("-_.", "must contain a letter or digit"),
+        ("foo bar", "' ' is not allowed"),
+        ("foo\n", "'\\n' is not allowed"),
+        ("naïve", "'ï' is not allowed"),
+        ("-naïve-", "'ï' is not allowed"),
+    ] {
+        let err = distribution::normalize_package_name(name).unwrap_err();
+        assert!(
+            matches!(
+                err.downcast_ref(),
+                Some(distribution::Error::InvalidPackageName(_))
+            ),
+            "{name:?}"
+        );
+        assert!(err.to_string().contains(reason), "{name:?}: {err}");
+    }
+}
@@ -0,0 +1,275 @@
+mod common;
+
+use common::{kayak, wheel, MockIndex};
+use serde_json::json;
+
+/// The status kayak exits with when displaying the text format
+fn exit_code(index: &str, args: &[&str]) -> Option<i32> {
+    kayak(index)
+        .args(["--format", "text"])
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+}
+
+fn index() -> MockIndex {
+    let index = MockIndex::with_projects(&[]);
+    index.serve_metadata("example", "1.0.0", json!({}));
+    index
+}
+
+#[test]
+fn found_project_exits_successfully() {
+    assert_eq!(exit_code(&index().url, &["example", "1.0.0"]), Some(0));
+}
+
+#[test]
+fn index_with_an_html_root_is_still_used() {
+    let index = MockIndex::start();
+    index.serve(
+        "/",
+        b"<!DOCTYPE html><html><body>An index</body></html>".to_vec(),
+    );
+    index.serve_metadata("example", "1.0.0", json!({}));
+    let output = kayak(&index.url)
+        .args(["--format", "text", "example", "1.0.0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("could not tell which API version"));
+    assert_eq!(index.hits("/"), 0);
+    assert_eq!(index.hits("/simple/"), 1);
+}
+
+#[test]
+fn missing_project_exits_not_found() {
+    let index = index();
+    assert_eq!(exit_code(&index.url, &["missing"]), Some(3));
+    assert_eq!(exit_code(&index.url, &["example", "2.0.0"]), Some(3));
+}
+
+#[test]
+fn unreachable_index_exits_network() {
+    // nothing listens on the discard port
+    assert_eq!(exit_code("http://127.0.0.1:9", &["example"]), Some(4));
+}
+
+#[test]
+fn invalid_input_exits_usage() {
+    let index = index();
+    assert_eq!(exit_code(&index.url, &["not a name!"]), Some(2));
+    assert_eq!(
+        exit_code(&index.url, &["example", "not-a-version"]),
+        Some(2)
+    );
+    assert_eq!(exit_code(&index.url, &["--no-such-option"]), Some(2));
+    let files_as_a_line = kayak(&index.url)
+        .args(["--format", "line"])
+        .args(["--files", "example", "1.0.0"])
+        .output()
+        .unwrap();
+    assert_eq!(files_as_a_line.status.code(), Some(2));
+    let output = std::env::temp_dir().join(format!("kayak-{}-interactive", std::process::id()));
+    let interactive_to_a_file = kayak(&index.url)
+        .args(["--format", "interactive"])
+        .arg("--output")
+        .arg(&output)
+        .args(["example", "1.0.0"])
+        .output()
+        .unwrap();
+    assert_eq!(interactive_to_a_file.status.code(), Some(2));
+    assert!(!output.exists());
+    let verify_without_a_project = kayak(&index.url)
+        .args(["--format", "interactive", "--verify-download"])
+        .output()
+        .unwrap();
+    assert_eq!(verify_without_a_project.status.code(), Some(2));
+    let download_without_a_project = kayak(&index.url)
+        .args(["--format", "interactive", "--download", "."])
+        .output()
+        .unwrap();
+    assert_eq!(download_without_a_project.status.code(), Some(2));
+    let diff_without_a_project = kayak(&index.url)
+        .args(["--format", "interactive", "--diff", "1.0.0"])
+        .output()
+        .unwrap();
+    assert_eq!(diff_without_a_project.status.code(), Some(2));
+    let requirements_without_a_project = kayak(&index.url)
+        .args(["--format", "interactive", "--requirements"])
+        .output()
+        .unwrap();
+    assert_eq!(requirements_without_a_project.status.code(), Some(2));
+}
+
+#[test]
+fn names_are_normalized_without_the_network() {
+    let normalize = |name: &str| {
+        kayak("http://127.0.0.1:1")
+            .args(["--normalize-name", name])
+            .output()
+            .unwrap()
+    };
+    let output = normalize("Flask_SQLAlchemy");
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "flask-sqlalchemy\n"
+    );
+    let output = normalize("flask!");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("\"flask!\" is not a valid project name")
+    );
+}
+
+#[test]
+fn silent_mode_only_sets_exit_status() {
+    let index = index();
+    for format in ["text", "json", "yaml"] {
+        let output = kayak(&index.url)
+            .args(["-qq", "--format", format, "example", "1.0.0"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        if format == "text" {
+            assert!(output.stdout.is_empty());
+        }
+    }
+    assert_eq!(exit_code(&index.url, &["-qq", "missing"]), Some(3));
+    assert_eq!(exit_code(&index.url, &["-qq", "example", "2.0.0"]), Some(3));
+    assert_eq!(
+        exit_code(&index.url, &["-qq", "--versions", "missing"]),
+        Some(3)
+    );
+}
+
+#[test]
+fn incompatible_requires_python_is_warned() {
+    let index = MockIndex::with_projects(&[]);
+    index.serve_metadata("example", "1.0.0", json!({"requires_python": ">=3.9"}));
+    let stderr = |python| {
+        let output = kayak(&index.url)
+            .args(["--format", "text"])
+            .args(["--python-version", python, "example", "1.0.0"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        String::from_utf8(output.stderr).unwrap()
+    };
+    assert_eq!(
+        stderr("3.7"),
+        "warning: this release requires Python >=3.9; you asked about 3.7\n"
+    );
+    assert_eq!(stderr("3.12"), "");
+}
+
+#[cfg(unix)]
+#[test]
+fn incompatible_python_on_the_path_is_warned() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = std::env::temp_dir().join(format!("kayak-{}-old-python", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let python = dir.join("python3");
+    let index = MockIndex::with_projects(&[]);
+    index.serve_metadata("example", "1.0.0", json!({"requires_python": ">=3.9"}));
+    let stderr = |version: &str, args: &[&str]| {
+        std::fs::write(&python, format!("#!/bin/sh\necho Python {version}\n")).unwrap();
+        std::fs::set_permissions(&python, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let output = kayak(&index.url)
+            .args(["--format", "text"])
+            .args(args)
+            .args(["example", "1.0.0"])
+            .env("PATH", &dir)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        String::from_utf8(output.stderr).unwrap()
+    };
+    assert_eq!(
+        stderr("3.7.0", &[]),
+        "warning: this release requires Python >=3.9; the Python on the PATH is 3.7\n"
+    );
+    assert_eq!(stderr("3.12.0", &[]), "");
+    assert_eq!(stderr("3.7.0", &["--python-version", "3.12"]), "");
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn has_import_and_has_command_exit_not_provided() {
+    let index = index();
+    index.serve_project(
+        "tool",
+        "1.0.0",
+        "tool-1.0.0-py3-none-any.whl",
+        wheel(
+            "tool-1.0.0.dist-info",
+            &[
+                ("tool/__init__.py", ""),
+                ("tool-1.0.0.data/scripts/tool", "#!python"),
+            ],
+        ),
+    );
+    let check = |args: &[&str]| exit_code(&index.url, &[&["tool", "1.0.0"], args].concat());
+    assert_eq!(check(&["--has-import", "tool"]), Some(0));
+    assert_eq!(check(&["--has-command", "tool"]), Some(0));
+    assert_eq!(
+        check(&["--has-import", "tool", "--has-command", "tool"]),
+        Some(0)
+    );
+    assert_eq!(check(&["--has-import", "other"]), Some(5));
+    assert_eq!(check(&["--has-command", "other"]), Some(5));
+
+    let output = kayak(&index.url)
+        .args(["tool", "1.0.0", "--has-import", "tool"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "tool-1.0.0-py3-none-any.whl provides the import tool\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn the_host_is_only_detected_to_pick_a_wheel() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = std::env::temp_dir().join(format!("kayak-{}-host", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let marker = dir.join("detected");
+    let python = dir.join("python3");
+    std::fs::write(
+        &python,
+        format!("#!/bin/sh\n: > {}\necho Python 3.12.0\n", marker.display()),
+    )
+    .unwrap();
+    std::fs::set_permissions(&python, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let index = index();
+    let run = |args: &[&str]| {
+        kayak(&index.url)
+            .args(["--format", "text"])
+            .args(args)
+            .env("PATH", &dir)
+            .output()
+            .unwrap()
+            .status
+            .code()
+    };
+    assert_eq!(run(&["--metadata-only", "example", "1.0.0"]), Some(0));
+    assert_eq!(run(&["--normalize-name", "Example"]), Some(0));
+    assert_eq!(run(&["--json-schema"]), Some(0));
+    assert!(!marker.exists());
+
+    index.serve_project(
+        "tool",
+        "1.0.0",
+        "tool-1.0.0-py3-none-any.whl",
+        wheel("tool-1.0.0.dist-info", &[("tool/__init__.py", "")]),
+    );
+    assert_eq!(run(&["--packages", "tool", "1.0.0"]), Some(0));
+    assert!(marker.exists());
+    std::fs::remove_dir_all(dir).unwrap();
+}
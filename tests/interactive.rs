@@ -0,0 +1,455 @@
+mod common;
+
+use common::{display_fields, MockIndex};
+use kayak::ui::interactive::{
+    encode_cli, export_json, find_matches, help_page, matching_versions, parse_project_input,
+    save_export, status_line, switch_version, switch_versions_view, toggle_field, VersionPicker,
+    HELP_CONTROLS, MENU_CONTROLS,
+};
+use kayak::ui::json;
+use kayak::{Project, ProjectOptions, SortBy};
+use pep440::Version;
+use ratatui::buffer::Buffer;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+/// Keys that run handles itself, because they do not change the displayed fields
+const SESSION_KEYS: [&str; 8] = ["<SPACE>", "j", "b", "/", "i", "o", "CTRL-p", "CTRL-e"];
+
+/// Every advertised pair of keys must be handled, and the second must undo the first
+fn assert_handled(on: &str, off: &str) {
+    let on = on.chars().next().unwrap();
+    let off = off.chars().next().unwrap();
+    let mut fields = display_fields();
+    let before = format!("{fields:?}");
+    assert!(toggle_field(&mut fields, on), "{on} is not handled");
+    assert_ne!(format!("{fields:?}"), before, "{on} changes nothing");
+    assert!(toggle_field(&mut fields, off), "{off} is not handled");
+    assert_eq!(format!("{fields:?}"), before, "{off} does not undo {on}");
+}
+
+#[test]
+fn menu_controls_are_handled() {
+    for control in MENU_CONTROLS {
+        let (keys, _) = control.split_once(": ").unwrap();
+        if SESSION_KEYS.contains(&keys) {
+            continue;
+        }
+        // such as u[U] or a[A]+
+        let (on, off) = keys.trim_end_matches('+').split_once('[').unwrap();
+        assert_handled(on, off.trim_end_matches(']'));
+    }
+}
+
+#[test]
+fn help_controls_are_handled() {
+    for [name, keys, _] in HELP_CONTROLS {
+        if SESSION_KEYS.contains(&keys) {
+            continue;
+        }
+        // such as "on: u off: U" or "more: a less: A"
+        let words = keys.split_whitespace().collect::<Vec<_>>();
+        let [_, on, _, off] = words[..] else {
+            panic!("keys for {name} are not a pair: {keys}");
+        };
+        assert_handled(on, off);
+    }
+}
+
+#[test]
+fn menu_and_help_advertise_the_same_keys() {
+    for control in MENU_CONTROLS {
+        let (keys, _) = control.split_once(": ").unwrap();
+        let on = keys.split('[').next().unwrap();
+        assert!(
+            HELP_CONTROLS
+                .iter()
+                .any(|[_, help_keys, _]| help_keys == &on
+                    || help_keys.split_whitespace().nth(1) == Some(on)),
+            "{on} is in the menu but not the help"
+        );
+    }
+}
+
+#[test]
+fn matching_versions_prefers_exact_then_newest_whole_segments() {
+    // in comparison order, as from Package::ordered_versions
+    let versions = ["1.0", "1.1", "1.1.1", "1.2", "1.10", "2.0", "10.0"]
+        .into_iter()
+        .map(|v| Version::parse(v).unwrap())
+        .collect::<Vec<_>>();
+    let matched = |input| {
+        matching_versions(&versions, input)
+            .into_iter()
+            .map(|v| v.normalize())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(matched("1.1"), ["1.1", "1.1.1"]);
+    assert_eq!(matched("1"), ["1.0", "1.10", "1.2", "1.1.1", "1.1"]);
+    assert_eq!(matched("1."), ["1.10", "1.2", "1.1.1", "1.1", "1.0"]);
+    assert_eq!(matched("10"), ["10.0"]);
+    assert_eq!(
+        matched(""),
+        ["10.0", "2.0", "1.10", "1.2", "1.1.1", "1.1", "1.0"]
+    );
+    assert!(matched("3").is_empty());
+}
+
+#[test]
+fn search_matches_ignore_case_and_count_wide_characters() {
+    let buffer = Buffer::with_lines([
+        "Dependencies",
+        "  requests>=2",
+        "📦 Requests-OAuth requests",
+        "",
+    ]);
+    assert_eq!(
+        find_matches(&buffer, "REQUESTS"),
+        [(1, 2..10), (2, 3..11), (2, 18..26)]
+    );
+    assert_eq!(find_matches(&buffer, "-oauth"), [(2, 11..17)]);
+    assert!(find_matches(&buffer, "").is_empty());
+    assert!(find_matches(&buffer, "requests\n").is_empty());
+}
+
+#[test]
+fn help_pages_show_every_control_once() {
+    for height in [0, 3, 4, 10, 40, 200] {
+        let (_, pages) = help_page(height, 0);
+        let shown = (0..pages)
+            .flat_map(|page| help_page(height, page).0)
+            .collect::<Vec<_>>();
+        assert_eq!(shown, (0..HELP_CONTROLS.len()).collect::<Vec<_>>());
+    }
+    assert_eq!(help_page(4, 0), (0..1, HELP_CONTROLS.len()));
+    // a page past the last stays on the last
+    let (_, pages) = help_page(40, 0);
+    assert_eq!(help_page(40, 5), help_page(40, pages - 1));
+}
+
+#[test]
+fn toggling_on_a_field_left_out_of_the_order_displays_it() {
+    let mut fields = display_fields();
+    fields.order = Some(vec![kayak::Field::Summary]);
+    toggle_field(&mut fields, 'u');
+    assert_eq!(
+        fields.order,
+        Some(vec![kayak::Field::Summary, kayak::Field::Urls])
+    );
+    toggle_field(&mut fields, 's');
+    assert_eq!(
+        fields.order,
+        Some(vec![kayak::Field::Summary, kayak::Field::Urls])
+    );
+}
+
+#[test]
+fn leaving_the_versions_list_restores_the_details() {
+    let mut fields = display_fields();
+    fields.summary = true;
+    let before = format!("{fields:?}");
+    let mut details = None;
+
+    switch_versions_view(&mut fields, &mut details, true);
+    assert!(fields.versions);
+    toggle_field(&mut fields, 't');
+    toggle_field(&mut fields, 'S');
+    fields.sort_by = SortBy::Date;
+    switch_versions_view(&mut fields, &mut details, false);
+
+    // only the options for listing versions are kept
+    assert_eq!(fields.sort_by, SortBy::Date);
+    fields.sort_by = SortBy::Version;
+    assert_eq!(format!("{fields:?}"), before);
+}
+
+#[test]
+fn versions_list_is_exported_as_a_command_line() {
+    let mut project = Project::new(
+        "example".to_string(),
+        None,
+        None,
+        ProjectOptions {
+            index: "https://example.org".to_string(),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    fields.versions = true;
+    fields.time = true;
+    assert_eq!(
+        encode_cli(&mut project, &fields),
+        "kayak example --index https://example.org/ --versions -qq --time"
+    );
+    fields.name = true;
+    assert_eq!(
+        encode_cli(&mut project, &fields),
+        "kayak example --index https://example.org/ --versions --time"
+    );
+}
+
+#[test]
+fn hiding_dependencies_keeps_the_selected_extras() {
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        ProjectOptions {
+            index: "https://example.org".to_string(),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    fields.name = true;
+    fields.dependencies = true;
+    fields.extras = Some(vec!["test".to_string()]);
+    toggle_field(&mut fields, 'D');
+    assert_eq!(fields.extras, Some(vec!["test".to_string()]));
+    assert_eq!(
+        encode_cli(&mut project, &fields),
+        "kayak example --index https://example.org/ 1.0.0"
+    );
+    toggle_field(&mut fields, 'd');
+    assert_eq!(
+        encode_cli(&mut project, &fields),
+        "kayak example --index https://example.org/ 1.0.0 --extras test"
+    );
+}
+
+#[test]
+fn exported_command_line_keeps_the_downloads_source() {
+    let source = "https://stats.example.org/{project}";
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        ProjectOptions {
+            index: "https://example.org".to_string(),
+            downloads_source: Some(source.to_string()),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    fields.name = true;
+    fields.downloads = true;
+    assert_eq!(
+        encode_cli(&mut project, &fields),
+        format!("kayak example --index https://example.org/ 1.0.0 --downloads --downloads-source {source}")
+    );
+
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        ProjectOptions {
+            downloads_source: Some(kayak::warehouse::DOWNLOADS_URI.to_string()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        encode_cli(&mut project, &fields),
+        "kayak example 1.0.0 --downloads"
+    );
+}
+
+#[test]
+fn version_picked_from_the_list_is_exported() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", json!({}));
+    index.serve_metadata("example", "2.0.0", json!({}));
+    index.serve(
+        "/pypi/example/json",
+        json!({
+            "info": index.version_json("example", "2.0.0", &[])["info"],
+            "releases": {"1.0.0": [], "2.0.0": []},
+        })
+        .to_string()
+        .into_bytes(),
+    );
+    let mut project = Some(Project::new(
+        "example".to_string(),
+        None,
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    ));
+    let mut last_good_project = None;
+    let mut fields = display_fields();
+    fields.name = true;
+
+    let mut picker = VersionPicker::new(project.as_mut().unwrap()).unwrap();
+    assert_eq!(picker.selected().unwrap().normalize(), "2.0.0");
+    picker.select_next();
+    let version = picker.selected().unwrap().clone();
+    switch_version(&mut project, &mut last_good_project, true, &version);
+    assert_eq!(
+        encode_cli(project.as_mut().unwrap(), &fields),
+        format!("kayak example --index {}/ 1.0.0", index.url)
+    );
+    assert_eq!(
+        encode_cli(last_good_project.as_mut().unwrap(), &fields),
+        format!("kayak example --index {}/", index.url)
+    );
+
+    let mut picker = VersionPicker::new(project.as_mut().unwrap()).unwrap();
+    assert_eq!(picker.selected(), Some(&version));
+    picker.select_previous();
+    let version = picker.selected().unwrap().clone();
+    switch_version(&mut project, &mut last_good_project, true, &version);
+    assert_eq!(
+        encode_cli(project.as_mut().unwrap(), &fields),
+        format!("kayak example --index {}/ 2.0.0", index.url)
+    );
+}
+
+#[test]
+fn exported_command_line_uses_the_resolved_name() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", serde_json::json!({"name": "Example"}));
+    index.serve_metadata("old-name", "1.0.0", serde_json::json!({"name": "New_Name"}));
+    let project = |name: &str| {
+        Project::new(
+            name.to_string(),
+            None,
+            None,
+            ProjectOptions {
+                index: index.url.clone(),
+                ..Default::default()
+            },
+        )
+    };
+    let mut fields = display_fields();
+    fields.versions = true;
+
+    // the same name, however it is capitalized, was not resolved to anything else
+    let mut example = project("example");
+    example.version().unwrap();
+    assert_eq!(example.resolved_name(), None);
+    assert!(encode_cli(&mut example, &fields).starts_with("kayak example --index "));
+
+    let mut renamed = project("old-name");
+    assert_eq!(renamed.resolved_name(), None);
+    renamed.version().unwrap();
+    assert_eq!(renamed.resolved_name(), Some("New_Name"));
+    assert!(encode_cli(&mut renamed, &fields).starts_with("kayak New_Name --index "));
+}
+
+#[test]
+fn typed_projects_are_checked_before_they_are_fetched() {
+    assert_eq!(
+        parse_project_input("  requests 2.31.0 py3-none-any "),
+        Ok((
+            "requests".to_string(),
+            Some("2.31.0".to_string()),
+            Some("py3-none-any".to_string())
+        ))
+    );
+    assert_eq!(
+        parse_project_input("requests 2.31.0 sdist"),
+        Ok((
+            "requests".to_string(),
+            Some("2.31.0".to_string()),
+            Some("sdist".to_string())
+        ))
+    );
+    assert_eq!(
+        parse_project_input(" "),
+        Err("please enter the name of a package".to_string())
+    );
+    assert!(parse_project_input("-requests")
+        .unwrap_err()
+        .starts_with("\"-requests\" is not a valid project name: "));
+    assert_eq!(
+        parse_project_input("requests two"),
+        Err("\"two\" is not a valid version".to_string())
+    );
+    assert_eq!(
+        parse_project_input("requests 2.31.0 win_amd64"),
+        Ok((
+            "requests".to_string(),
+            Some("2.31.0".to_string()),
+            Some("win_amd64".to_string())
+        ))
+    );
+    assert!(parse_project_input("requests 2.31.0 sdist more").is_err());
+}
+
+#[test]
+fn status_line_describes_only_what_was_fetched() {
+    let index = MockIndex::start();
+    index.serve_artifacts("example", "1.0.0", &["example-1.0.0-py3-none-any.whl"]);
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        options.clone(),
+    );
+    let source = format!("from {}/", index.url);
+    assert_eq!(status_line(&mut project), source);
+    project.version().unwrap();
+    assert_eq!(
+        status_line(&mut project),
+        format!("example 1.0.0 | {source}")
+    );
+    project.distribution().unwrap();
+    assert_eq!(
+        status_line(&mut project),
+        format!("example 1.0.0 | py3-none-any (picked for this platform) | {source}")
+    );
+
+    let mut chosen = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        Some("py3-none-any".to_string()),
+        options,
+    );
+    chosen.distribution().unwrap();
+    assert_eq!(
+        status_line(&mut chosen),
+        format!("example 1.0.0 | py3-none-any | {source}")
+    );
+}
+
+#[test]
+fn exports_never_overwrite_a_file() {
+    let path = std::env::temp_dir().join(format!("kayak-{}-export.json", std::process::id()));
+    let saved = save_export(&path, "{}").unwrap();
+    assert!(saved.contains("saved the displayed details"), "{saved}");
+    let err = save_export(&path, "[]").unwrap_err();
+    assert!(err.contains("already exists"), "{err}");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "{}");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn displayed_details_are_exported_as_json() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", json!({"summary": "An example"}));
+    let mut project = Project::new(
+        "Example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    fields.summary = true;
+    let (path, report) = export_json(&mut project, &fields).unwrap();
+    assert_eq!(path, PathBuf::from("example-1.0.0.json"));
+    assert_eq!(
+        serde_json::from_str::<Value>(&report).unwrap(),
+        json::report(&mut project, &fields).unwrap()
+    );
+    assert_eq!(
+        serde_json::from_str::<Value>(&report).unwrap()["summary"],
+        "An example"
+    );
+}
@@ -0,0 +1,434 @@
+mod common;
+
+use common::{display_fields, kayak, wheel, MockIndex};
+use kayak::ui::json;
+use kayak::{Field, Project, ProjectOptions};
+use serde_json::{json, Value};
+
+#[test]
+fn display_lines_continues_past_missing_projects() {
+    let index = MockIndex::start();
+    index.serve_metadata("first", "1.0.0", json!({}));
+    index.serve_metadata("second", "2.0.0", json!({}));
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let projects = ["first", "missing", "second"]
+        .into_iter()
+        .map(|name| Project::new(name.to_string(), None, None, options.clone()))
+        .collect();
+
+    let mut fields = display_fields();
+    fields.name = true;
+
+    let mut out = Vec::new();
+    json::display_lines(projects, fields, &mut out).unwrap();
+    let lines = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str::<Value>(l).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["name"], "first");
+    assert!(lines[0].get("error").is_none());
+    assert_eq!(lines[1]["name"], "missing");
+    assert!(lines[1]["error"].as_str().unwrap().contains("NotFound"));
+    assert_eq!(lines[2]["name"], "second");
+}
+
+/// Whether value matches schema, for the subset of JSON Schema that json::schema uses
+fn conforms(value: &Value, schema: &Value, root: &Value) -> bool {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.trim_start_matches("#/$defs/");
+        return conforms(value, &root["$defs"][name], root);
+    }
+    if let Some(options) = schema["oneOf"].as_array() {
+        return options.iter().filter(|s| conforms(value, s, root)).count() == 1;
+    }
+    if let Some(constant) = schema.get("const") {
+        return value == constant;
+    }
+    if let Some(values) = schema["enum"].as_array() {
+        return values.contains(value);
+    }
+    match schema["type"].as_str() {
+        Some("string") => value.is_string(),
+        Some("integer") => value.is_u64() || value.is_i64(),
+        Some("array") => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(|i| conforms(i, &schema["items"], root))),
+        Some("object") => {
+            let Some(object) = value.as_object() else {
+                return false;
+            };
+            let properties = schema["properties"].as_object().unwrap();
+            let required = schema["required"].as_array().cloned().unwrap_or_default();
+            required
+                .iter()
+                .all(|r| object.contains_key(r.as_str().unwrap()))
+                && object.iter().all(|(k, v)| {
+                    properties
+                        .get(k)
+                        .is_some_and(|property| conforms(v, property, root))
+                })
+        }
+        other => panic!("unexpected schema type {other:?}"),
+    }
+}
+
+#[test]
+fn schema_describes_every_report_field() {
+    let schema = json::schema();
+    let strings = || Some(vec!["a".to_string()]);
+    let full = json::Report {
+        name: Some("name".to_string()),
+        version: Some("1.0".to_string()),
+        yanked: true,
+        yanked_reason: Some("broken".to_string()),
+        distribution: Some("sdist".to_string()),
+        time: Some("2020-01-01T00:00:00".to_string()),
+        license: Some("MIT".to_string()),
+        copyright: Some("someone".to_string()),
+        people: Some(vec![json::PersonReport {
+            roles: vec!["author".to_string(), "maintainer".to_string()],
+            name: Some("Someone".to_string()),
+            email: Some("someone@example.com".to_string()),
+        }]),
+        summary: Some("summary".to_string()),
+        downloads: Some(json::DownloadsReport {
+            last_day: 1,
+            last_week: 7,
+            last_month: 30,
+        }),
+        urls: Some(vec![json::UrlReport {
+            label: "Homepage".to_string(),
+            url: "https://example.com".to_string(),
+        }]),
+        keywords: strings(),
+        classifiers: strings(),
+        unrecognized_classifiers: strings(),
+        artifacts: Some(vec![json::ArtifactReport {
+            packagetype: "sdist".to_string(),
+            filename: "name-1.0.tar.gz".to_string(),
+            tag: Some("py3-none-any".to_string()),
+            url: Some("https://example.com/name-1.0.tar.gz".to_string()),
+            upload_time: Some("2020-01-01T00:00:00".to_string()),
+            sha256: Some("0".repeat(64)),
+        }]),
+        dependencies: Some(json::DependenciesReport {
+            requires_python: Some(">=3.8".to_string()),
+            python_specifiers: Some(vec![json::SpecifierReport {
+                operator: ">=".to_string(),
+                version: "3.8".to_string(),
+            }]),
+            requires_dist: vec!["other".to_string()],
+            extras: vec![json::ExtraReport {
+                name: "test".to_string(),
+                requires_dist: vec!["pytest".to_string()],
+            }],
+            other_extras: vec!["docs".to_string()],
+        }),
+        packages: strings(),
+        package_kinds: Some(vec![json::PackageKindReport {
+            name: "a".to_string(),
+            kind: "package".to_string(),
+        }]),
+        executables: strings(),
+        entry_points: Some(vec![json::EntryPointGroupReport {
+            group: "console_scripts".to_string(),
+            entry_points: vec![json::EntryPointReport {
+                name: "a".to_string(),
+                object: "a:main".to_string(),
+            }],
+        }]),
+        readme: Some("readme".to_string()),
+    };
+    let full = serde_json::to_value(full).unwrap();
+    assert!(conforms(&full, &schema, &schema));
+    let properties = schema["$defs"]["report"]["properties"].as_object().unwrap();
+    assert_eq!(
+        full.as_object().unwrap().keys().collect::<Vec<_>>(),
+        properties.keys().collect::<Vec<_>>()
+    );
+
+    let empty = serde_json::to_value(json::Report::default()).unwrap();
+    assert!(conforms(&empty, &schema, &schema));
+    let versions = serde_json::to_value(json::VersionsReport {
+        name: None,
+        versions: vec!["1.0".to_string()],
+        yanked: vec!["1.0".to_string()],
+        vulnerable: vec!["1.0".to_string()],
+        releases: vec![json::ReleaseReport {
+            version: "1.0".to_string(),
+            yanked: true,
+            yanked_reason: Some("broken".to_string()),
+            vulnerable: true,
+            upload_time: Some("2020-01-01T00:00:00Z".to_string()),
+        }],
+    })
+    .unwrap();
+    assert!(conforms(&versions, &schema, &schema));
+    let files = serde_json::to_value(json::FilesReport {
+        name: Some("name".to_string()),
+        version: Some("1.0".to_string()),
+        files: vec![json::InstalledFileReport {
+            location: "scripts".to_string(),
+            path: "a".to_string(),
+            size: Some(1),
+        }],
+    })
+    .unwrap();
+    assert!(conforms(&files, &schema, &schema));
+    let size = serde_json::to_value(json::SizeReport {
+        name: Some("name".to_string()),
+        version: Some("1.0".to_string()),
+        download_size: Some(1),
+        installed_size: 1,
+        installed_files: 1,
+        largest_files: vec![json::InstalledFileReport {
+            location: "site-packages".to_string(),
+            path: "a".to_string(),
+            size: Some(1),
+        }],
+    })
+    .unwrap();
+    assert!(conforms(&size, &schema, &schema));
+    assert!(!conforms(&json!({"unknown": 1}), &schema, &schema));
+}
+
+#[test]
+fn json_schema_needs_no_project() {
+    let schema = |args: &[&str]| {
+        kayak("http://127.0.0.1:1")
+            .arg("--json-schema")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let output = schema(&[]);
+    assert!(output.status.success());
+    let printed: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(printed, json::schema());
+
+    let path = std::env::temp_dir().join(format!("kayak-{}-schema.json", std::process::id()));
+    let output = schema(&["--output", path.to_str().unwrap()]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let written: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(written, json::schema());
+}
+
+#[test]
+fn real_output_conforms_to_the_schema() {
+    let index = MockIndex::with_projects(&[]);
+    let filename = "example-1.0.0-py3-none-any.whl";
+    index.serve_project(
+        "example",
+        "1.0.0",
+        filename,
+        wheel(
+            "example-1.0.0.dist-info",
+            &[
+                ("example/__init__.py", ""),
+                ("example-1.0.0.data/scripts/tool", "#!python"),
+                (
+                    "example-1.0.0.dist-info/entry_points.txt",
+                    "[console_scripts]\nexample = example:main\n",
+                ),
+            ],
+        ),
+    );
+    let mut version = index.version_json("example", "1.0.0", &[filename]);
+    for (key, value) in [
+        ("summary", json!("An example")),
+        ("license", json!("MIT")),
+        ("author", json!("Someone")),
+        ("author_email", json!("someone@example.com")),
+        ("keywords", json!("one,two")),
+        (
+            "classifiers",
+            json!(["Programming Language :: Python", "Not :: A Classifier"]),
+        ),
+        ("project_urls", json!({"Source": "https://example.com/src"})),
+        ("requires_python", json!(">=3.8")),
+        ("requires_dist", json!(["other", "pytest; extra == 'test'"])),
+        ("description", json!("# Example")),
+        ("description_content_type", json!("text/markdown")),
+    ] {
+        version["info"][key] = value;
+    }
+    index.serve("/pypi/example/1.0.0/json", version.to_string().into_bytes());
+    let run = |args: &[&str]| -> Value {
+        let output = kayak(&index.url)
+            .args(["--format", "json"])
+            .args(args)
+            .output()
+            .unwrap();
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+    let schema = json::schema();
+    let every_field = [
+        "-tslukcdrpeg",
+        "-aa",
+        "-pp",
+        "--people",
+        "--describe-tags",
+        "example",
+        "1.0.0",
+    ];
+    for args in [
+        &every_field[..],
+        &["--versions", "--time", "example"],
+        &["--files", "example", "1.0.0"],
+        &["--size-report", "example", "1.0.0"],
+    ] {
+        let report = run(args);
+        assert!(conforms(&report, &schema, &schema), "{args:?}: {report:#}");
+    }
+}
+
+#[test]
+fn report_keys_follow_the_given_order() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({"summary": "An example", "license": "MIT"}),
+    );
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    fields.name = true;
+    fields.summary = true;
+    fields.license = true;
+    fields.order = Some(vec![Field::Summary, Field::License, Field::Name]);
+    let report = json::report(&mut project, &fields).unwrap();
+    assert_eq!(
+        report.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["summary", "license", "name", "version"]
+    );
+}
+
+#[test]
+fn raw_metadata_keeps_every_field() {
+    let index = MockIndex::with_projects(&[]);
+    let mut response = index.version_json("example", "1.0.0", &[]);
+    response["last_serial"] = json!(42);
+    index.serve(
+        "/pypi/example/1.0.0/json",
+        response.to_string().into_bytes(),
+    );
+    let output = kayak(&index.url)
+        .args(["--raw-metadata", "Example", "1.0.0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let printed: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(printed, response);
+}
+
+#[test]
+fn versions_are_reported_with_their_details() {
+    let index = MockIndex::start();
+    let files = |version: &str| {
+        index.version_json("example", version, &[&format!("example-{version}.tar.gz")])["urls"]
+            .clone()
+    };
+    let mut yanked = files("2.0");
+    yanked[0]["yanked"] = json!(true);
+    yanked[0]["yanked_reason"] = json!("broken");
+    index.serve(
+        "/pypi/example/json",
+        json!({
+            "info": index.version_json("example", "2.0", &[])["info"],
+            "releases": {"1.0": files("1.0"), "2.0": yanked, "0.1": []},
+            "vulnerabilities": [{
+                "id": "PYSEC-0000-0",
+                "source": "osv",
+                "link": "https://osv.dev/vulnerability/PYSEC-0000-0",
+                "aliases": [],
+                "details": "an example advisory",
+                "summary": null,
+                "fixed_in": ["2.1"],
+                "withdrawn": null,
+            }],
+        })
+        .to_string()
+        .into_bytes(),
+    );
+    let mut project = Project::new(
+        "example".to_string(),
+        None,
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    fields.versions = true;
+    fields.time = true;
+    fields.reverse = true;
+    let report = json::report(&mut project, &fields).unwrap();
+    assert_eq!(
+        report["releases"],
+        json!([
+            {"version": "0.1"},
+            {"version": "1.0", "upload_time": "2024-01-01T00:00:00Z"},
+            {
+                "version": "2.0",
+                "yanked": true,
+                "yanked_reason": "broken",
+                "vulnerable": true,
+                "upload_time": "2024-01-01T00:00:00Z",
+            },
+        ])
+    );
+    assert!(conforms(&report, &json::schema(), &json::schema()));
+}
+
+#[test]
+fn times_are_rfc_3339_whatever_the_display_options() {
+    let index = MockIndex::start();
+    index.serve_artifacts("example", "1.0.0", &["example-1.0.0.tar.gz"]);
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    fields.time = true;
+    fields.artifacts = 4;
+    fields.local_time = true;
+    fields.date_format = Some("%d %b %Y".to_string());
+    let report = json::report(&mut project, &fields).unwrap();
+    assert_eq!(report["time"], "2024-01-01T00:00:00Z");
+    assert_eq!(
+        report["artifacts"][0]["upload_time"],
+        "2024-01-01T00:00:00Z"
+    );
+
+    fields.local_time = false;
+    fields.date_format = None;
+    fields.relative_time = true;
+    let report = json::report(&mut project, &fields).unwrap();
+    assert_eq!(report["time"], "2024-01-01T00:00:00Z");
+    assert_eq!(
+        report["artifacts"][0]["upload_time"],
+        "2024-01-01T00:00:00Z"
+    );
+}
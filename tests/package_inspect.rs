@@ -0,0 +1,346 @@
+mod common;
+
+use common::{kayak, streamed, wheel, MockIndex};
+use kayak::package_inspect;
+use std::collections::HashSet;
+
+fn inspect(files: &[(&str, &str)]) -> package_inspect::Package {
+    let index = MockIndex::start();
+    index.serve(
+        "/example-1.0.0-py3-none-any.whl",
+        wheel("example-1.0.0.dist-info", files),
+    );
+    package_inspect::fetch(&format!("{}/example-1.0.0-py3-none-any.whl", index.url)).unwrap()
+}
+
+fn names(names: &[&str]) -> HashSet<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn provides_packages_from_wheel_root() {
+    let package = inspect(&[
+        ("example/__init__.py", ""),
+        ("example/core.py", ""),
+        ("example_helper.py", ""),
+    ]);
+    assert_eq!(
+        package.provides_packages(),
+        names(&["example", "example_helper"])
+    );
+}
+
+#[test]
+fn provides_packages_from_purelib_and_platlib() {
+    let package = inspect(&[
+        ("example-1.0.0.data/purelib/example/__init__.py", ""),
+        ("example-1.0.0.data/platlib/example_ext/__init__.py", ""),
+    ]);
+    assert_eq!(
+        package.provides_packages(),
+        names(&["example", "example_ext"])
+    );
+}
+
+#[test]
+fn provides_packages_excludes_other_data() {
+    let package = inspect(&[
+        ("example/__init__.py", ""),
+        ("example-1.0.0.data/scripts/example", "#!python"),
+        ("example-1.0.0.data/headers/example.h", ""),
+        ("example-1.0.0.data/data/share/example.txt", ""),
+    ]);
+    assert_eq!(package.provides_packages(), names(&["example"]));
+}
+
+#[test]
+fn provides_import_kinds_classifies_names() {
+    let package = inspect(&[
+        ("example/__init__.py", ""),
+        ("example/core.py", ""),
+        ("example_helper.py", ""),
+        ("google/example/__init__.py", ""),
+    ]);
+    let kinds = package.provides_import_kinds();
+    assert_eq!(kinds.len(), 3);
+    assert_eq!(kinds["example"], package_inspect::ImportKind::Package);
+    assert_eq!(kinds["example_helper"], package_inspect::ImportKind::Module);
+    assert_eq!(kinds["google"], package_inspect::ImportKind::Namespace);
+}
+
+#[test]
+fn entry_point_groups_exclude_scripts() {
+    let package = inspect(&[
+        ("example/__init__.py", ""),
+        (
+            "example-1.0.0.dist-info/entry_points.txt",
+            "[console_scripts]\n\
+             example = example.cli:main\n\
+             \n\
+             [pytest11]\n\
+             example = example.plugin\n\
+             \n\
+             [example.formats]\n\
+             yaml = example.formats:Yaml [yaml]\n\
+             json = example.formats:Json\n",
+        ),
+    ]);
+    assert_eq!(
+        package.entry_point_groups(),
+        vec![
+            (
+                "example.formats".to_string(),
+                vec![
+                    ("json".to_string(), "example.formats:Json".to_string()),
+                    (
+                        "yaml".to_string(),
+                        "example.formats:Yaml [yaml]".to_string()
+                    ),
+                ]
+            ),
+            (
+                "pytest11".to_string(),
+                vec![("example".to_string(), "example.plugin".to_string())]
+            ),
+        ]
+    );
+    assert_eq!(package.console_scripts(), vec!["example".to_string()]);
+}
+
+#[test]
+fn fetch_selects_dist_info_named_for_wheel() {
+    // a stray .dist-info from another distribution, listed before this wheel's own
+    let package = inspect(&[
+        ("example/__init__.py", ""),
+        (
+            "vendored-2.0.dist-info/METADATA",
+            "Metadata-Version: 2.1\nName: vendored\nVersion: 2.0\n",
+        ),
+        (
+            "vendored-2.0.dist-info/RECORD",
+            "vendored/__init__.py,sha256=0,0\n",
+        ),
+    ]);
+    assert_eq!(package.provides_packages(), names(&["example"]));
+}
+
+#[test]
+fn fetch_rejects_several_unrelated_dist_infos() {
+    let index = MockIndex::start();
+    index.serve(
+        "/example-1.0.0-py3-none-any.whl",
+        wheel(
+            "unrelated-1.0.dist-info",
+            &[
+                ("example/__init__.py", ""),
+                (
+                    "vendored-2.0.dist-info/METADATA",
+                    "Metadata-Version: 2.1\nName: vendored\nVersion: 2.0\n",
+                ),
+                ("vendored-2.0.dist-info/RECORD", ""),
+            ],
+        ),
+    );
+    let error = package_inspect::fetch(&format!("{}/example-1.0.0-py3-none-any.whl", index.url))
+        .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("2 .dist-info directories, but none for example-1.0.0"));
+}
+
+#[test]
+fn fetch_wheel_without_sizes_in_local_headers() {
+    let index = MockIndex::start();
+    index.serve(
+        "/example-1.0.0-py3-none-any.whl",
+        streamed(wheel(
+            "example-1.0.0.dist-info",
+            &[("example/__init__.py", "")],
+        )),
+    );
+    let package =
+        package_inspect::fetch(&format!("{}/example-1.0.0-py3-none-any.whl", index.url)).unwrap();
+    assert_eq!(package.provides_packages(), names(&["example"]));
+}
+
+#[test]
+fn failed_requests_are_not_downloaded_again() {
+    let index = MockIndex::start();
+    let url = format!("{}/example-1.0.0-py3-none-any.whl", index.url);
+    assert!(package_inspect::fetch(&url).is_err());
+    assert_eq!(index.hits("/example-1.0.0-py3-none-any.whl"), 1);
+}
+
+#[test]
+fn inspect_url_displays_a_wheel_off_any_index() {
+    let index = MockIndex::start();
+    index.serve(
+        "/builds/example-1.0.0-py3-none-any.whl",
+        wheel(
+            "example-1.0.0.dist-info",
+            &[
+                ("example/__init__.py", ""),
+                ("example-1.0.0.data/scripts/example", "#!python"),
+            ],
+        ),
+    );
+    let inspect_url = |url: &str| {
+        kayak("http://127.0.0.1:1")
+            .args(["--inspect-url", url])
+            .output()
+            .unwrap()
+    };
+
+    let output = inspect_url(&format!(
+        "{}/builds/example-1.0.0-py3-none-any.whl",
+        index.url
+    ));
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "example@1.0.0\nImportable Packages\n  example\nExecutable Commands\n  example (script)\n"
+    );
+
+    let output = inspect_url(&format!("{}/builds/example-1.0.0.tar.gz", index.url));
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("is not the filename of a wheel"));
+    assert_eq!(index.hits("/builds/example-1.0.0.tar.gz"), 0);
+}
+
+#[test]
+fn installed_files_are_grouped_by_location() {
+    let package = inspect(&[
+        ("example/__init__.py", "pass\n"),
+        ("example-1.0.0.data/platlib/example_ext.so", ""),
+        ("example-1.0.0.data/scripts/example", "#!python\n"),
+        ("example-1.0.0.data/headers/example.h", ""),
+        ("example-1.0.0.data/data/share/example.1", ""),
+        ("example-1.0.0.data/unknown/ignored", ""),
+    ]);
+    let mut out = Vec::new();
+    kayak::ui::text::display_files(&package, &common::display_fields(), &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "Installed Files
+  site-packages
+    example-1.0.0.dist-info/METADATA  0 bytes
+    example-1.0.0.dist-info/RECORD
+    example/__init__.py  5 bytes
+    example_ext.so  0 bytes
+  scripts
+    example  9 bytes
+  headers
+    example.h  0 bytes
+  data
+    share/example.1  0 bytes
+"
+    );
+
+    let report = serde_json::to_value(kayak::ui::json::report_files(
+        &package,
+        &common::display_fields(),
+    ))
+    .unwrap();
+    assert_eq!(
+        report["files"][1],
+        serde_json::json!({"location": "site-packages", "path": "example-1.0.0.dist-info/RECORD"})
+    );
+    assert_eq!(
+        report["files"][4],
+        serde_json::json!({"location": "scripts", "path": "example", "size": 9})
+    );
+    let mut out = Vec::new();
+    kayak::ui::yaml::display_files(&package, &common::display_fields(), &mut out).unwrap();
+    assert!(String::from_utf8(out).unwrap().starts_with(
+        "files:\n- location: site-packages\n  path: example-1.0.0.dist-info/METADATA\n  size: 0\n"
+    ));
+}
+
+#[test]
+fn size_report_totals_the_installed_files() {
+    let index = MockIndex::with_projects(&[]);
+    let core = "x".repeat(2048);
+    index.serve_project(
+        "example",
+        "1.0.0",
+        "example-1.0.0-py3-none-any.whl",
+        wheel(
+            "example-1.0.0.dist-info",
+            &[
+                ("example/__init__.py", "pass\n"),
+                ("example/core.py", &core),
+                ("example-1.0.0.data/scripts/example", "#!python\n"),
+            ],
+        ),
+    );
+    let output = kayak(&index.url)
+        .arg("example")
+        .args(["--size-report", "--largest", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "example@1.0.0
+Download Size
+  1.0 KiB
+Installed Size
+  2.0 KiB in 5 files
+Largest Files
+  2.0 KiB  example/core.py
+  9 B  example (scripts)
+"
+    );
+
+    let output = kayak(&index.url)
+        .args(["--format", "json", "example"])
+        .args(["--size-report", "--largest", "1"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap(),
+        serde_json::json!({
+            "name": "example",
+            "version": "1.0.0",
+            "download_size": 1024,
+            "installed_size": 2062,
+            "installed_files": 5,
+            "largest_files": [
+                {"location": "site-packages", "path": "example/core.py", "size": 2048},
+            ],
+        })
+    );
+}
+
+#[test]
+fn executables_are_sorted_and_listed_once_with_how_they_are_installed() {
+    let package = inspect(&[
+        ("example/__init__.py", ""),
+        ("example-1.0.0.data/scripts/zeta", ""),
+        ("example-1.0.0.data/scripts/example", ""),
+        (
+            "example-1.0.0.dist-info/entry_points.txt",
+            "[console_scripts]\n\
+             example = example.cli:main\n\
+             alpha = example.cli:alpha\n",
+        ),
+    ]);
+    assert_eq!(package.executables(), ["alpha", "example", "zeta"]);
+    assert_eq!(
+        package.executable_kinds(),
+        [
+            (
+                "alpha".to_string(),
+                package_inspect::ExecutableKind::EntryPoint
+            ),
+            (
+                "example".to_string(),
+                package_inspect::ExecutableKind::ScriptAndEntryPoint
+            ),
+            ("zeta".to_string(), package_inspect::ExecutableKind::Script),
+        ]
+    );
+}
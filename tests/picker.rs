@@ -0,0 +1,633 @@
+mod common;
+
+use common::{display_fields, wheel, MockIndex};
+use kayak::distribution::{Host, Platform};
+use kayak::ui::json;
+use kayak::{Api, Project, ProjectOptions};
+
+#[test]
+fn toggling_packages_and_executables_inspects_wheel_once() {
+    let index = MockIndex::start();
+    let wheel_path = index.serve_project(
+        "example",
+        "1.0.0",
+        "example-1.0.0-py3-none-any.whl",
+        wheel(
+            "example-1.0.0.dist-info",
+            &[
+                ("example/__init__.py", ""),
+                ("example-1.0.0.data/scripts/example", "#!python"),
+            ],
+        ),
+    );
+    let mut project = Project::new(
+        "example".to_string(),
+        None,
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+
+    let mut fields = display_fields();
+    for (packages, executables) in [(true, false), (false, true), (true, true), (false, false)] {
+        fields.packages = packages.into();
+        fields.executables = executables;
+        json::report(&mut project, &fields).unwrap();
+    }
+    assert_eq!(index.hits(&wheel_path), 1);
+    assert_eq!(index.hits("/pypi/example/1.0.0/json"), 1);
+}
+
+#[test]
+fn metadata_only_never_fetches_distributions() {
+    let index = MockIndex::start();
+    let wheel_path = index.serve_project(
+        "example",
+        "1.0.0",
+        "example-1.0.0-py3-none-any.whl",
+        wheel("example-1.0.0.dist-info", &[("example/__init__.py", "")]),
+    );
+    let mut project = Project::new(
+        "example".to_string(),
+        None,
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            metadata_only: true,
+            ..Default::default()
+        },
+    );
+
+    let report = project.report().unwrap();
+    assert_eq!(report.version, "1.0.0");
+    assert!(report.packages.is_none());
+    assert!(project.import_package().is_err());
+    assert_eq!(index.hits(&wheel_path), 0);
+}
+
+#[test]
+fn report_matches_the_json_report() {
+    let index = MockIndex::start();
+    let mut version = index.version_json("example", "1.0.0", &["example-1.0.0-py3-none-any.whl"]);
+    version["info"]["project_urls"] = serde_json::json!({
+        "Source": "https://example.com/source",
+        "Documentation": "https://example.com/docs",
+        "Changelog": "https://example.com/changes",
+    });
+    index.serve("/pypi/example/1.0.0/json", version.to_string().into_bytes());
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    fields.urls = true;
+    let json_report = json::report(&mut project, &fields).unwrap();
+
+    // the wheel is never served, and failing to inspect it fails the whole report
+    let err = project.report().unwrap_err();
+    assert!(format!("{err:#}").contains("404"), "{err:#}");
+
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            metadata_only: true,
+            ..Default::default()
+        },
+    );
+    let report = serde_json::to_value(project.report().unwrap()).unwrap();
+    assert_eq!(report["urls"], json_report["urls"]);
+    assert_eq!(
+        report["artifacts"][0]["upload_time"],
+        "2024-01-01T00:00:00Z"
+    );
+}
+
+#[test]
+fn best_wheel_is_the_one_for_the_host() {
+    let index = MockIndex::start();
+    index.serve_artifacts(
+        "example",
+        "1.0.0",
+        &[
+            "example-1.0.0.tar.gz",
+            "example-1.0.0-py3-none-any.whl",
+            "example-1.0.0-cp311-cp311-manylinux_2_17_x86_64.whl",
+            "example-1.0.0-cp311-cp311-win_amd64.whl",
+        ],
+    );
+    let best = |host: Option<Host>| {
+        let mut project = Project::new(
+            "example".to_string(),
+            Some("1.0.0".to_string()),
+            None,
+            ProjectOptions {
+                index: index.url.clone(),
+                host,
+                ..Default::default()
+            },
+        );
+        project.distribution().unwrap().filename.clone()
+    };
+    let host = |python, platform| Host {
+        python,
+        platform: Platform::from_tag(platform),
+    };
+
+    // generic wheels are preferred when nothing is known of the host
+    assert_eq!(best(None), "example-1.0.0-py3-none-any.whl");
+    assert_eq!(
+        best(Some(host(Some((3, 11)), "manylinux_2_36_x86_64"))),
+        "example-1.0.0-cp311-cp311-manylinux_2_17_x86_64.whl"
+    );
+    assert_eq!(
+        best(Some(host(None, "win_amd64"))),
+        "example-1.0.0-cp311-cp311-win_amd64.whl"
+    );
+    // no platform wheel can be installed, so the generic wheel is the best one that can
+    assert_eq!(
+        best(Some(host(Some((3, 12)), "manylinux_2_36_x86_64"))),
+        "example-1.0.0-py3-none-any.whl"
+    );
+    assert_eq!(
+        best(Some(host(Some((3, 11)), "manylinux_2_12_x86_64"))),
+        "example-1.0.0-py3-none-any.whl"
+    );
+}
+
+#[test]
+fn prefetch_fetches_everything_displayed_once() {
+    let index = MockIndex::start();
+    let wheel_path = index.serve_project(
+        "example",
+        "1.0.0",
+        "example-1.0.0-py3-none-any.whl",
+        b"not a zip".to_vec(),
+    );
+    let mut project = Project::new(
+        "example".to_string(),
+        None,
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    let mut fields = display_fields();
+    assert!(project.needs_fetch(&fields));
+    project.prefetch(&fields).unwrap();
+    assert!(!project.needs_fetch(&fields));
+
+    fields.packages = 1;
+    assert!(project.needs_fetch(&fields));
+    // a distribution that cannot be inspected is not an error until it is displayed
+    project.prefetch(&fields).unwrap();
+    assert!(!project.needs_fetch(&fields));
+    let downloads = index.hits(&wheel_path);
+    assert!(downloads > 0);
+    assert!(project.import_package().is_err());
+    assert!(project.import_package().is_err());
+    assert_eq!(index.hits(&wheel_path), downloads);
+}
+
+#[test]
+fn distribution_selector_is_lenient() {
+    let index = MockIndex::start();
+    index.serve_artifacts(
+        "example",
+        "1.0.0",
+        &[
+            "example-1.0.0-py2.py3-none-any.whl",
+            "example-1.0.0-cp311-cp311-manylinux_2_17_x86_64.whl",
+            "example-1.0.0-cp311-cp311-win_amd64.whl",
+        ],
+    );
+    let select = |dist: &str| {
+        let mut project = Project::new(
+            "example".to_string(),
+            Some("1.0.0".to_string()),
+            Some(dist.to_string()),
+            ProjectOptions {
+                index: index.url.clone(),
+                ..Default::default()
+            },
+        );
+        project
+            .distribution()
+            .map(|d| d.filename.clone())
+            .map_err(|e| format!("{e:#}"))
+    };
+    assert_eq!(
+        select("CP311-cp311-manylinux-2-17-x86-64"),
+        Ok("example-1.0.0-cp311-cp311-manylinux_2_17_x86_64.whl".to_string())
+    );
+    assert_eq!(
+        select("py3.py2-none-any"),
+        Ok("example-1.0.0-py2.py3-none-any.whl".to_string())
+    );
+    assert_eq!(
+        select("example-1.0.0-cp311-cp311-win_amd64.whl"),
+        Ok("example-1.0.0-cp311-cp311-win_amd64.whl".to_string())
+    );
+    let missing = select("cp312-cp312-win_amd64").unwrap_err();
+    assert!(
+        missing.contains(
+            "no wheel matching cp312-cp312-win_amd64; available: cp311-cp311-win_amd64, "
+        ),
+        "{missing}"
+    );
+}
+
+#[test]
+fn distribution_selector_may_be_part_of_a_filename() {
+    let index = MockIndex::start();
+    index.serve_artifacts(
+        "example",
+        "1.0.0",
+        &[
+            "example-1.0.0-1-cp312-cp312-win_amd64.whl",
+            "example-1.0.0-2-cp312-cp312-win_amd64.whl",
+            "example-1.0.0-cp311-cp311-win_amd64.whl",
+            "example-1.0.0-cp312-cp312-manylinux_2_17_x86_64.whl",
+        ],
+    );
+    let select = |dist: &str| {
+        let mut project = Project::new(
+            "example".to_string(),
+            Some("1.0.0".to_string()),
+            Some(dist.to_string()),
+            ProjectOptions {
+                index: index.url.clone(),
+                ..Default::default()
+            },
+        );
+        project
+            .distribution()
+            .map(|d| d.filename.clone())
+            .map_err(|e| format!("{e:#}"))
+    };
+    assert_eq!(
+        select("cp311"),
+        Ok("example-1.0.0-cp311-cp311-win_amd64.whl".to_string())
+    );
+    assert_eq!(
+        select("cp312-CP312-win_amd64"),
+        Ok("example-1.0.0-2-cp312-cp312-win_amd64.whl".to_string())
+    );
+    assert_eq!(
+        select("MANYLINUX"),
+        Ok("example-1.0.0-cp312-cp312-manylinux_2_17_x86_64.whl".to_string())
+    );
+    assert_eq!(
+        select("win_amd64"),
+        Err(
+            "win_amd64 matches wheels with different tags; choose one of: \
+             cp311-cp311-win_amd64, cp312-cp312-win_amd64"
+                .to_string()
+        )
+    );
+    assert!(select("musllinux")
+        .unwrap_err()
+        .starts_with("no wheel matching musllinux; available: "));
+}
+
+#[test]
+fn sdist_selection_prefers_tar_gz() {
+    let index = MockIndex::start();
+    index.serve_artifacts(
+        "example",
+        "1.0.0",
+        &[
+            "example-1.0.0.zip",
+            "example-1.0.0.tar.gz",
+            "example-1.0.0-py3-none-any.whl",
+        ],
+    );
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        Some("sdist".to_string()),
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        project.distribution().unwrap().filename,
+        "example-1.0.0.tar.gz"
+    );
+
+    // every sdist is listed, each by its format
+    let list = |artifacts: u8| {
+        let project = Project::new(
+            "example".to_string(),
+            Some("1.0.0".to_string()),
+            None,
+            ProjectOptions {
+                index: index.url.clone(),
+                ..Default::default()
+            },
+        );
+        let mut fields = display_fields();
+        fields.artifacts = artifacts;
+        let mut out = Vec::new();
+        kayak::ui::text::display(project, fields, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    assert_eq!(
+        list(2),
+        "Distribution Types\n  sdist (zip)\n  sdist\n  py3-none-any\n"
+    );
+    assert!(list(1).starts_with("Distribution Types\n  sdists and "));
+}
+
+#[test]
+fn failed_selection_lists_available_distributions() {
+    let index = MockIndex::start();
+    index.serve_artifacts(
+        "example",
+        "1.0.0",
+        &[
+            "example-1.0.0.tar.gz",
+            "example-1.0.0-cp39-cp39-win_amd64.whl",
+        ],
+    );
+    index.serve_artifacts("example", "2.0.0", &["example-2.0.0.tar.gz"]);
+    let error = |version: &str, dist: Option<&str>| {
+        let mut project = Project::new(
+            "example".to_string(),
+            Some(version.to_string()),
+            dist.map(str::to_string),
+            ProjectOptions {
+                index: index.url.clone(),
+                ..Default::default()
+            },
+        );
+        format!("{}", project.distribution().unwrap_err())
+    };
+    assert_eq!(
+        error("1.0.0", Some("cp39-cp39-win32")),
+        "no wheel matching cp39-cp39-win32; available: cp39-cp39-win_amd64, sdist"
+    );
+    assert_eq!(error("2.0.0", None), "no wheel to select; available: sdist");
+}
+
+#[test]
+fn project_from_url_bypasses_index_paths() {
+    let index = MockIndex::start();
+    let response = index.version_json("example", "1.0.0", &["example-1.0.0-py3-none-any.whl"]);
+    index.serve(
+        "/mirror/example-1.0.0.json",
+        response.to_string().into_bytes(),
+    );
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+
+    let mut project = Project::from_url(
+        &format!("{}/mirror/example-1.0.0.json", index.url),
+        options.clone(),
+    )
+    .unwrap();
+    assert_eq!(project.package_selector(), "example");
+    assert_eq!(project.version().unwrap().version, "1.0.0");
+    assert_eq!(
+        project.distribution().unwrap().filename,
+        "example-1.0.0-py3-none-any.whl"
+    );
+    assert_eq!(index.hits("/pypi/example/1.0.0/json"), 0);
+
+    let missing = Project::from_url(
+        &format!("{}/mirror/missing.json", index.url),
+        options.clone(),
+    );
+    assert!(missing.is_err());
+    assert!(Project::from_url("data:text/plain,example", options).is_err());
+}
+
+#[test]
+fn project_from_url_accepts_a_project_response() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", serde_json::json!({}));
+    let mut project = Project::from_url(
+        &format!("{}/pypi/example/json", index.url),
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(project.is_package_fetched());
+    // the response does not describe any version, so one is still selected from the index
+    assert_eq!(project.version().unwrap().version, "1.0.0");
+}
+
+#[test]
+fn missing_version_lists_available_versions() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", serde_json::json!({}));
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("9.9.9".to_string()),
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    let err = project.version().unwrap_err();
+    assert_eq!(
+        format!("{err:#}"),
+        "version 9.9.9 not found; available: 1.0.0: NotFound"
+    );
+
+    // once the project is known, a missing version never reaches the index
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("2.0".to_string()),
+        None,
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    project.package().unwrap();
+    assert!(project.version().is_err());
+    assert_eq!(index.hits("/pypi/example/2.0/json"), 0);
+}
+
+#[test]
+fn only_missing_versions_are_passed_over() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", serde_json::json!({}));
+    let mut project = index.version_json("example", "1.0.0", &[]);
+    project["releases"] = serde_json::json!({"1.0.0": [], "2.0.0": [], "3.0.0": []});
+    index.serve("/pypi/example/json", project.to_string().into_bytes());
+    let version = |index: &MockIndex| {
+        Project::new(
+            "example".to_string(),
+            None,
+            None,
+            ProjectOptions {
+                index: index.url.clone(),
+                ..Default::default()
+            },
+        )
+        .version()
+        .map(|v| v.version.clone())
+        .map_err(|err| format!("{err:#}"))
+    };
+
+    // neither 3.0.0 nor 2.0.0 has metadata on the index
+    assert_eq!(version(&index), Ok("1.0.0".to_string()));
+
+    index.serve("/pypi/example/2.0.0/json", b"not json".to_vec());
+    let err = version(&index).unwrap_err();
+    assert!(!err.contains("NoValidVersion"), "{err}");
+}
+
+#[test]
+fn simple_api_lists_files_by_version() {
+    let index = MockIndex::start();
+    let file = |filename: &str, extra: serde_json::Value| {
+        let mut file = serde_json::json!({
+            "filename": filename,
+            "url": format!("../../files/{filename}"),
+            "hashes": {"sha256": "0".repeat(64)},
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            file[key] = value.clone();
+        }
+        file
+    };
+    index.serve(
+        "/simple/my-example/",
+        serde_json::json!({
+            "meta": {"api-version": "1.1"},
+            "name": "my-example",
+            "versions": ["0.9", "1.0.0", "2.0.0"],
+            "files": [
+                file("my_example-1.0.0.tar.gz", serde_json::json!({"requires-python": ">=3.8"})),
+                file(
+                    "my_example-1.0.0-py3-none-any.whl",
+                    serde_json::json!({"upload-time": "2024-01-01T00:00:00.000000Z", "size": 10}),
+                ),
+                file("my-example-2.0.0.zip", serde_json::json!({"yanked": "broken"})),
+                file("notes.txt", serde_json::json!({})),
+            ],
+        })
+        .to_string()
+        .into_bytes(),
+    );
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        api: Api::Simple,
+        ..Default::default()
+    };
+
+    // the newest version is yanked, so the one before it is selected
+    let mut project = Project::new("My.Example".to_string(), None, None, options.clone());
+    let version = project.version().unwrap();
+    assert_eq!(version.version, "1.0.0");
+    assert_eq!(version.requires_python.as_deref(), Some(">=3.8"));
+    let mut files = version
+        .urls
+        .iter()
+        .map(|u| (u.packagetype.as_str(), u.url.as_str()))
+        .collect::<Vec<_>>();
+    files.sort();
+    assert_eq!(
+        files,
+        [
+            (
+                "bdist_wheel",
+                format!("{}/files/my_example-1.0.0-py3-none-any.whl", index.url).as_str()
+            ),
+            (
+                "sdist",
+                format!("{}/files/my_example-1.0.0.tar.gz", index.url).as_str()
+            ),
+        ]
+    );
+    let wheel = project.distribution().unwrap();
+    assert_eq!(wheel.upload_time, "2024-01-01T00:00:00");
+    assert_eq!(wheel.size, 10);
+    assert_eq!(
+        project
+            .package()
+            .unwrap()
+            .ordered_versions()
+            .iter()
+            .map(|v| v.normalize())
+            .collect::<Vec<_>>(),
+        ["0.9", "1.0.0", "2.0.0"]
+    );
+
+    let mut yanked = Project::new(
+        "my-example".to_string(),
+        Some("2.0.0".into()),
+        None,
+        options,
+    );
+    let version = yanked.version().unwrap();
+    assert!(version.yanked);
+    assert_eq!(version.yanked_reason.as_deref(), Some("broken"));
+    assert_eq!(index.hits("/pypi/my-example/json"), 0);
+}
+
+#[test]
+fn simple_api_files_without_a_sha256_are_saved_unverified() {
+    let index = MockIndex::start();
+    index.serve(
+        "/simple/example/",
+        serde_json::json!({
+            "meta": {"api-version": "1.1"},
+            "name": "example",
+            "versions": ["1.0.0"],
+            "files": [{
+                "filename": "example-1.0.0.tar.gz",
+                "url": "../../files/example-1.0.0.tar.gz",
+                "hashes": {"md5": "0".repeat(32)},
+            }],
+        })
+        .to_string()
+        .into_bytes(),
+    );
+    index.serve("/files/example-1.0.0.tar.gz", b"example".to_vec());
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        Some("sdist".to_string()),
+        ProjectOptions {
+            index: index.url.clone(),
+            api: Api::Simple,
+            ..Default::default()
+        },
+    );
+    let distribution = project.distribution().unwrap();
+    assert_eq!(distribution.digests.sha256, None);
+    let err = distribution.verify().unwrap_err();
+    assert!(
+        matches!(
+            err.downcast_ref(),
+            Some(kayak::warehouse::Error::NoDigest(f)) if f == "example-1.0.0.tar.gz"
+        ),
+        "{err}"
+    );
+
+    let dir = std::env::temp_dir().join(format!("kayak-{}-no-digest", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let saved = distribution.download(&dir).unwrap();
+    assert_eq!(std::fs::read(&saved).unwrap(), b"example");
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use common::{display_fields, kayak, wheel, MockIndex};
+use kayak::ui::pretty;
+use kayak::{Project, ProjectOptions};
+use serde_json::json;
+use std::iter;
+
+#[test]
+fn short_dependencies_share_lines() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({
+            "requires_dist": ["a", "b", "c", "d", "e", "f; extra == 'more'", "g; extra == 'more'"],
+        }),
+    );
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let mut fields = display_fields();
+    fields.dependencies = true;
+
+    let mut out = Vec::new();
+    pretty::display(
+        Project::new("example".into(), None, None, options),
+        fields,
+        &mut out,
+    )
+    .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+
+    assert!(lines.iter().any(|l| l.contains("a  ") && l.contains(" b")));
+    // an extra's dependencies stay under its header
+    let header = lines.iter().position(|l| l.contains("[more]")).unwrap();
+    assert!(!lines[header].contains('f'));
+    assert!(lines[header + 1].contains('f') && lines[header + 1].contains('g'));
+}
+
+#[test]
+fn oversized_sections_are_clipped() {
+    // more lines than a layout constraint can hold
+    let entry_points = iter::once("[example.plugins]".to_string())
+        .chain((0..70_000).map(|e| format!("plugin{e} = example.plugins:plugin{e}")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let index = MockIndex::start();
+    index.serve_project(
+        "example",
+        "1.0.0",
+        "example-1.0.0-py3-none-any.whl",
+        wheel(
+            "example-1.0.0.dist-info",
+            &[("example-1.0.0.dist-info/entry_points.txt", &entry_points)],
+        ),
+    );
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let mut fields = display_fields();
+    fields.entry_points = true;
+
+    let mut out = Vec::new();
+    pretty::display(
+        Project::new("example".into(), None, None, options),
+        fields,
+        &mut out,
+    )
+    .unwrap();
+    assert!(String::from_utf8(out)
+        .unwrap()
+        .contains("[example.plugins]"));
+}
+
+#[test]
+fn unrecognized_classifiers_are_still_displayed() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({
+            "classifiers": ["Programming Language :: Python", "Programming Language :: Not A Language"],
+        }),
+    );
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let mut fields = display_fields();
+    fields.classifiers = true;
+
+    let mut out = Vec::new();
+    pretty::display(
+        Project::new("example".into(), None, None, options),
+        fields,
+        &mut out,
+    )
+    .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Programming Language :: Python "), "{out}");
+    assert!(out.contains("Programming Language :: Not A Language (unrecognized)"));
+}
+
+#[test]
+fn off_screen_render_is_only_as_tall_as_the_details() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", json!({"summary": "An example"}));
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let mut fields = display_fields();
+    fields.name = true;
+    fields.summary = true;
+
+    let mut project = Project::new("example".into(), None, None, options);
+    let buffer = pretty::render_buffer(&mut project, &fields, 40).unwrap();
+    assert!(buffer.area.height > 0 && buffer.area.height < 10);
+    let last = (0..40)
+        .map(|x| buffer[(x, buffer.area.height - 1)].symbol())
+        .collect::<String>();
+    assert!(!last.trim().is_empty());
+}
+
+#[test]
+fn long_details_are_never_cut_off() {
+    let readme = (1..=1500)
+        .map(|n| format!("line {n}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", json!({"description": readme}));
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let mut fields = display_fields();
+    fields.readme = 1;
+    fields.width = Some(40);
+
+    let mut project = Project::new("example".into(), None, None, options.clone());
+    let buffer = pretty::render_buffer(&mut project, &fields, 40).unwrap();
+    assert_eq!(buffer.area.height, 1500);
+
+    let display = |fields| {
+        let mut out = Vec::new();
+        pretty::display(
+            Project::new("example".into(), None, None, options.clone()),
+            fields,
+            &mut out,
+        )
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    let out = display(fields.clone());
+    assert_eq!(out.lines().last(), Some("line 1500"), "{out}");
+
+    // a page this wide is too big for ratatui before all of the readme is drawn
+    fields.width = Some(80);
+    let out = display(fields);
+    assert_eq!(
+        out.lines().last(),
+        Some("[cut off here; --format text shows every detail]"),
+        "{out}"
+    );
+}
+
+#[test]
+fn piped_output_is_plain_text() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", json!({"summary": "An example"}));
+    let output = kayak(&index.url)
+        .args(["--format", "pretty", "example", "1.0.0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let out = String::from_utf8(output.stdout).unwrap();
+    assert!(out.contains("An example"), "{out}");
+    assert!(!out.contains('\x1b'), "{out:?}");
+    assert!(out.lines().all(|l| l.chars().count() <= 80), "{out}");
+}
+
+#[test]
+fn width_overrides_the_terminal() {
+    let readme = "word ".repeat(40);
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({
+            "summary": "An example",
+            "description": readme,
+            "description_content_type": "text/markdown",
+        }),
+    );
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let mut fields = display_fields();
+    fields.summary = true;
+    fields.width = Some(50);
+    let mut out = Vec::new();
+    pretty::display(
+        Project::new("example".into(), None, None, options.clone()),
+        fields.clone(),
+        &mut out,
+    )
+    .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.lines().map(|l| l.chars().count()).max(), Some(50));
+
+    // a styled readme is wrapped to the width too, not counting its escape codes
+    fields.summary = false;
+    fields.readme = 2;
+    fields.color = true;
+    fields.width = Some(30);
+    let mut out = Vec::new();
+    kayak::ui::text::display(
+        Project::new("example".into(), None, None, options),
+        fields,
+        &mut out,
+    )
+    .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let visible = |line: &str| {
+        let mut count = 0;
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            } else {
+                count += 1;
+            }
+        }
+        count
+    };
+    assert!(out.lines().count() > 5, "{out}");
+    assert!(out.lines().all(|l| visible(l) <= 30), "{out}");
+}
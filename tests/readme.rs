@@ -0,0 +1,60 @@
+use kayak::ui::rst_to_markdown;
+use kayak::ui::text::page;
+
+#[test]
+fn rst_titles_become_headings() {
+    let rst = "=======\nExample\n=======\n\nIntro\n\nInstalling\n----------\n\nUsage\n-----\n";
+    assert_eq!(
+        rst_to_markdown(rst),
+        "# Example\n\nIntro\n\n## Installing\n\n## Usage"
+    );
+}
+
+#[test]
+fn rst_directives_are_dropped() {
+    let rst = "Before\n\n.. image:: https://example.com/badge.svg\n   :target: https://example.com\n\n.. _docs: https://example.com\n\nAfter...\n";
+    assert_eq!(rst_to_markdown(rst), "Before\n\nAfter...");
+}
+
+#[test]
+fn rst_code_directives_become_fenced_code() {
+    let rst = "Usage\n\n.. code-block:: python\n   :linenos:\n\n   import example\n\n   if example:\n       example.run()\n\nThen\n\n.. code::\n\n  $ example\n";
+    assert_eq!(
+        rst_to_markdown(rst),
+        "Usage\n\n```python\nimport example\n\nif example:\n    example.run()\n```\n\nThen\n\n```\n$ example\n```"
+    );
+}
+
+#[test]
+fn rst_inline_markup_is_translated() {
+    assert_eq!(
+        rst_to_markdown(
+            "Call ``main()`` or :func:`run`, see `the docs <https://example.com>`_ and docs_."
+        ),
+        "Call `main()` or `run`, see [the docs](https://example.com) and docs_."
+    );
+    assert_eq!(rst_to_markdown("For example::"), "For example:");
+}
+
+#[test]
+fn short_output_is_not_paged() {
+    let mut out = Vec::new();
+    page("one\ntwo\n", "true", 10, &mut out).unwrap();
+    assert_eq!(out, b"one\ntwo\n");
+}
+
+#[test]
+fn long_output_is_paged() {
+    let long = "line\n".repeat(50);
+    let mut out = Vec::new();
+    // the pager consumes the output instead
+    page(&long, "true", 10, &mut out).unwrap();
+    assert!(out.is_empty());
+
+    // without a working pager the output is still written
+    page(&long, "", 10, &mut out).unwrap();
+    assert_eq!(out, long.as_bytes());
+    out.clear();
+    page(&long, "no-such-pager --flag", 10, &mut out).unwrap();
+    assert_eq!(out, long.as_bytes());
+}
@@ -0,0 +1,664 @@
+mod common;
+
+use chrono::{TimeDelta, TimeZone, Utc};
+use common::{display_fields, kayak, MockIndex};
+use kayak::ui::format_age;
+use kayak::ui::text;
+use kayak::{warehouse, Field, Project, ProjectOptions};
+use serde_json::json;
+
+fn display(index: &MockIndex, name: &str, version: &str, fields: kayak::DisplayFields) -> String {
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    let mut out = Vec::new();
+    text::display(
+        Project::new(name.to_string(), Some(version.to_string()), None, options),
+        fields,
+        &mut out,
+    )
+    .unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn yanked_reason_is_displayed() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({"yanked": true, "yanked_reason": "CVE-2024-0001, use 1.0.1"}),
+    );
+    let mut fields = display_fields();
+    fields.name = true;
+
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields),
+        "example@1.0.0 [YANKED]\n  yanked: CVE-2024-0001, use 1.0.1\n"
+    );
+}
+
+#[test]
+fn legacy_artifacts_are_displayed() {
+    let index = MockIndex::start();
+    index.serve_artifacts(
+        "example",
+        "0.1.0",
+        &["example-0.1.0-py2.7.egg", "example-0.1.0-py3.4.egg"],
+    );
+    let mut fields = display_fields();
+    fields.artifacts = 1;
+    assert_eq!(
+        display(&index, "example", "0.1.0", fields),
+        "Distribution Types\n  legacy artifacts\n"
+    );
+
+    let mut fields = display_fields();
+    fields.artifacts = 2;
+    assert_eq!(
+        display(&index, "example", "0.1.0", fields),
+        "Distribution Types\n  bdist_egg example-0.1.0-py2.7.egg\n  bdist_egg example-0.1.0-py3.4.egg\n"
+    );
+}
+
+#[test]
+fn artifact_summary_names_the_pythons_covered() {
+    let index = MockIndex::start();
+    index.serve_artifacts(
+        "example",
+        "0.1.0",
+        &[
+            "example-0.1.0.tar.gz",
+            "example-0.1.0-py2-none-any.whl",
+            "example-0.1.0-cp310-cp310-win_amd64.whl",
+            "example-0.1.0-cp39-cp39-win_amd64.whl",
+            "example-0.1.0-cp310-cp310-manylinux_2_17_x86_64.whl",
+        ],
+    );
+    let mut fields = display_fields();
+    fields.artifacts = 1;
+    assert_eq!(
+        display(&index, "example", "0.1.0", fields),
+        "Distribution Types\n  sdist and pure wheel for py2 and platform-specific wheels for cp39, cp310\n"
+    );
+}
+
+#[test]
+fn malformed_wheel_names_are_displayed() {
+    let index = MockIndex::start();
+    index.serve_artifacts(
+        "example",
+        "0.1.0",
+        &["example-0.1.0-py3-none-any.whl", "example.whl"],
+    );
+    let mut fields = display_fields();
+    fields.artifacts = 1;
+    assert_eq!(
+        display(&index, "example", "0.1.0", fields),
+        "Distribution Types\n  pure wheel and unrecognized wheel\n"
+    );
+
+    let mut fields = display_fields();
+    fields.artifacts = 2;
+    assert_eq!(
+        display(&index, "example", "0.1.0", fields),
+        "Distribution Types\n  py3-none-any\n  bdist_wheel example.whl\n"
+    );
+}
+
+#[test]
+fn from_file_renders_a_saved_response() {
+    let fixture = format!(
+        "{}/tests/fixtures/example-rc.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    // nothing listens on the index, a saved response needs no network
+    let output = kayak("http://127.0.0.1:1")
+        .args(["--format", "text", "--from-file", &fixture])
+        .arg("--license")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "example-rc@1.1.0\n  MIT © author@example.org\n  A project whose newest release is a release candidate\n"
+    );
+}
+
+/// Compare the full details of a saved version response to its golden file of the same name
+///
+/// Set KAYAK_UPDATE_GOLDEN to write the current output to the golden file instead.
+fn assert_golden(fixture: &str, fields: &kayak::DisplayFields) {
+    let fixtures = format!("{}/tests/fixtures", env!("CARGO_MANIFEST_DIR"));
+    let response = std::fs::read_to_string(format!("{fixtures}/{fixture}.json")).unwrap();
+    let version: kayak::warehouse::PackageVersion = serde_json::from_str(&response).unwrap();
+    let rendered = text::format_version_details(&version, None, None, None, fields) + "\n";
+    let golden = format!("{fixtures}/{fixture}.txt");
+    if std::env::var_os("KAYAK_UPDATE_GOLDEN").is_some() {
+        std::fs::write(&golden, &rendered).unwrap();
+    }
+    assert_eq!(rendered, std::fs::read_to_string(&golden).unwrap());
+}
+
+fn every_metadata_field() -> kayak::DisplayFields {
+    let mut fields = display_fields();
+    fields.name = true;
+    fields.time = true;
+    fields.summary = true;
+    fields.license = true;
+    fields.urls = true;
+    fields.keywords = true;
+    fields.classifiers = true;
+    fields.artifacts = 2;
+    fields.dependencies = true;
+    fields.readme = 1;
+    fields
+}
+
+#[test]
+fn golden_yanked() {
+    assert_golden("example-yanked-1.0.0", &every_metadata_field());
+}
+
+#[test]
+fn golden_missing_summary() {
+    assert_golden("example-no-summary-1.0.0", &every_metadata_field());
+}
+
+#[test]
+fn golden_markdown_readme() {
+    // the readme is rendered from its markdown, styles and all
+    let mut fields = every_metadata_field();
+    fields.readme = 2;
+    fields.color = true;
+    assert_golden("example-markdown-1.0.0", &fields);
+}
+
+#[test]
+fn fields_are_displayed_in_the_given_order() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({"summary": "An example", "license": "MIT", "requires_dist": ["other"]}),
+    );
+    let mut fields = display_fields();
+    fields.name = true;
+    fields.summary = true;
+    fields.dependencies = true;
+    fields.order = Some(vec![Field::Dependencies, Field::Name, Field::Summary]);
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields),
+        "Dependencies\n  other\nexample@1.0.0\n  An example\n"
+    );
+}
+
+#[test]
+fn legacy_docs_and_download_urls_are_linked() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({
+            "project_urls": {"Docs": "https://example.com/docs"},
+            "docs_url": "https://example.com/docs/",
+            "download_url": "https://example.com/example-1.0.0.tar.gz",
+        }),
+    );
+    index.serve_metadata("example", "2.0.0", json!({"download_url": "UNKNOWN"}));
+    let mut fields = display_fields();
+    fields.urls = true;
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields.clone()),
+        "Links\n  📦  https://pypi.org/project/example/\n  📄  https://example.com/docs\n  ⇩  https://example.com/example-1.0.0.tar.gz\n"
+    );
+    assert_eq!(
+        display(&index, "example", "2.0.0", fields),
+        "Links\n  📦  https://pypi.org/project/example/\n"
+    );
+}
+
+#[test]
+fn people_are_listed_once_with_every_role() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({
+            "author": "Someone",
+            "author_email": "someone@example.com",
+            "maintainer": "Someone",
+            "maintainer_email": "someone@example.com",
+        }),
+    );
+    index.serve_metadata(
+        "example",
+        "2.0.0",
+        json!({"maintainer_email": "\"Other\" <other@example.com>"}),
+    );
+    let mut fields = display_fields();
+    fields.people = true;
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields.clone()),
+        "People\n  Author & Maintainer: Someone <someone@example.com>\n"
+    );
+    assert_eq!(
+        display(&index, "example", "2.0.0", fields),
+        "People\n  Maintainer: Other <other@example.com>\n"
+    );
+}
+
+#[test]
+fn warnings_report_dropped_details() {
+    let index = MockIndex::with_projects(&[]);
+    let mut version =
+        index.version_json("example", "1.0.0", &["example.whl", "example-1.0.0.tar.gz"]);
+    version["info"]["classifiers"] = json!([
+        "Programming Language :: Python",
+        "Programming Language :: Not A Language",
+    ]);
+    index.serve("/pypi/example/1.0.0/json", version.to_string().into_bytes());
+    let run = |args: &[&str]| {
+        kayak(&index.url)
+            .args(["example", "1.0.0", "--classifiers"])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&["--format", "text", "--packages", "--warnings"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr
+            .lines()
+            .filter(|l| l.starts_with("warning: "))
+            .collect::<Vec<_>>(),
+        [
+            "warning: skipped wheel with unparseable name example.whl",
+            "warning: this version has no wheel, only an sdist, which cannot be inspected for \
+             packages, executables, or entry points",
+            &format!(
+                "warning: ignored 1 classifier not recognized by trove-classifiers version {}: \
+                 Programming Language :: Not A Language",
+                trove_classifiers::PYPA_VERSION
+            ),
+        ],
+        "{stderr}"
+    );
+
+    let output = run(&["--format", "text", "--packages"]);
+    assert!(!String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("warning: "));
+    assert!(String::from_utf8(output.stdout).unwrap().contains(
+        "Classifiers\n  Programming Language :: Python\n  \
+         Programming Language :: Not A Language (unrecognized)\n"
+    ));
+    let output = run(&["--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["classifiers"],
+        json!(["Programming Language :: Python"])
+    );
+    assert_eq!(
+        report["unrecognized_classifiers"],
+        json!(["Programming Language :: Not A Language"])
+    );
+
+    let version: kayak::warehouse::PackageVersion = serde_json::from_value(version).unwrap();
+    kayak::warnings::enable();
+    assert_eq!(version.classifiers().len(), 1);
+    // other tests may raise their own warnings once they are enabled for this process
+    assert!(kayak::warnings::take().contains(&format!(
+        "ignored 1 classifier not recognized by trove-classifiers version {}: \
+         Programming Language :: Not A Language",
+        trove_classifiers::PYPA_VERSION
+    )));
+    assert_eq!(
+        version.unrecognized_classifiers(),
+        ["Programming Language :: Not A Language"]
+    );
+}
+
+#[test]
+fn unselected_wheels_are_not_reported_as_missing() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", json!({}));
+    index.serve_artifacts(
+        "example",
+        "1.0.0",
+        &[
+            "example-1.0.0-py3-none-manylinux_2_17_x86_64.whl",
+            "example-1.0.0.tar.gz",
+        ],
+    );
+    let mut project = Project::new(
+        "example".to_string(),
+        Some("1.0.0".to_string()),
+        Some("musllinux".to_string()),
+        ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        },
+    );
+    kayak::warnings::enable();
+    let err = project.import_package().unwrap_err();
+    assert!(
+        format!("{err:#}").contains("no wheel matching musllinux"),
+        "{err:#}"
+    );
+    assert!(!kayak::warnings::take()
+        .iter()
+        .any(|w| w.contains("only an sdist")));
+}
+
+#[test]
+fn vulnerable_latest_version_is_flagged_in_the_listing() {
+    let index = MockIndex::start();
+    let project = index.version_json("example", "1.0.0", &[]);
+    let advisory = |withdrawn: Option<&str>| {
+        json!({
+            "id": "PYSEC-0000-0",
+            "source": "osv",
+            "link": "https://osv.dev/vulnerability/PYSEC-0000-0",
+            "aliases": [],
+            "details": "an example advisory",
+            "summary": null,
+            "fixed_in": ["1.0.1"],
+            "withdrawn": withdrawn,
+        })
+    };
+    let serve = |vulnerabilities| {
+        let body = json!({
+            "info": project["info"],
+            "releases": {"0.9.0": [], "1.0.0": []},
+            "vulnerabilities": vulnerabilities,
+        });
+        index.serve("/pypi/example/json", body.to_string().into_bytes());
+    };
+    let mut fields = display_fields();
+    fields.versions = true;
+    let list = || {
+        let options = ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        text::display(
+            Project::new("example".to_string(), None, None, options),
+            fields.clone(),
+            &mut out,
+        )
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    serve(json!([advisory(None)]));
+    assert_eq!(list(), "1.0.0 [VULNERABLE], 0.9.0\n");
+    serve(json!([advisory(Some("2024-01-01T00:00:00Z"))]));
+    assert_eq!(list(), "1.0.0, 0.9.0\n");
+}
+
+#[test]
+fn lines_hold_one_project_each() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "first",
+        "1.0.0",
+        json!({"summary": "The first\n example", "license": "MIT License\n\nCopyright (c) someone"}),
+    );
+    index.serve_metadata("second", "2.0.0", json!({"yanked": true}));
+    let options = ProjectOptions {
+        index: index.url.clone(),
+        ..Default::default()
+    };
+    // a yanked version is only looked up when it is asked for
+    let projects = [
+        ("first", None),
+        ("missing", None),
+        ("second", Some("2.0.0")),
+    ]
+    .into_iter()
+    .map(|(name, version)| {
+        Project::new(
+            name.to_string(),
+            version.map(str::to_string),
+            None,
+            options.clone(),
+        )
+    })
+    .collect();
+
+    let mut out = Vec::new();
+    text::display_lines(projects, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "first 1.0.0 — The first example (MIT License)");
+    assert!(lines[1].starts_with("missing error: "));
+    assert_eq!(lines[2], "second 2.0.0 [YANKED]");
+}
+
+#[test]
+fn release_and_upload_times_share_a_format() {
+    let index = MockIndex::start();
+    index.serve_artifacts("example", "1.0.0", &["example-1.0.0.tar.gz"]);
+    let mut fields = display_fields();
+    fields.time = true;
+    fields.artifacts = 4;
+    let url = format!("{}/files/example-1.0.0.tar.gz", index.url);
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields.clone()),
+        format!("  2024-01-01 00:00:00 UTC\nDistribution Types\n  sdist 2024-01-01 00:00:00 UTC {url}\n")
+    );
+    fields.date_format = Some("%d %b %Y %H:%M %Z".to_string());
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields),
+        format!(
+            "  01 Jan 2024 00:00 UTC\nDistribution Types\n  sdist 01 Jan 2024 00:00 UTC {url}\n"
+        )
+    );
+    assert!(kayak::ui::is_date_format("%d %b %Y"));
+    assert!(!kayak::ui::is_date_format("%Q"));
+}
+
+#[test]
+fn ages_are_in_the_largest_whole_unit() {
+    let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let age = |delta| format_age(&(now - delta), &now);
+    assert_eq!(age(TimeDelta::seconds(30)), "just now");
+    assert_eq!(age(TimeDelta::minutes(1)), "1 minute ago");
+    assert_eq!(age(TimeDelta::hours(5)), "5 hours ago");
+    assert_eq!(age(TimeDelta::days(13)), "1 week ago");
+    assert_eq!(age(TimeDelta::days(95)), "3 months ago");
+    assert_eq!(age(TimeDelta::days(800)), "2 years ago");
+    // a clock that is behind the index's
+    assert_eq!(age(TimeDelta::days(-2)), "just now");
+}
+
+#[test]
+fn links_are_listed_by_category_then_label() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({
+            "project_urls": {
+                "Tracker": "https://example.com/issues",
+                "Chat": "https://example.com/chat",
+                "Source": "https://example.com/src",
+                "Blog": "https://example.com/blog",
+                "Homepage": "https://example.com",
+                "Changelog": "https://example.com/changes",
+            },
+            "docs_url": "https://example.com/docs",
+        }),
+    );
+    let mut fields = display_fields();
+    fields.urls = true;
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields),
+        "Links\n  📦  https://pypi.org/project/example/\n  🏠  https://example.com\n  📄  https://example.com/docs\n  🐙  https://example.com/src\n  📜  https://example.com/changes\n  🐞  https://example.com/issues\n  🔗  https://example.com/blog\n  🔗  https://example.com/chat\n"
+    );
+}
+
+#[test]
+fn links_can_be_labelled_without_emoji() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({"project_urls": {"Issues": "https://example.com/issues", "Chat": "https://example.com/chat"}}),
+    );
+    let mut fields = display_fields();
+    fields.urls = true;
+    fields.emoji = false;
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields),
+        "Links\n  [index]  https://pypi.org/project/example/\n  [bug]  https://example.com/issues\n  [link]  https://example.com/chat\n"
+    );
+}
+
+#[test]
+fn links_are_categorized_by_whole_words() {
+    let index = MockIndex::start();
+    index.serve_metadata(
+        "example",
+        "1.0.0",
+        json!({"project_urls": {
+            "Docker Image": "https://hub.docker.com/r/example",
+            "Report a problem": "https://example.com/report",
+            "Issue Tracker": "https://example.com/issues",
+            "Docs": "https://example.com/Docs",
+            "Documentation": "https://example.com/docs",
+            "Repository": "https://example.com/src/",
+            "Source": "https://EXAMPLE.com/src",
+        }}),
+    );
+    let mut fields = display_fields();
+    fields.urls = true;
+    fields.emoji = false;
+    assert_eq!(
+        display(&index, "example", "1.0.0", fields),
+        "Links\n  [index]  https://pypi.org/project/example/\n  \
+         [docs]  https://example.com/Docs\n  [docs]  https://example.com/docs\n  \
+         [source]  https://example.com/src/\n  [bug]  https://example.com/issues\n  \
+         [link]  https://hub.docker.com/r/example\n  [link]  https://example.com/report\n"
+    );
+}
+
+#[test]
+fn projects_without_releases_say_so() {
+    let index = MockIndex::start();
+    let path = format!(
+        "{}/tests/fixtures/example-reserved.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    index.serve("/pypi/example-reserved/json", std::fs::read(path).unwrap());
+    let project = |version: Option<&str>| {
+        let options = ProjectOptions {
+            index: index.url.clone(),
+            ..Default::default()
+        };
+        Project::new(
+            "example-reserved".to_string(),
+            version.map(str::to_string),
+            None,
+            options,
+        )
+    };
+
+    let mut fields = display_fields();
+    fields.versions = true;
+    let mut out = Vec::new();
+    text::display(project(None), fields, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "no releases\n");
+
+    for version in [None, Some("1.0.0")] {
+        let err = project(version).version().map(|_| ()).unwrap_err();
+        assert!(
+            matches!(
+                err.downcast_ref::<warehouse::Error>(),
+                Some(warehouse::Error::NoReleases)
+            ),
+            "{err:?}"
+        );
+    }
+}
+
+#[test]
+fn downloads_are_displayed_only_when_they_can_be_counted() {
+    let index = MockIndex::start();
+    index.serve_metadata("example", "1.0.0", json!({}));
+    index.serve(
+        "/stats/example",
+        json!({
+            "data": {"last_day": 12, "last_month": 1234567, "last_week": 890},
+            "package": "example",
+            "type": "recent_downloads",
+        })
+        .to_string()
+        .into_bytes(),
+    );
+    let display = |source: &str| {
+        let options = ProjectOptions {
+            index: index.url.clone(),
+            downloads_source: Some(format!("{}{source}", index.url.trim_end_matches('/'))),
+            ..Default::default()
+        };
+        let mut fields = display_fields();
+        fields.downloads = true;
+        let mut out = Vec::new();
+        text::display(
+            Project::new(
+                "Example".to_string(),
+                Some("1.0.0".to_string()),
+                None,
+                options,
+            ),
+            fields,
+            &mut out,
+        )
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    assert_eq!(
+        display("/stats/{project}"),
+        "Recent Downloads\n  12 last day, 890 last week, 1,234,567 last month\n"
+    );
+    assert_eq!(display("/missing/{project}"), "");
+}
+
+#[test]
+fn downloads_on_other_indexes_need_their_own_source() {
+    let index = MockIndex::with_projects(&[]);
+    index.serve_metadata("example", "1.0.0", json!({}));
+    index.serve(
+        "/stats/example",
+        json!({"data": {"last_day": 1, "last_month": 3, "last_week": 2}})
+            .to_string()
+            .into_bytes(),
+    );
+    let run = |args: &[&str]| {
+        kayak(&index.url)
+            .args(["--format", "text"])
+            .args(["-qq", "--warnings", "--downloads"])
+            .args(args)
+            .args(["example", "1.0.0"])
+            .output()
+            .unwrap()
+    };
+    let output = run(&[]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty(), "{output:?}");
+    assert!(output.stderr.is_empty(), "{output:?}");
+
+    let source = format!("{}/stats/{{project}}", index.url.trim_end_matches('/'));
+    let output = run(&["--downloads-source", &source]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Recent Downloads\n  1 last day, 2 last week, 3 last month\n"
+    );
+}
@@ -0,0 +1,40 @@
+use kayak::ui::yaml::to_yaml;
+use serde_json::json;
+
+#[test]
+fn nested_report() {
+    let report = json!({
+        "name": "example",
+        "version": "1.0",
+        "urls": [
+            {"label": "Package Index", "url": "https://pypi.org/project/example/"},
+        ],
+        "dependencies": {
+            "requires_python": ">=3.8",
+            "requires_dist": ["idna (<4,>=2.5)", "pysocks; extra == \"socks\""],
+        },
+        "keywords": [],
+    });
+    assert_eq!(
+        to_yaml(&report),
+        "name: example\n\
+         version: \"1.0\"\n\
+         urls:\n\
+         - label: Package Index\n  \
+           url: https://pypi.org/project/example/\n\
+         dependencies:\n  \
+           requires_python: \">=3.8\"\n  \
+           requires_dist:\n  \
+           - idna (<4,>=2.5)\n  \
+           - pysocks; extra == \"socks\"\n\
+         keywords: []"
+    );
+}
+
+#[test]
+fn ambiguous_scalars_are_quoted() {
+    assert_eq!(
+        to_yaml(&json!(["yes", "1.5", "- dash", "a: b", "multi\nline", ""])),
+        "- \"yes\"\n- \"1.5\"\n- \"- dash\"\n- \"a: b\"\n- \"multi\\nline\"\n- \"\""
+    );
+}